@@ -0,0 +1,26 @@
+//! Runs a few tensor operations using nothing but the prelude.
+
+use MIT::prelude::*;
+
+fn main() -> MlResult<()> {
+    let weights = Tensor::<f32>::new(vec![vec![0.5, -1.0], vec![2.0, 0.25]])?;
+    let inputs = Tensor::<f32>::new(vec![vec![1.0, 2.0], vec![3.0, 4.0]])?;
+
+    let hidden = ops!(inputs, Matmul, weights)?;
+    let shifted = scalar_ops!(hidden, Add, 1.0)?;
+    let activated = ops!(shifted, Square)?;
+    let (top, _) = ops!(activated, Topk, 1, true)?;
+    let (max, _) = ops!(activated, Matmax, Some(-1), false)?;
+
+    assert_shape_eq!(top, [2, 1]);
+    assert_tensor_close!(top.view(&[2])?, max);
+    assert_tensor_close!(activated.add(&activated)?, scalar_ops!(activated, Mul, 2.0)?);
+
+    match ops!(inputs, Matmul, Tensor::<f32>::new(vec![vec![1.0, 2.0, 3.0]])?) {
+        Err(MlError::TensorError(TensorError::OpFailed { op: "Matmul", .. })) => {}
+        other => panic!("expected a shape error, got {:?}", other.map(|t| t.shape().to_vec())),
+    }
+
+    println!("{:?}", activated);
+    Ok(())
+}
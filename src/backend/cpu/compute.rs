@@ -1,6 +1,11 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+
 use super::parallel::ParallelExecutor;
 
 const PARALLEL_THRESHOLD: usize = 1024;
+const PAIRWISE_BLOCK: usize = 128;
+
+static PAIRWISE_SUM_THRESHOLD: AtomicUsize = AtomicUsize::new(4096);
 
 #[derive(Debug)]
 pub struct CpuCompute {
@@ -192,31 +197,16 @@ impl CpuCompute {
     }
 
     // Optimized reduction operations
+    /// Sums all elements
+    ///
+    /// Slices with at least [`pairwise_sum_threshold`] elements are summed pairwise,
+    /// see [`set_pairwise_sum_threshold`].
     pub fn sum(&self, a: &[f32]) -> f32 {
-        let mut sum = 0.0;
-        let chunks = a.len() / 8;
-        let remainder = a.len() % 8;
-
-        // Process 8 elements at a time
-        for i in 0..chunks {
-            let idx = i * 8;
-            sum += a[idx]
-                + a[idx + 1]
-                + a[idx + 2]
-                + a[idx + 3]
-                + a[idx + 4]
-                + a[idx + 5]
-                + a[idx + 6]
-                + a[idx + 7];
-        }
-
-        // Handle remaining elements
-        let start = chunks * 8;
-        for i in 0..remainder {
-            sum += a[start + i];
+        if a.len() >= pairwise_sum_threshold() {
+            pairwise_sum(a)
+        } else {
+            sequential_sum(a)
         }
-
-        sum
     }
 
     pub fn mean(&self, a: &[f32]) -> f32 {
@@ -227,6 +217,58 @@ impl CpuCompute {
     }
 }
 
+/// Returns the length from which [`CpuCompute::sum`] switches to pairwise summation
+pub fn pairwise_sum_threshold() -> usize {
+    PAIRWISE_SUM_THRESHOLD.load(Ordering::Relaxed)
+}
+
+/// Sets the length from which [`CpuCompute::sum`] switches to pairwise summation
+///
+/// A sequential sum accumulates rounding error proportional to the number of elements,
+/// while a pairwise sum, which recursively sums both halves, grows it only with the log
+/// of that number. The default crossover of 4096 elements keeps small sums on the
+/// slightly faster sequential loop, where the error is still negligible for `f32`.
+/// Pass `usize::MAX` to always sum sequentially, or `0` to always sum pairwise.
+pub fn set_pairwise_sum_threshold(threshold: usize) {
+    PAIRWISE_SUM_THRESHOLD.store(threshold, Ordering::Relaxed);
+}
+
+/// Sums both halves recursively, down to blocks small enough to sum sequentially
+fn pairwise_sum(a: &[f32]) -> f32 {
+    if a.len() <= PAIRWISE_BLOCK {
+        return sequential_sum(a);
+    }
+    let (left, right) = a.split_at(a.len() / 2);
+    pairwise_sum(left) + pairwise_sum(right)
+}
+
+fn sequential_sum(a: &[f32]) -> f32 {
+    let mut sum = 0.0;
+    let chunks = a.len() / 8;
+    let remainder = a.len() % 8;
+
+    // Process 8 elements at a time
+    for i in 0..chunks {
+        let idx = i * 8;
+        sum += a[idx]
+            + a[idx + 1]
+            + a[idx + 2]
+            + a[idx + 3]
+            + a[idx + 4]
+            + a[idx + 5]
+            + a[idx + 6]
+            + a[idx + 7];
+    }
+
+    // Handle remaining elements
+    let start = chunks * 8;
+    for i in 0..remainder {
+        sum += a[start + i];
+    }
+
+    sum
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -250,6 +292,23 @@ mod tests {
         assert_eq!(div, vec![4.0, 2.5, 2.0]);
     }
 
+    #[test]
+    fn test_pairwise_sum_accuracy() {
+        let a = vec![0.1f32; 1 << 22];
+        let exact = 0.1f32 as f64 * a.len() as f64;
+        let relative_error = |sum: f32| ((sum as f64 - exact) / exact).abs();
+
+        // the sequential loop drifts by far more than f32 precision, pairwise summation does not
+        assert!(relative_error(sequential_sum(&a)) > 1e-3);
+        assert!(relative_error(pairwise_sum(&a)) < 1e-6);
+        assert_eq!(CpuCompute::new().sum(&a), pairwise_sum(&a));
+
+        let small = [1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0, 9.0];
+        assert_eq!(pairwise_sum(&small), 45.0);
+        assert_eq!(pairwise_sum(&[]), 0.0);
+        assert_eq!(pairwise_sum(&(1..=1000).map(|x| x as f32).collect::<Vec<_>>()), 500500.0);
+    }
+
     #[test]
     fn test_matmul() {
         let compute = CpuCompute::new();
@@ -8,7 +8,7 @@ mod compute;
 mod core;
 mod parallel;
 
-pub use compute::CpuCompute;
+pub use compute::{pairwise_sum_threshold, set_pairwise_sum_threshold, CpuCompute};
 pub use core::CpuCore;
 
 #[derive(Debug)]
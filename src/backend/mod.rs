@@ -4,7 +4,7 @@ mod device;
 mod feature;
 pub use device::{Device, DeviceType};
 mod cpu;
-pub use cpu::CpuBackend;
+pub use cpu::{pairwise_sum_threshold, set_pairwise_sum_threshold, CpuBackend};
 use crate::MlResult;
 
 
@@ -1,12 +1,18 @@
 use std::fmt::{Display, Formatter};
-use crate::tensor::TensorError;
+use crate::tensor::{FormatError, TensorError};
 
 pub mod tensor;
 pub mod backend;
+pub mod nn;
+pub mod testing;
+pub mod prelude;
+#[cfg(feature = "enable_backpropagation")]
+pub mod optim;
 
 #[derive(Debug)]
 pub enum MlError {
     TensorError(TensorError),
+    FormatError(FormatError),
     StringError(String),
 }
 
@@ -14,18 +20,33 @@ impl Display for MlError {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         match self {
             MlError::TensorError(e) => write!(f, "Tensor error: {}", e),
+            MlError::FormatError(e) => write!(f, "Format error: {}", e),
             MlError::StringError(s) => write!(f, "{}", s),
         }
     }
 }
 
-impl std::error::Error for MlError {}
+impl std::error::Error for MlError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            MlError::TensorError(e) => Some(e),
+            MlError::FormatError(e) => Some(e),
+            MlError::StringError(_) => None,
+        }
+    }
+}
 
 impl From<TensorError> for MlError {
     fn from(error: TensorError) -> Self {
         MlError::TensorError(error)
     }
 }
+impl From<FormatError> for MlError {
+    fn from(error: FormatError) -> Self {
+        MlError::FormatError(error)
+    }
+}
+
 impl From<MlError> for TensorError {
     fn from(val: MlError) -> Self {
         match val {
@@ -0,0 +1,135 @@
+use crate::{MlError, MlResult};
+use crate::tensor::{ArcTensor, Function, Matmul, Tensor, TensorBase, TensorError};
+
+/// Computes `softmax(q @ kᵀ / sqrt(d) + mask) @ v`
+///
+/// All operands share the same leading (batch, head) dimensions, e.g. `[B, H, L, D]`.
+///
+/// # Arguments
+/// * `q` - The queries with shape `[..., L, D]`
+/// * `k` - The keys with shape `[..., S, D]`
+/// * `v` - The values with shape `[..., S, Dv]`
+/// * `mask` - Optional mask broadcastable to `[..., L, S]`; positions with a nonzero
+///   value are excluded by filling their scores with `-inf`
+///
+/// # Returns
+/// * `Ok(ArcTensor)` with shape `[..., L, Dv]`
+/// * `Err(MlError::TensorError)` naming the operand whose shape does not match
+pub fn scaled_dot_product_attention(
+    q: &dyn TensorBase<f32>,
+    k: &dyn TensorBase<f32>,
+    v: &dyn TensorBase<f32>,
+    mask: Option<&dyn TensorBase<f32>>,
+) -> MlResult<ArcTensor<f32>> {
+    let ndim = q.shape().len();
+    if ndim < 2 {
+        return Err(attention_error(format!("query must have at least 2 dimensions, got shape {:?}", q.shape())));
+    }
+    let (batch, d) = (&q.shape()[..ndim - 2], q.shape()[ndim - 1]);
+    if k.shape().len() != ndim || &k.shape()[..ndim - 2] != batch || k.shape()[ndim - 1] != d {
+        return Err(attention_error(format!("key shape {:?} does not match query shape {:?}", k.shape(), q.shape())));
+    }
+    if v.shape().len() != ndim || &v.shape()[..ndim - 2] != batch || v.shape()[ndim - 2] != k.shape()[ndim - 2] {
+        return Err(attention_error(format!("value shape {:?} does not match key shape {:?}", v.shape(), k.shape())));
+    }
+
+    let key_t = k.mT()?;
+    let scores = Matmul::new(q, Some(&*key_t))?.forward()?;
+    let scale = 1.0 / (d as f32).sqrt();
+    let mut scores = Tensor::<f32>::from_vec(scores.data().iter().map(|&x| x * scale).collect(), scores.shape())?;
+
+    if let Some(mask) = mask {
+        let mask = Tensor::<bool>::from_vec(mask.data().iter().map(|&m| m != 0.0).collect(), mask.shape())?;
+        scores = scores.masked_fill(&*mask, f32::NEG_INFINITY).map_err(|_| {
+            attention_error(format!("mask shape {:?} does not broadcast to scores shape {:?}", mask.shape(), scores.shape()))
+        })?;
+    }
+
+    let weights = scores.softmax(-1)?;
+    Matmul::new(&*weights, Some(v))?.forward()
+}
+
+fn attention_error(reason: String) -> MlError {
+    MlError::TensorError(TensorError::InvalidOperation {
+        op: "scaled_dot_product_attention",
+        reason,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::MlResult;
+    use crate::nn::scaled_dot_product_attention;
+    use crate::tensor::{Rng, Tensor, TensorBase};
+    use crate::testing::assert_close;
+
+    fn causal_mask(len: usize) -> Vec<f32> {
+        (0..len * len).map(|i| if i % len > i / len { 1.0 } else { 0.0 }).collect()
+    }
+
+    #[test]
+    fn test_attention_hand_computed() -> MlResult<()> {
+        let q = Tensor::<f32>::from_vec(vec![1.0, 0.0], &[1, 1, 1, 2])?;
+        let k = Tensor::<f32>::from_vec(vec![1.0, 0.0, 0.0, 1.0], &[1, 1, 2, 2])?;
+        let v = Tensor::<f32>::from_vec(vec![1.0, 2.0, 3.0, 4.0], &[1, 1, 2, 2])?;
+        let result = scaled_dot_product_attention(&*q, &*k, &*v, None)?;
+
+        // scores = [1/sqrt(2), 0]
+        let w0 = 1.0 / (1.0 + (-1.0 / 2f32.sqrt()).exp());
+        assert_close(&*result, &[w0 + 3.0 * (1.0 - w0), 2.0 * w0 + 4.0 * (1.0 - w0)], &[1, 1, 1, 2], 1e-6);
+        Ok(())
+    }
+
+    #[test]
+    fn test_attention_matches_reference() -> MlResult<()> {
+        let (b, h, l, d) = (2, 2, 3, 4);
+        let mut rng = Rng::new(3);
+        let mut random = |n: usize| (0..n).map(|_| rng.normal(0.0, 1.0)).collect::<Vec<f32>>();
+        let (q, k, v) = (random(b * h * l * d), random(b * h * l * d), random(b * h * l * d));
+        let mask = causal_mask(l);
+
+        let shape = [b, h, l, d];
+        let result = scaled_dot_product_attention(
+            &*Tensor::<f32>::from_vec(q.clone(), &shape)?,
+            &*Tensor::<f32>::from_vec(k.clone(), &shape)?,
+            &*Tensor::<f32>::from_vec(v.clone(), &shape)?,
+            Some(&*Tensor::<f32>::from_vec(mask.clone(), &[l, l])?),
+        )?;
+
+        let mut expected = Vec::with_capacity(b * h * l * d);
+        for m in 0..b * h {
+            for i in 0..l {
+                let scores: Vec<f32> = (0..l).map(|j| {
+                    let dot: f32 = (0..d).map(|x| q[(m * l + i) * d + x] * k[(m * l + j) * d + x]).sum();
+                    if mask[i * l + j] != 0.0 { f32::NEG_INFINITY } else { dot / (d as f32).sqrt() }
+                }).collect();
+                let max = scores.iter().cloned().fold(f32::NEG_INFINITY, f32::max);
+                let total: f32 = scores.iter().map(|s| (s - max).exp()).sum();
+
+                for x in 0..d {
+                    expected.push((0..l).map(|j| (scores[j] - max).exp() / total * v[(m * l + j) * d + x]).sum());
+                }
+            }
+            // the first query can only attend to the first key
+            assert_eq!(&result.data()[m * l * d..m * l * d + d], &v[m * l * d..m * l * d + d]);
+        }
+        assert_close(&*result, &expected, &shape, 1e-5);
+        Ok(())
+    }
+
+    #[test]
+    fn test_attention_shape_errors() -> MlResult<()> {
+        let q = Tensor::<f32>::from_vec(vec![0.0; 8], &[1, 2, 4])?;
+        let k = Tensor::<f32>::from_vec(vec![0.0; 6], &[1, 2, 3])?;
+        let v = Tensor::<f32>::from_vec(vec![0.0; 12], &[1, 3, 4])?;
+
+        let err = scaled_dot_product_attention(&*q, &*k, &*q, None).err().unwrap();
+        assert!(err.to_string().contains("key"));
+        let err = scaled_dot_product_attention(&*q, &*q, &*v, None).err().unwrap();
+        assert!(err.to_string().contains("value"));
+        let mask = Tensor::<f32>::from_vec(vec![0.0; 3], &[3])?;
+        let err = scaled_dot_product_attention(&*q, &*q, &*q, Some(&*mask)).err().unwrap();
+        assert!(err.to_string().contains("mask"));
+        Ok(())
+    }
+}
@@ -0,0 +1,198 @@
+use crate::{MlError, MlResult};
+use crate::tensor::{ArcTensor, Tensor, TensorBase, TensorError};
+
+/// A lookup table that maps integer indices to rows of a `[num_embeddings, dim]` weight tensor.
+///
+/// If `padding_idx` is set, that row is kept at zero and never receives a gradient.
+pub struct Embedding {
+    weight: ArcTensor<f32>,
+    padding_idx: Option<usize>,
+}
+
+impl Embedding {
+    /// Creates an embedding layer from an existing weight tensor
+    ///
+    /// # Arguments
+    /// * `weight` - The embedding table with shape `[num_embeddings, dim]`
+    /// * `padding_idx` - Optional row that is zeroed and excluded from gradients
+    ///
+    /// # Returns
+    /// * `Ok(Embedding)` if the weight is 2-D and `padding_idx` is in range
+    /// * `Err(MlError::TensorError)` otherwise
+    pub fn new(weight: ArcTensor<f32>, padding_idx: Option<usize>) -> MlResult<Self> {
+        if weight.shape().len() != 2 {
+            return Err(MlError::TensorError(TensorError::InvalidOperation {
+                op: "embedding",
+                reason: format!("weight must be 2-D [num_embeddings, dim], got shape {:?}", weight.shape()),
+            }));
+        }
+
+        let (num_embeddings, dim) = (weight.shape()[0], weight.shape()[1]);
+        let weight = match padding_idx {
+            Some(idx) if idx >= num_embeddings => {
+                return Err(MlError::TensorError(TensorError::InvalidOperation {
+                    op: "embedding",
+                    reason: format!("padding_idx {} is out of range for embedding table of size {}", idx, num_embeddings),
+                }));
+            }
+            Some(idx) => {
+                let mut data = weight.data().to_vec();
+                data[idx * dim..(idx + 1) * dim].fill(0.0);
+                Tensor::<f32>::from_vec(data, weight.shape())?
+            }
+            None => weight,
+        };
+
+        Ok(Self { weight, padding_idx })
+    }
+
+    /// Returns the embedding table
+    pub fn weight(&self) -> &dyn TensorBase<f32> {
+        &*self.weight
+    }
+
+    /// Returns the number of rows in the embedding table
+    pub fn num_embeddings(&self) -> usize {
+        self.weight.shape()[0]
+    }
+
+    /// Returns the size of each embedding vector
+    pub fn embedding_dim(&self) -> usize {
+        self.weight.shape()[1]
+    }
+
+    /// Returns the padding row, if any
+    pub fn padding_idx(&self) -> Option<usize> {
+        self.padding_idx
+    }
+
+    /// Looks up the embedding vector for every index
+    ///
+    /// # Arguments
+    /// * `indices` - A tensor of row indices with any shape `[...]`
+    ///
+    /// # Returns
+    /// A new tensor with shape `[..., dim]` holding the selected rows
+    pub fn forward(&self, indices: &dyn TensorBase<usize>) -> MlResult<ArcTensor<f32>> {
+        self.check_indices(indices)?;
+
+        let dim = self.embedding_dim();
+        let mut data = Vec::with_capacity(indices.data().len() * dim);
+        for &idx in indices.data() {
+            data.extend_from_slice(&self.weight.data()[idx * dim..(idx + 1) * dim]);
+        }
+
+        let mut shape = indices.shape().to_vec();
+        shape.push(dim);
+        Tensor::<f32>::from_vec(data, &shape)
+    }
+
+    /// Computes the gradient of the embedding table
+    ///
+    /// Gradients of rows that are looked up multiple times are summed (scatter-add),
+    /// and the padding row always receives zero.
+    ///
+    /// # Arguments
+    /// * `indices` - The indices passed to `forward`
+    /// * `grad` - The gradient of the output with shape `[..., dim]`
+    ///
+    /// # Returns
+    /// A new tensor with shape `[num_embeddings, dim]`
+    #[cfg(feature = "enable_backpropagation")]
+    pub fn backward(&self, indices: &dyn TensorBase<usize>, grad: &dyn TensorBase<f32>) -> MlResult<ArcTensor<f32>> {
+        self.check_indices(indices)?;
+
+        let dim = self.embedding_dim();
+        let mut expected = indices.shape().to_vec();
+        expected.push(dim);
+        if grad.shape() != expected.as_slice() {
+            return Err(MlError::TensorError(TensorError::InvalidShape {
+                expected,
+                got: grad.shape().to_vec(),
+            }));
+        }
+
+        let mut data = vec![0.0; self.num_embeddings() * dim];
+        for (&idx, row_grad) in indices.data().iter().zip(grad.data().chunks(dim)) {
+            if Some(idx) == self.padding_idx {
+                continue;
+            }
+            for (w, &g) in data[idx * dim..(idx + 1) * dim].iter_mut().zip(row_grad) {
+                *w += g;
+            }
+        }
+
+        Tensor::<f32>::from_vec(data, self.weight.shape())
+    }
+
+    fn check_indices(&self, indices: &dyn TensorBase<usize>) -> MlResult<()> {
+        let num_embeddings = self.num_embeddings();
+        match indices.data().iter().find(|&&idx| idx >= num_embeddings) {
+            Some(&idx) => Err(MlError::TensorError(TensorError::InvalidOperation {
+                op: "embedding",
+                reason: format!("index {} is out of range for embedding table of size {}", idx, num_embeddings),
+            })),
+            None => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::MlResult;
+    use crate::nn::Embedding;
+    use crate::tensor::{Tensor, TensorBase};
+
+    fn table() -> MlResult<Embedding> {
+        let weight = Tensor::<f32>::from_vec(vec![0.0, 1.0, 10.0, 11.0, 20.0, 21.0, 30.0, 31.0], &[4, 2])?;
+        Embedding::new(weight, None)
+    }
+
+    #[test]
+    fn test_embedding_lookup() -> MlResult<()> {
+        let embedding = table()?;
+        let indices = Tensor::<usize>::from_vec(vec![3, 0, 1, 3], &[2, 2])?;
+        let result = embedding.forward(&*indices)?;
+
+        assert_eq!(result.shape(), &[2, 2, 2]);
+        assert_eq!(result.data(), &[30.0, 31.0, 0.0, 1.0, 10.0, 11.0, 30.0, 31.0]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_embedding_out_of_range() -> MlResult<()> {
+        let embedding = table()?;
+        let indices = Tensor::<usize>::from_vec(vec![1, 7], &[2])?;
+        let message = embedding.forward(&*indices).err().unwrap().to_string();
+
+        assert!(message.contains("index 7"));
+        assert!(message.contains("size 4"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_embedding_padding_row() -> MlResult<()> {
+        let weight = Tensor::<f32>::from_vec(vec![1.0; 6], &[3, 2])?;
+        let embedding = Embedding::new(weight, Some(1))?;
+        let indices = Tensor::<usize>::from_vec(vec![1, 2], &[2])?;
+        let result = embedding.forward(&*indices)?;
+
+        assert_eq!(embedding.weight().data(), &[1.0, 1.0, 0.0, 0.0, 1.0, 1.0]);
+        assert_eq!(result.data(), &[0.0, 0.0, 1.0, 1.0]);
+        Ok(())
+    }
+
+    #[cfg(feature = "enable_backpropagation")]
+    #[test]
+    fn test_embedding_backward() -> MlResult<()> {
+        let weight = Tensor::<f32>::from_vec(vec![1.0; 8], &[4, 2])?;
+        let embedding = Embedding::new(weight, Some(0))?;
+        let indices = Tensor::<usize>::from_vec(vec![2, 0, 2], &[3])?;
+        let grad = Tensor::<f32>::from_vec(vec![1.0, 2.0, 5.0, 5.0, 3.0, 4.0], &[3, 2])?;
+        let weight_grad = embedding.backward(&*indices, &*grad)?;
+
+        assert_eq!(weight_grad.shape(), &[4, 2]);
+        assert_eq!(weight_grad.data(), &[0.0, 0.0, 0.0, 0.0, 4.0, 6.0, 0.0, 0.0]);
+        Ok(())
+    }
+}
@@ -0,0 +1,186 @@
+use crate::{MlError, MlResult};
+use crate::tensor::{ArcTensor, Rng, Tensor, TensorBase, TensorError};
+
+/// Which fan to preserve the variance of in Kaiming initialization.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FanMode {
+    /// Preserves the variance of activations in the forward pass
+    FanIn,
+    /// Preserves the variance of gradients in the backward pass
+    FanOut,
+}
+
+/// The nonlinearity following a layer, used to pick the recommended gain.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Nonlinearity {
+    Linear,
+    Sigmoid,
+    Tanh,
+    Relu,
+    /// Leaky ReLU with the given negative slope
+    LeakyRelu(f32),
+}
+
+impl Nonlinearity {
+    /// Returns the recommended gain for this nonlinearity
+    pub fn gain(&self) -> f32 {
+        match self {
+            Nonlinearity::Linear | Nonlinearity::Sigmoid => 1.0,
+            Nonlinearity::Tanh => 5.0 / 3.0,
+            Nonlinearity::Relu => 2.0f32.sqrt(),
+            Nonlinearity::LeakyRelu(slope) => (2.0 / (1.0 + slope * slope)).sqrt(),
+        }
+    }
+}
+
+/// A weight initialization scheme that layers can take in their constructors.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Init {
+    Zeros,
+    XavierUniform { gain: f32 },
+    XavierNormal { gain: f32 },
+    KaimingUniform { fan_mode: FanMode, nonlinearity: Nonlinearity },
+    KaimingNormal { fan_mode: FanMode, nonlinearity: Nonlinearity },
+}
+
+impl Init {
+    /// Creates a tensor of the given shape initialized with this scheme
+    ///
+    /// # Arguments
+    /// * `shape` - The shape of the weight
+    /// * `rng` - The random number generator to sample from
+    pub fn init(&self, shape: &[usize], rng: &mut Rng) -> MlResult<ArcTensor<f32>> {
+        match *self {
+            Init::Zeros => Tensor::<f32>::from_vec(vec![0.0; shape.iter().product()], shape),
+            Init::XavierUniform { gain } => xavier_uniform(shape, gain, rng),
+            Init::XavierNormal { gain } => xavier_normal(shape, gain, rng),
+            Init::KaimingUniform { fan_mode, nonlinearity } => kaiming_uniform(shape, fan_mode, nonlinearity, rng),
+            Init::KaimingNormal { fan_mode, nonlinearity } => kaiming_normal(shape, fan_mode, nonlinearity, rng),
+        }
+    }
+}
+
+/// Computes `(fan_in, fan_out)` for a weight shape
+///
+/// The shape is interpreted as `[out_features, in_features]`, or as
+/// `[out_channels, in_channels, kernel...]` for rank > 2, where the kernel
+/// size multiplies both fans.
+///
+/// # Returns
+/// * `Ok((fan_in, fan_out))` on success
+/// * `Err(MlError::TensorError)` if the shape has fewer than two dimensions
+pub fn fan_in_and_fan_out(shape: &[usize]) -> MlResult<(usize, usize)> {
+    if shape.len() < 2 {
+        return Err(MlError::TensorError(TensorError::InvalidOperation {
+            op: "init",
+            reason: format!("fan in and fan out require at least 2 dimensions, got shape {:?}", shape),
+        }));
+    }
+
+    let receptive_field: usize = shape[2..].iter().product();
+    Ok((shape[1] * receptive_field, shape[0] * receptive_field))
+}
+
+/// Samples from `U(-a, a)` with `a = gain * sqrt(6 / (fan_in + fan_out))`
+pub fn xavier_uniform(shape: &[usize], gain: f32, rng: &mut Rng) -> MlResult<ArcTensor<f32>> {
+    let std = xavier_std(shape, gain)?;
+    uniform(shape, 3.0f32.sqrt() * std, rng)
+}
+
+/// Samples from `N(0, std²)` with `std = gain * sqrt(2 / (fan_in + fan_out))`
+pub fn xavier_normal(shape: &[usize], gain: f32, rng: &mut Rng) -> MlResult<ArcTensor<f32>> {
+    let std = xavier_std(shape, gain)?;
+    normal(shape, std, rng)
+}
+
+/// Samples from `U(-a, a)` with `a = gain * sqrt(3 / fan)`
+pub fn kaiming_uniform(shape: &[usize], fan_mode: FanMode, nonlinearity: Nonlinearity, rng: &mut Rng) -> MlResult<ArcTensor<f32>> {
+    let std = kaiming_std(shape, fan_mode, nonlinearity)?;
+    uniform(shape, 3.0f32.sqrt() * std, rng)
+}
+
+/// Samples from `N(0, std²)` with `std = gain / sqrt(fan)`
+pub fn kaiming_normal(shape: &[usize], fan_mode: FanMode, nonlinearity: Nonlinearity, rng: &mut Rng) -> MlResult<ArcTensor<f32>> {
+    let std = kaiming_std(shape, fan_mode, nonlinearity)?;
+    normal(shape, std, rng)
+}
+
+fn xavier_std(shape: &[usize], gain: f32) -> MlResult<f32> {
+    let (fan_in, fan_out) = fan_in_and_fan_out(shape)?;
+    Ok(gain * (2.0 / (fan_in + fan_out).max(1) as f32).sqrt())
+}
+
+fn kaiming_std(shape: &[usize], fan_mode: FanMode, nonlinearity: Nonlinearity) -> MlResult<f32> {
+    let (fan_in, fan_out) = fan_in_and_fan_out(shape)?;
+    let fan = match fan_mode {
+        FanMode::FanIn => fan_in,
+        FanMode::FanOut => fan_out,
+    };
+    Ok(nonlinearity.gain() / (fan.max(1) as f32).sqrt())
+}
+
+fn uniform(shape: &[usize], bound: f32, rng: &mut Rng) -> MlResult<ArcTensor<f32>> {
+    let data = (0..shape.iter().product()).map(|_| rng.uniform(-bound, bound)).collect();
+    Tensor::<f32>::from_vec(data, shape)
+}
+
+fn normal(shape: &[usize], std: f32, rng: &mut Rng) -> MlResult<ArcTensor<f32>> {
+    let data = (0..shape.iter().product()).map(|_| rng.normal(0.0, std)).collect();
+    Tensor::<f32>::from_vec(data, shape)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::MlResult;
+    use crate::nn::init::*;
+    use crate::testing::assert_close;
+
+    fn std_of(data: &[f32]) -> f32 {
+        let mean = data.iter().sum::<f32>() / data.len() as f32;
+        (data.iter().map(|x| (x - mean) * (x - mean)).sum::<f32>() / data.len() as f32).sqrt()
+    }
+
+    #[test]
+    fn test_fan_in_and_fan_out() -> MlResult<()> {
+        assert_eq!(fan_in_and_fan_out(&[20, 10])?, (10, 20));
+        assert_eq!(fan_in_and_fan_out(&[16, 3, 5, 5])?, (75, 400));
+        assert!(fan_in_and_fan_out(&[10]).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_xavier_std() -> MlResult<()> {
+        let mut rng = Rng::new(0);
+        let expected = (2.0f32 / 2000.0).sqrt();
+
+        let weight = xavier_uniform(&[1000, 1000], 1.0, &mut rng)?;
+        assert!((std_of(weight.data()) - expected).abs() < 0.05 * expected);
+        let weight = xavier_normal(&[1000, 1000], 1.0, &mut rng)?;
+        assert!((std_of(weight.data()) - expected).abs() < 0.05 * expected);
+        Ok(())
+    }
+
+    #[test]
+    fn test_kaiming_bounds() -> MlResult<()> {
+        let mut rng = Rng::new(1);
+        let weight = kaiming_uniform(&[64, 32, 3, 3], FanMode::FanIn, Nonlinearity::Relu, &mut rng)?;
+        let bound = 2.0f32.sqrt() * (3.0 / 288.0f32).sqrt();
+
+        assert_eq!(weight.shape(), &[64, 32, 3, 3]);
+        assert!(weight.data().iter().all(|x| x.abs() <= bound));
+        Ok(())
+    }
+
+    #[test]
+    fn test_init_deterministic() -> MlResult<()> {
+        let init = Init::KaimingNormal { fan_mode: FanMode::FanOut, nonlinearity: Nonlinearity::LeakyRelu(0.01) };
+        let a = init.init(&[8, 4], &mut Rng::new(7))?;
+        let b = init.init(&[8, 4], &mut Rng::new(7))?;
+        let c = init.init(&[8, 4], &mut Rng::new(8))?;
+
+        assert_close(&*b, a.data(), &[8, 4], 0.0);
+        assert_ne!(a.data(), c.data());
+        assert_close(&*Init::Zeros.init(&[2, 2], &mut Rng::new(0))?, &[0.0; 4], &[2, 2], 0.0);
+        Ok(())
+    }
+}
@@ -0,0 +1,444 @@
+use crate::{MlError, MlResult};
+use crate::backend::{Backend, CpuBackend, Device};
+use crate::tensor::{Abs, ArcTensor, Function, Square, Sub, Tensor, TensorBase, TensorError};
+
+/// Specifies how an elementwise loss is reduced to its final value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Reduction {
+    /// Averages the elementwise losses into a single value
+    #[default]
+    Mean,
+    /// Sums the elementwise losses into a single value
+    Sum,
+    /// Returns the elementwise losses with the shape of the input
+    None,
+}
+
+impl Reduction {
+    fn reduce(&self, loss: ArcTensor<f32>) -> MlResult<ArcTensor<f32>> {
+        let backend = CpuBackend::new()?;
+        match self {
+            Reduction::Mean => Ok(Tensor::<f32>::scalar(backend.mean(loss.data()))),
+            Reduction::Sum => Ok(Tensor::<f32>::scalar(backend.sum(loss.data()))),
+            Reduction::None => Ok(loss),
+        }
+    }
+
+    #[cfg(feature = "enable_backpropagation")]
+    fn scale(&self, len: usize) -> f32 {
+        match self {
+            Reduction::Mean => 1.0 / len.max(1) as f32,
+            Reduction::Sum | Reduction::None => 1.0,
+        }
+    }
+}
+
+/// Computes the mean squared error `(pred - target)^2`
+///
+/// # Arguments
+/// * `pred` - The predicted values
+/// * `target` - The target values, with the same shape as `pred`
+/// * `reduction` - How the elementwise losses are reduced
+///
+/// # Returns
+/// A single-element tensor for `Mean`/`Sum`, or the elementwise losses for `None`
+pub fn mse_loss(pred: &dyn TensorBase<f32>, target: &dyn TensorBase<f32>, reduction: Reduction) -> MlResult<ArcTensor<f32>> {
+    pred.chk_shape(target)?;
+    let diff = Sub::new(pred, Some(target))?.forward()?;
+    let loss = Square::new(&*diff, None)?.forward()?;
+    reduction.reduce(loss)
+}
+
+/// Computes the mean absolute error `|pred - target|`
+///
+/// # Arguments
+/// * `pred` - The predicted values
+/// * `target` - The target values, with the same shape as `pred`
+/// * `reduction` - How the elementwise losses are reduced
+///
+/// # Returns
+/// A single-element tensor for `Mean`/`Sum`, or the elementwise losses for `None`
+pub fn l1_loss(pred: &dyn TensorBase<f32>, target: &dyn TensorBase<f32>, reduction: Reduction) -> MlResult<ArcTensor<f32>> {
+    pred.chk_shape(target)?;
+    let diff = Sub::new(pred, Some(target))?.forward()?;
+    let loss = Abs::new(&*diff, None)?.forward()?;
+    reduction.reduce(loss)
+}
+
+/// Computes the cross-entropy loss between logits and class-index targets
+///
+/// The loss is computed as the negative log-likelihood of `log_softmax(logits)` along the
+/// last dimension in a single numerically stable pass (log-sum-exp with the row maximum
+/// subtracted), so large logits do not overflow.
+///
+/// # Arguments
+/// * `logits` - Unnormalized scores with shape `[..., C]`
+/// * `targets` - Class indices with shape `[...]`
+/// * `weight` - Optional per-class weight with shape `[C]`
+/// * `ignore_index` - Optional target value excluded from the loss and from the denominator
+/// * `reduction` - How the per-sample losses are reduced
+///
+/// # Returns
+/// A single-element tensor for `Mean`/`Sum`, or the per-sample losses with shape `[...]`
+/// for `None` (ignored entries are `0`). Under `Mean` the result is the weighted average
+/// `sum(w[y] * loss) / sum(w[y])`, which is NaN if every entry is ignored.
+pub fn cross_entropy(
+    logits: &dyn TensorBase<f32>,
+    targets: &dyn TensorBase<usize>,
+    weight: Option<&dyn TensorBase<f32>>,
+    ignore_index: Option<usize>,
+    reduction: Reduction,
+) -> MlResult<ArcTensor<f32>> {
+    let classes = check_logits(logits, targets.shape())?;
+    if let Some(weight) = weight {
+        if weight.shape() != [classes] {
+            return Err(MlError::TensorError(TensorError::InvalidShape {
+                expected: vec![classes],
+                got: weight.shape().to_vec(),
+            }));
+        }
+    }
+
+    let mut losses = Vec::with_capacity(targets.data().len());
+    let (mut total, mut denominator) = (0.0, 0.0);
+    for (row, &target) in logits.data().chunks(classes).zip(targets.data()) {
+        if Some(target) == ignore_index {
+            losses.push(0.0);
+            continue;
+        }
+        if target >= classes {
+            return Err(MlError::TensorError(TensorError::InvalidOperation {
+                op: "cross_entropy",
+                reason: format!("target {} is out of range for {} classes", target, classes),
+            }));
+        }
+
+        let w = weight.map_or(1.0, |weight| weight.data()[target]);
+        let loss = -w * (row[target] - log_sum_exp(row));
+        losses.push(loss);
+        total += loss;
+        denominator += w;
+    }
+
+    match reduction {
+        Reduction::Mean => Ok(Tensor::<f32>::scalar(total / denominator)),
+        Reduction::Sum => Ok(Tensor::<f32>::scalar(total)),
+        Reduction::None => Tensor::<f32>::from_vec(losses, targets.shape()),
+    }
+}
+
+/// Computes the cross-entropy loss between logits and probability (or one-hot) targets
+///
+/// # Arguments
+/// * `logits` - Unnormalized scores with shape `[..., C]`
+/// * `targets` - Target probabilities with the same shape as `logits`
+/// * `reduction` - How the per-sample losses are reduced
+///
+/// # Returns
+/// A single-element tensor for `Mean`/`Sum`, or the per-sample losses with shape `[...]` for `None`
+pub fn cross_entropy_with_probs(logits: &dyn TensorBase<f32>, targets: &dyn TensorBase<f32>, reduction: Reduction) -> MlResult<ArcTensor<f32>> {
+    logits.chk_shape(targets)?;
+    let shape = logits.shape().split_last().map_or(&[][..], |(_, batch)| batch);
+    let classes = check_logits(logits, shape)?;
+
+    let losses = logits.data().chunks(classes)
+        .zip(targets.data().chunks(classes))
+        .map(|(row, probs)| {
+            let lse = log_sum_exp(row);
+            -row.iter().zip(probs).map(|(&x, &p)| p * (x - lse)).sum::<f32>()
+        })
+        .collect();
+
+    reduction.reduce(Tensor::<f32>::from_vec(losses, shape)?)
+}
+
+/// Computes the binary cross-entropy between probabilities and targets
+///
+/// The logarithms are clamped to `-100` so that probabilities of exactly `0` or `1`
+/// produce a finite loss. Prefer `bce_with_logits` when the inputs come from a sigmoid.
+///
+/// # Arguments
+/// * `probs` - Predicted probabilities in `[0, 1]`
+/// * `targets` - Target values in `[0, 1]`, with the same shape as `probs`
+/// * `reduction` - How the elementwise losses are reduced
+///
+/// # Returns
+/// A single-element tensor for `Mean`/`Sum`, or the elementwise losses for `None`
+///
+/// # Errors
+/// In debug builds, returns `InvalidOperation` if any probability is outside `[0, 1]`
+pub fn bce_loss(probs: &dyn TensorBase<f32>, targets: &dyn TensorBase<f32>, reduction: Reduction) -> MlResult<ArcTensor<f32>> {
+    probs.chk_shape(targets)?;
+    if cfg!(debug_assertions) {
+        if let Some(p) = probs.data().iter().find(|p| !(0.0..=1.0).contains(*p)) {
+            return Err(MlError::TensorError(TensorError::InvalidOperation {
+                op: "bce_loss",
+                reason: format!("probabilities must be in [0, 1], got {}", p),
+            }));
+        }
+    }
+
+    let losses = probs.data().iter().zip(targets.data())
+        .map(|(&p, &y)| -(y * p.ln().max(-100.0) + (1.0 - y) * (1.0 - p).ln().max(-100.0)))
+        .collect();
+    reduction.reduce(Tensor::<f32>::from_vec(losses, probs.shape())?)
+}
+
+/// Computes the binary cross-entropy between logits and targets with the sigmoid folded in
+///
+/// Uses `max(-x, 0) + ln(1 + exp(-|x|))` for `-ln(sigmoid(x))`, so logits of any magnitude
+/// produce a finite loss.
+///
+/// # Arguments
+/// * `logits` - Unnormalized scores
+/// * `targets` - Target values in `[0, 1]`, with the same shape as `logits`
+/// * `pos_weight` - Optional weight of positive examples, with shape `[C]` matching the last dimension
+/// * `reduction` - How the elementwise losses are reduced
+///
+/// # Returns
+/// A single-element tensor for `Mean`/`Sum`, or the elementwise losses for `None`
+pub fn bce_with_logits(
+    logits: &dyn TensorBase<f32>,
+    targets: &dyn TensorBase<f32>,
+    pos_weight: Option<&dyn TensorBase<f32>>,
+    reduction: Reduction,
+) -> MlResult<ArcTensor<f32>> {
+    logits.chk_shape(targets)?;
+    let classes = logits.shape().last().copied().unwrap_or(1);
+    if let Some(pos_weight) = pos_weight {
+        if pos_weight.shape() != [classes] {
+            return Err(MlError::TensorError(TensorError::InvalidShape {
+                expected: vec![classes],
+                got: pos_weight.shape().to_vec(),
+            }));
+        }
+    }
+
+    let losses = logits.data().iter().zip(targets.data()).enumerate()
+        .map(|(i, (&x, &y))| {
+            let log_weight = pos_weight.map_or(1.0, |w| 1.0 + (w.data()[i % classes] - 1.0) * y);
+            let neg_log_sigmoid = (-x).max(0.0) + (-x.abs()).exp().ln_1p();
+            (1.0 - y) * x + log_weight * neg_log_sigmoid
+        })
+        .collect();
+    reduction.reduce(Tensor::<f32>::from_vec(losses, logits.shape())?)
+}
+
+fn check_logits(logits: &dyn TensorBase<f32>, target_shape: &[usize]) -> MlResult<usize> {
+    let shape = logits.shape();
+    if shape.is_empty() || shape[shape.len() - 1] == 0 {
+        return Err(MlError::TensorError(TensorError::EmptyTensor));
+    }
+    if &shape[..shape.len() - 1] != target_shape {
+        return Err(MlError::TensorError(TensorError::InvalidShape {
+            expected: shape[..shape.len() - 1].to_vec(),
+            got: target_shape.to_vec(),
+        }));
+    }
+    Ok(shape[shape.len() - 1])
+}
+
+fn log_sum_exp(row: &[f32]) -> f32 {
+    let max = row.iter().fold(f32::NEG_INFINITY, |a, &b| a.max(b));
+    max + row.iter().map(|&x| (x - max).exp()).sum::<f32>().ln()
+}
+
+/// Computes the gradient of `mse_loss` with respect to `pred`
+///
+/// # Returns
+/// A tensor with the shape of `pred` holding `2(pred - target)`, divided by the
+/// number of elements under `Reduction::Mean`
+#[cfg(feature = "enable_backpropagation")]
+pub fn mse_loss_backward(pred: &dyn TensorBase<f32>, target: &dyn TensorBase<f32>, reduction: Reduction) -> MlResult<ArcTensor<f32>> {
+    pred.chk_shape(target)?;
+    let scale = 2.0 * reduction.scale(pred.data().len());
+    let grad = pred.data().iter().zip(target.data()).map(|(&p, &t)| scale * (p - t)).collect();
+    Tensor::<f32>::from_vec(grad, pred.shape())
+}
+
+/// Computes the gradient of `l1_loss` with respect to `pred`
+///
+/// The subgradient at `pred == target` is taken to be zero.
+///
+/// # Returns
+/// A tensor with the shape of `pred` holding `sign(pred - target)`, divided by the
+/// number of elements under `Reduction::Mean`
+#[cfg(feature = "enable_backpropagation")]
+pub fn l1_loss_backward(pred: &dyn TensorBase<f32>, target: &dyn TensorBase<f32>, reduction: Reduction) -> MlResult<ArcTensor<f32>> {
+    pred.chk_shape(target)?;
+    let scale = reduction.scale(pred.data().len());
+    let grad = pred.data().iter().zip(target.data())
+        .map(|(&p, &t)| if p == t { 0.0 } else { scale * (p - t).signum() })
+        .collect();
+    Tensor::<f32>::from_vec(grad, pred.shape())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::ops::Deref;
+    use crate::MlResult;
+    use crate::nn::*;
+    use crate::ops;
+    use crate::tensor::{Exp, Function, Tensor, TensorBase};
+    use crate::testing::assert_close;
+
+    #[test]
+    fn test_mse_loss() -> MlResult<()> {
+        let pred = Tensor::<f32>::from_vec(vec![1.0, 2.0, 3.0, 4.0], &[2, 2])?;
+        let target = Tensor::<f32>::from_vec(vec![0.0, 2.0, 5.0, 1.0], &[2, 2])?;
+
+        assert_close(&*mse_loss(&*pred, &*target, Reduction::Mean)?, &[3.5], &[], 1e-6);
+        assert_close(&*mse_loss(&*pred, &*target, Reduction::Sum)?, &[14.0], &[], 1e-6);
+        assert_close(&*mse_loss(&*pred, &*target, Reduction::None)?, &[1.0, 0.0, 4.0, 9.0], &[2, 2], 1e-6);
+        Ok(())
+    }
+
+    #[test]
+    fn test_l1_loss() -> MlResult<()> {
+        let pred = Tensor::<f32>::from_vec(vec![1.0, 2.0, 3.0, 4.0], &[4])?;
+        let target = Tensor::<f32>::from_vec(vec![0.0, 2.0, 5.0, 1.0], &[4])?;
+
+        assert_close(&*l1_loss(&*pred, &*target, Reduction::Mean)?, &[1.5], &[], 1e-6);
+        assert_close(&*l1_loss(&*pred, &*target, Reduction::Sum)?, &[6.0], &[], 1e-6);
+        assert_close(&*l1_loss(&*pred, &*target, Reduction::None)?, &[1.0, 0.0, 2.0, 3.0], &[4], 1e-6);
+        Ok(())
+    }
+
+    #[test]
+    fn test_loss_shape_mismatch() -> MlResult<()> {
+        let pred = Tensor::<f32>::from_vec(vec![1.0, 2.0, 3.0, 4.0], &[2, 2])?;
+        let target = Tensor::<f32>::from_vec(vec![1.0, 2.0, 3.0, 4.0], &[4])?;
+        let message = mse_loss(&*pred, &*target, Reduction::Mean).err().unwrap().to_string();
+
+        assert!(message.contains("[2, 2]") && message.contains("[4]"));
+        assert!(l1_loss(&*pred, &*target, Reduction::Sum).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_cross_entropy_matches_composed() -> MlResult<()> {
+        let logits = Tensor::<f32>::from_vec(vec![1.0, 2.0, 0.5, -1.0, 0.0, 3.0], &[2, 3])?;
+        let targets = Tensor::<usize>::from_vec(vec![1, 2], &[2])?;
+        let loss = cross_entropy(&*logits, &*targets, None, None, Reduction::Mean)?;
+
+        // log_softmax -> gather -> mean, step by step
+        let exp = ops!(logits, Exp)?;
+        let sums: Vec<f32> = exp.data().chunks(3).map(|row| row.iter().sum()).collect();
+        let picked = [
+            (exp.data()[1] / sums[0]).ln(),
+            (exp.data()[5] / sums[1]).ln(),
+        ];
+        let expected = -(picked[0] + picked[1]) / 2.0;
+
+        assert_close(&*loss, &[expected], &[], 1e-6);
+
+        let probs = Tensor::<f32>::from_vec(vec![0.0, 1.0, 0.0, 0.0, 0.0, 1.0], &[2, 3])?;
+        let soft = cross_entropy_with_probs(&*logits, &*probs, Reduction::Mean)?;
+        assert_close(&*soft, &[expected], &[], 1e-6);
+        Ok(())
+    }
+
+    #[test]
+    fn test_cross_entropy_stability() -> MlResult<()> {
+        let logits = Tensor::<f32>::from_vec(vec![1e4, 0.0, -1e4, 1e4], &[2, 2])?;
+        let targets = Tensor::<usize>::from_vec(vec![0, 0], &[2])?;
+        let loss = cross_entropy(&*logits, &*targets, None, None, Reduction::None)?;
+
+        assert!(loss.data().iter().all(|x| x.is_finite()));
+        assert_close(&*loss, &[0.0, 2e4], &[2], 1.0);
+        assert!(loss.data()[0].abs() < 1e-6);
+        Ok(())
+    }
+
+    #[test]
+    fn test_cross_entropy_ignore_index_and_weight() -> MlResult<()> {
+        let logits = Tensor::<f32>::from_vec(vec![0.0, 0.0, 0.0, 0.0, 2.0, 0.0], &[3, 2])?;
+        let targets = Tensor::<usize>::from_vec(vec![0, 9, 1], &[3])?;
+        let weight = Tensor::<f32>::from_vec(vec![1.0, 3.0], &[2])?;
+
+        let none = cross_entropy(&*logits, &*targets, Some(&*weight), Some(9), Reduction::None)?;
+        let l0 = 2.0f32.ln();
+        let l2 = 3.0 * (2.0 + (1.0 + (-2.0f32).exp()).ln());
+        assert_close(&*none, &[l0, 0.0, l2], &[3], 1e-5);
+        assert_eq!(none.data()[1], 0.0);
+
+        let mean = cross_entropy(&*logits, &*targets, Some(&*weight), Some(9), Reduction::Mean)?;
+        assert_close(&*mean, &[(l0 + l2) / 4.0], &[], 1e-5);
+
+        assert!(cross_entropy(&*logits, &*targets, None, None, Reduction::Mean).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_bce_formulations_agree() -> MlResult<()> {
+        let logit_data = vec![-2.0, -0.5, 0.0, 0.7, 3.0];
+        let probs = logit_data.iter().map(|&x: &f32| 1.0 / (1.0 + (-x).exp())).collect();
+        let logits = Tensor::<f32>::from_vec(logit_data, &[5])?;
+        let probs = Tensor::<f32>::from_vec(probs, &[5])?;
+        let targets = Tensor::<f32>::from_vec(vec![0.0, 1.0, 1.0, 0.0, 0.3], &[5])?;
+
+        let a = bce_loss(&*probs, &*targets, Reduction::None)?;
+        let b = bce_with_logits(&*logits, &*targets, None, Reduction::None)?;
+        assert_close(&*b, a.data(), &[5], 1e-5);
+        Ok(())
+    }
+
+    #[test]
+    fn test_bce_with_logits_stability() -> MlResult<()> {
+        let logits = Tensor::<f32>::from_vec(vec![100.0, -100.0, 100.0, -100.0], &[4])?;
+        let targets = Tensor::<f32>::from_vec(vec![1.0, 0.0, 0.0, 1.0], &[4])?;
+        let loss = bce_with_logits(&*logits, &*targets, None, Reduction::None)?;
+
+        assert!(loss.data().iter().all(|x| x.is_finite()));
+        assert_close(&*loss, &[0.0, 0.0, 100.0, 100.0], &[4], 1e-4);
+        assert!(loss.data()[0] < 1e-6 && loss.data()[1] < 1e-6);
+        Ok(())
+    }
+
+    #[test]
+    fn test_bce_with_logits_pos_weight() -> MlResult<()> {
+        let logits = Tensor::<f32>::from_vec(vec![0.0, 1.0, 0.0, 1.0], &[2, 2])?;
+        let targets = Tensor::<f32>::from_vec(vec![1.0, 1.0, 0.0, 0.0], &[2, 2])?;
+        let pos_weight = Tensor::<f32>::from_vec(vec![3.0, 0.5], &[2])?;
+        let loss = bce_with_logits(&*logits, &*targets, Some(&*pos_weight), Reduction::None)?;
+
+        let softplus = |x: f32| (1.0 + x.exp()).ln();
+        let expected = [3.0 * softplus(0.0), 0.5 * softplus(-1.0), softplus(0.0), softplus(1.0)];
+        assert_close(&*loss, &expected, &[2, 2], 1e-6);
+        Ok(())
+    }
+
+    #[cfg(debug_assertions)]
+    #[test]
+    fn test_bce_loss_rejects_out_of_range() -> MlResult<()> {
+        let probs = Tensor::<f32>::from_vec(vec![0.5, 1.5], &[2])?;
+        let targets = Tensor::<f32>::from_vec(vec![1.0, 0.0], &[2])?;
+        assert!(bce_loss(&*probs, &*targets, Reduction::Mean).is_err());
+        Ok(())
+    }
+
+    #[cfg(feature = "enable_backpropagation")]
+    #[test]
+    fn test_mse_loss_grad_check() -> MlResult<()> {
+        let pred_data = vec![0.5, -1.0, 2.0, 3.5];
+        let target = Tensor::<f32>::from_vec(vec![1.0, 1.0, 1.0, 1.0], &[4])?;
+        let pred = Tensor::<f32>::from_vec(pred_data.clone(), &[4])?;
+        let grad = mse_loss_backward(&*pred, &*target, Reduction::Mean)?;
+
+        let eps = 1e-2;
+        for i in 0..pred_data.len() {
+            let mut plus = pred_data.clone();
+            let mut minus = pred_data.clone();
+            plus[i] += eps;
+            minus[i] -= eps;
+            let plus = Tensor::<f32>::from_vec(plus, &[4])?;
+            let minus = Tensor::<f32>::from_vec(minus, &[4])?;
+            let numeric = (mse_loss(&*plus, &*target, Reduction::Mean)?.data()[0]
+                - mse_loss(&*minus, &*target, Reduction::Mean)?.data()[0]) / (2.0 * eps);
+
+            assert!((grad.data()[i] - numeric).abs() < 1e-3);
+            assert!((grad.data()[i] - 2.0 * (pred_data[i] - 1.0) / 4.0).abs() < 1e-6);
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,60 @@
+use std::sync::{Arc, RwLock, RwLockReadGuard, RwLockWriteGuard};
+
+use crate::{MlError, MlResult};
+use crate::tensor::Tensor;
+
+pub mod init;
+
+mod attention;
+mod embedding;
+mod loss;
+mod rnn;
+mod sequential;
+mod state;
+
+pub use attention::scaled_dot_product_attention;
+pub use embedding::Embedding;
+pub use rnn::{GRUCell, LSTMCell};
+pub use sequential::Sequential;
+pub use state::{load_state_dict, state_dict};
+pub use loss::{bce_loss, bce_with_logits, cross_entropy, cross_entropy_with_probs, l1_loss, mse_loss, Reduction};
+#[cfg(feature = "enable_backpropagation")]
+pub use loss::{l1_loss_backward, mse_loss_backward};
+
+/// A trainable tensor shared between a model and its optimizer.
+pub type Parameter = Arc<RwLock<Tensor<f32>>>;
+
+/// Creates a new parameter from raw data
+///
+/// # Arguments
+/// * `data` - The initial values in row-major order
+/// * `shape` - The shape of the parameter
+///
+/// # Returns
+/// * `Ok(Parameter)` if the data length matches the product of the shape
+/// * `Err(MlError::TensorError)` otherwise
+pub fn parameter(data: Vec<f32>, shape: &[usize]) -> MlResult<Parameter> {
+    Ok(Arc::new(RwLock::new(Tensor::<f32>::from_vec_owned(data, shape)?)))
+}
+
+/// A layer that owns trainable parameters.
+pub trait Module {
+    /// Returns handles to all trainable parameters of the layer
+    fn parameters(&self) -> Vec<Parameter>;
+
+    /// Returns the parameters with their names, in the same order as `parameters`
+    ///
+    /// Containers prefix the names of their children with the child's name and a dot,
+    /// e.g. `0.weight`. By default parameters are named by their position.
+    fn named_parameters(&self) -> Vec<(String, Parameter)> {
+        self.parameters().into_iter().enumerate().map(|(i, p)| (i.to_string(), p)).collect()
+    }
+}
+
+pub(crate) fn read_param(param: &Parameter) -> MlResult<RwLockReadGuard<'_, Tensor<f32>>> {
+    param.read().map_err(|_| MlError::StringError("parameter lock poisoned".to_string()))
+}
+
+pub(crate) fn write_param(param: &Parameter) -> MlResult<RwLockWriteGuard<'_, Tensor<f32>>> {
+    param.write().map_err(|_| MlError::StringError("parameter lock poisoned".to_string()))
+}
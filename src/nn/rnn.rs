@@ -0,0 +1,383 @@
+use std::sync::Arc;
+
+use crate::{MlError, MlResult};
+use crate::nn::{read_param, Module, Parameter};
+use crate::nn::init::Init;
+use crate::tensor::{ArcTensor, Function, Matmul, Rng, Tensor, TensorBase, TensorError};
+
+/// A long short-term memory cell computing a single time step.
+///
+/// The four gates (input, forget, cell, output) share one fused weight of shape
+/// `[4 * hidden_size, input_size + hidden_size]`, so a step needs a single matmul.
+pub struct LSTMCell {
+    weight: Parameter,
+    bias: Parameter,
+    input_size: usize,
+    hidden_size: usize,
+}
+
+impl LSTMCell {
+    /// Creates a new LSTM cell
+    ///
+    /// # Arguments
+    /// * `input_size` - The number of input features
+    /// * `hidden_size` - The number of hidden features
+    /// * `init` - The initialization scheme for the fused weight; the bias starts at zero
+    /// * `rng` - The random number generator used by `init`
+    pub fn new(input_size: usize, hidden_size: usize, init: Init, rng: &mut Rng) -> MlResult<Self> {
+        let weight = init.init(&[4 * hidden_size, input_size + hidden_size], rng)?;
+        Ok(Self {
+            weight: crate::nn::parameter(weight.data().to_vec(), weight.shape())?,
+            bias: crate::nn::parameter(vec![0.0; 4 * hidden_size], &[4 * hidden_size])?,
+            input_size,
+            hidden_size,
+        })
+    }
+
+    /// Returns the fused gate weight with shape `[4 * hidden_size, input_size + hidden_size]`
+    pub fn weight(&self) -> &Parameter {
+        &self.weight
+    }
+
+    /// Returns the fused gate bias with shape `[4 * hidden_size]`
+    pub fn bias(&self) -> &Parameter {
+        &self.bias
+    }
+
+    /// Computes one time step
+    ///
+    /// # Arguments
+    /// * `input` - The input with shape `[batch, input_size]`
+    /// * `hidden` - The previous hidden state with shape `[batch, hidden_size]`
+    /// * `cell` - The previous cell state with shape `[batch, hidden_size]`
+    ///
+    /// # Returns
+    /// The new `(hidden, cell)` states, each with shape `[batch, hidden_size]`
+    pub fn forward(&self, input: &dyn TensorBase<f32>, hidden: &dyn TensorBase<f32>, cell: &dyn TensorBase<f32>) -> MlResult<(ArcTensor<f32>, ArcTensor<f32>)> {
+        let batch = check_step_shapes(input, hidden, self.input_size, self.hidden_size)?;
+        hidden.chk_shape(cell)?;
+        let h = self.hidden_size;
+
+        let combined = concat_columns(input, hidden)?;
+        let gates = linear(&*combined, &*read_param(&self.weight)?, &*read_param(&self.bias)?)?;
+        let input_gate = gate_chunk(&*gates, batch, h, 0)?.sigmoid()?;
+        let forget_gate = gate_chunk(&*gates, batch, h, 1)?.sigmoid()?;
+        let cell_gate = gate_chunk(&*gates, batch, h, 2)?.tanh()?;
+        let output_gate = gate_chunk(&*gates, batch, h, 3)?.sigmoid()?;
+
+        let new_cell: Vec<f32> = (0..batch * h)
+            .map(|j| forget_gate.data()[j] * cell.data()[j] + input_gate.data()[j] * cell_gate.data()[j])
+            .collect();
+        let new_cell = Tensor::<f32>::from_vec(new_cell, &[batch, h])?;
+        let cell_activation = new_cell.tanh()?;
+        let new_hidden = output_gate.data().iter().zip(cell_activation.data()).map(|(o, c)| o * c).collect();
+
+        Ok((Tensor::<f32>::from_vec(new_hidden, &[batch, h])?, new_cell))
+    }
+
+    /// Runs the cell over a sequence
+    ///
+    /// # Arguments
+    /// * `input` - The inputs with shape `[seq_len, batch, input_size]`
+    /// * `hidden` - The initial hidden state with shape `[batch, hidden_size]`
+    /// * `cell` - The initial cell state with shape `[batch, hidden_size]`
+    ///
+    /// # Returns
+    /// The hidden states of every step stacked to `[seq_len, batch, hidden_size]`,
+    /// followed by the final `(hidden, cell)` states
+    pub fn forward_sequence(&self, input: &dyn TensorBase<f32>, hidden: &dyn TensorBase<f32>, cell: &dyn TensorBase<f32>) -> MlResult<(ArcTensor<f32>, ArcTensor<f32>, ArcTensor<f32>)> {
+        check_sequence_shape(input)?;
+        let mut hidden = Tensor::<f32>::from_vec(hidden.data().to_vec(), hidden.shape())?;
+        let mut cell = Tensor::<f32>::from_vec(cell.data().to_vec(), cell.shape())?;
+        let mut outputs = Vec::new();
+        for t in 0..input.shape()[0] {
+            (hidden, cell) = self.forward(&*input.select(0, t)?, &*hidden, &*cell)?;
+            outputs.extend_from_slice(hidden.data());
+        }
+
+        let shape = [input.shape()[0], hidden.shape()[0], self.hidden_size];
+        Ok((Tensor::<f32>::from_vec(outputs, &shape)?, hidden, cell))
+    }
+}
+
+impl Module for LSTMCell {
+    fn parameters(&self) -> Vec<Parameter> {
+        vec![Arc::clone(&self.weight), Arc::clone(&self.bias)]
+    }
+
+    fn named_parameters(&self) -> Vec<(String, Parameter)> {
+        vec![
+            ("weight".to_string(), Arc::clone(&self.weight)),
+            ("bias".to_string(), Arc::clone(&self.bias)),
+        ]
+    }
+}
+
+/// A gated recurrent unit cell computing a single time step.
+///
+/// The reset, update and new gates are stacked in `weight_ih` with shape
+/// `[3 * hidden_size, input_size]` and `weight_hh` with shape `[3 * hidden_size, hidden_size]`.
+/// They are kept separate because the reset gate scales only the hidden part of the new gate.
+pub struct GRUCell {
+    weight_ih: Parameter,
+    weight_hh: Parameter,
+    bias_ih: Parameter,
+    bias_hh: Parameter,
+    input_size: usize,
+    hidden_size: usize,
+}
+
+impl GRUCell {
+    /// Creates a new GRU cell
+    ///
+    /// # Arguments
+    /// * `input_size` - The number of input features
+    /// * `hidden_size` - The number of hidden features
+    /// * `init` - The initialization scheme for both weights; the biases start at zero
+    /// * `rng` - The random number generator used by `init`
+    pub fn new(input_size: usize, hidden_size: usize, init: Init, rng: &mut Rng) -> MlResult<Self> {
+        let weight_ih = init.init(&[3 * hidden_size, input_size], rng)?;
+        let weight_hh = init.init(&[3 * hidden_size, hidden_size], rng)?;
+        Ok(Self {
+            weight_ih: crate::nn::parameter(weight_ih.data().to_vec(), weight_ih.shape())?,
+            weight_hh: crate::nn::parameter(weight_hh.data().to_vec(), weight_hh.shape())?,
+            bias_ih: crate::nn::parameter(vec![0.0; 3 * hidden_size], &[3 * hidden_size])?,
+            bias_hh: crate::nn::parameter(vec![0.0; 3 * hidden_size], &[3 * hidden_size])?,
+            input_size,
+            hidden_size,
+        })
+    }
+
+    /// Computes one time step
+    ///
+    /// # Arguments
+    /// * `input` - The input with shape `[batch, input_size]`
+    /// * `hidden` - The previous hidden state with shape `[batch, hidden_size]`
+    ///
+    /// # Returns
+    /// The new hidden state with shape `[batch, hidden_size]`
+    pub fn forward(&self, input: &dyn TensorBase<f32>, hidden: &dyn TensorBase<f32>) -> MlResult<ArcTensor<f32>> {
+        let batch = check_step_shapes(input, hidden, self.input_size, self.hidden_size)?;
+        let h = self.hidden_size;
+
+        let input_gates = linear(input, &*read_param(&self.weight_ih)?, &*read_param(&self.bias_ih)?)?;
+        let hidden_gates = linear(hidden, &*read_param(&self.weight_hh)?, &*read_param(&self.bias_hh)?)?;
+        let (ih, hh) = (input_gates.data(), hidden_gates.data());
+
+        let gate_sum = |k: usize| {
+            let data = (0..batch * h).map(|j| {
+                let idx = (j / h) * 3 * h + k * h + j % h;
+                ih[idx] + hh[idx]
+            }).collect();
+            Tensor::<f32>::from_vec(data, &[batch, h])
+        };
+        let reset_gate = gate_sum(0)?.sigmoid()?;
+        let update_gate = gate_sum(1)?.sigmoid()?;
+
+        let new_gate = (0..batch * h).map(|j| {
+            let idx = (j / h) * 3 * h + 2 * h + j % h;
+            ih[idx] + reset_gate.data()[j] * hh[idx]
+        }).collect();
+        let new_gate = Tensor::<f32>::from_vec(new_gate, &[batch, h])?.tanh()?;
+
+        let new_hidden = (0..batch * h)
+            .map(|j| (1.0 - update_gate.data()[j]) * new_gate.data()[j] + update_gate.data()[j] * hidden.data()[j])
+            .collect();
+        Tensor::<f32>::from_vec(new_hidden, &[batch, h])
+    }
+
+    /// Runs the cell over a sequence
+    ///
+    /// # Arguments
+    /// * `input` - The inputs with shape `[seq_len, batch, input_size]`
+    /// * `hidden` - The initial hidden state with shape `[batch, hidden_size]`
+    ///
+    /// # Returns
+    /// The hidden states of every step stacked to `[seq_len, batch, hidden_size]`,
+    /// followed by the final hidden state
+    pub fn forward_sequence(&self, input: &dyn TensorBase<f32>, hidden: &dyn TensorBase<f32>) -> MlResult<(ArcTensor<f32>, ArcTensor<f32>)> {
+        check_sequence_shape(input)?;
+        let mut hidden = Tensor::<f32>::from_vec(hidden.data().to_vec(), hidden.shape())?;
+        let mut outputs = Vec::new();
+        for t in 0..input.shape()[0] {
+            hidden = self.forward(&*input.select(0, t)?, &*hidden)?;
+            outputs.extend_from_slice(hidden.data());
+        }
+
+        let shape = [input.shape()[0], hidden.shape()[0], self.hidden_size];
+        Ok((Tensor::<f32>::from_vec(outputs, &shape)?, hidden))
+    }
+}
+
+impl Module for GRUCell {
+    fn parameters(&self) -> Vec<Parameter> {
+        vec![
+            Arc::clone(&self.weight_ih),
+            Arc::clone(&self.weight_hh),
+            Arc::clone(&self.bias_ih),
+            Arc::clone(&self.bias_hh),
+        ]
+    }
+
+    fn named_parameters(&self) -> Vec<(String, Parameter)> {
+        vec![
+            ("weight_ih".to_string(), Arc::clone(&self.weight_ih)),
+            ("weight_hh".to_string(), Arc::clone(&self.weight_hh)),
+            ("bias_ih".to_string(), Arc::clone(&self.bias_ih)),
+            ("bias_hh".to_string(), Arc::clone(&self.bias_hh)),
+        ]
+    }
+}
+
+/// Validates `[batch, input_size]` and `[batch, hidden_size]` operands and returns the batch size
+fn check_step_shapes(input: &dyn TensorBase<f32>, hidden: &dyn TensorBase<f32>, input_size: usize, hidden_size: usize) -> MlResult<usize> {
+    let batch = input.shape().first().copied().unwrap_or(0);
+    if input.shape() != [batch, input_size] {
+        return Err(MlError::TensorError(TensorError::InvalidShape {
+            expected: vec![batch, input_size],
+            got: input.shape().to_vec(),
+        }));
+    }
+    if hidden.shape() != [batch, hidden_size] {
+        return Err(MlError::TensorError(TensorError::InvalidShape {
+            expected: vec![batch, hidden_size],
+            got: hidden.shape().to_vec(),
+        }));
+    }
+    Ok(batch)
+}
+
+fn check_sequence_shape(input: &dyn TensorBase<f32>) -> MlResult<()> {
+    if input.shape().len() != 3 {
+        return Err(MlError::TensorError(TensorError::InvalidOperation {
+            op: "forward_sequence",
+            reason: format!("expected input with shape [seq_len, batch, input_size], got {:?}", input.shape()),
+        }));
+    }
+    Ok(())
+}
+
+/// Concatenates two `[batch, _]` tensors along the feature dimension
+fn concat_columns(left: &dyn TensorBase<f32>, right: &dyn TensorBase<f32>) -> MlResult<ArcTensor<f32>> {
+    let (batch, l, r) = (left.shape()[0], left.shape()[1], right.shape()[1]);
+    let mut data = Vec::with_capacity(batch * (l + r));
+    for b in 0..batch {
+        data.extend_from_slice(&left.data()[b * l..(b + 1) * l]);
+        data.extend_from_slice(&right.data()[b * r..(b + 1) * r]);
+    }
+    Tensor::<f32>::from_vec(data, &[batch, l + r])
+}
+
+/// Computes `input @ weightᵀ + bias`
+fn linear(input: &dyn TensorBase<f32>, weight: &dyn TensorBase<f32>, bias: &dyn TensorBase<f32>) -> MlResult<ArcTensor<f32>> {
+    let weight_t = weight.mT()?;
+    let output = Matmul::new(input, Some(&*weight_t))?.forward()?;
+    let n = bias.data().len();
+    let data = output.data().iter().enumerate().map(|(i, &x)| x + bias.data()[i % n]).collect();
+    Tensor::<f32>::from_vec(data, output.shape())
+}
+
+/// Extracts the `k`-th `[batch, hidden]` block from stacked `[batch, n * hidden]` gates
+fn gate_chunk(gates: &dyn TensorBase<f32>, batch: usize, hidden: usize, k: usize) -> MlResult<ArcTensor<f32>> {
+    let width = gates.shape()[1];
+    let data = (0..batch)
+        .flat_map(|b| gates.data()[b * width + k * hidden..b * width + (k + 1) * hidden].iter().copied())
+        .collect();
+    Tensor::<f32>::from_vec(data, &[batch, hidden])
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::MlResult;
+    use crate::nn::init::Init;
+    use crate::nn::{GRUCell, LSTMCell, Module};
+    use crate::tensor::{Function, Matmul, Rng, Tensor, TensorBase};
+    use crate::testing::assert_close;
+
+    fn sigmoid(x: f32) -> f32 {
+        1.0 / (1.0 + (-x).exp())
+    }
+
+    #[test]
+    fn test_lstm_fused_matches_separate_gates() -> MlResult<()> {
+        let (batch, input_size, hidden_size) = (2, 3, 4);
+        let mut rng = Rng::new(5);
+        let lstm = LSTMCell::new(input_size, hidden_size, Init::XavierUniform { gain: 1.0 }, &mut rng)?;
+        let bias: Vec<f32> = (0..4 * hidden_size).map(|_| rng.normal(0.0, 0.1)).collect();
+        lstm.bias().write().unwrap().data_mut().copy_from_slice(&bias);
+
+        let x = Tensor::<f32>::from_vec((0..batch * input_size).map(|_| rng.normal(0.0, 1.0)).collect(), &[batch, input_size])?;
+        let h = Tensor::<f32>::from_vec((0..batch * hidden_size).map(|_| rng.normal(0.0, 1.0)).collect(), &[batch, hidden_size])?;
+        let c = Tensor::<f32>::from_vec((0..batch * hidden_size).map(|_| rng.normal(0.0, 1.0)).collect(), &[batch, hidden_size])?;
+        let (new_h, new_c) = lstm.forward(&*x, &*h, &*c)?;
+
+        // reference: one x-matmul and one h-matmul per gate
+        let weight = lstm.weight().read().unwrap().data().to_vec();
+        let width = input_size + hidden_size;
+        let gate = |k: usize| -> MlResult<Vec<f32>> {
+            let rows = k * hidden_size..(k + 1) * hidden_size;
+            let w_x: Vec<f32> = rows.clone().flat_map(|r| weight[r * width..r * width + input_size].to_vec()).collect();
+            let w_h: Vec<f32> = rows.flat_map(|r| weight[r * width + input_size..(r + 1) * width].to_vec()).collect();
+            let w_x = Tensor::<f32>::from_vec(w_x, &[hidden_size, input_size])?.mT()?;
+            let w_h = Tensor::<f32>::from_vec(w_h, &[hidden_size, hidden_size])?.mT()?;
+            let from_x = Matmul::new(&*x, Some(&*w_x))?.forward()?;
+            let from_h = Matmul::new(&*h, Some(&*w_h))?.forward()?;
+            Ok((0..batch * hidden_size).map(|j| from_x.data()[j] + from_h.data()[j] + bias[k * hidden_size + j % hidden_size]).collect())
+        };
+        let (i, f, g, o) = (gate(0)?, gate(1)?, gate(2)?, gate(3)?);
+
+        let expected_c: Vec<f32> = (0..batch * hidden_size).map(|j| sigmoid(f[j]) * c.data()[j] + sigmoid(i[j]) * g[j].tanh()).collect();
+        let expected_h: Vec<f32> = (0..batch * hidden_size).map(|j| sigmoid(o[j]) * expected_c[j].tanh()).collect();
+        assert_close(&*new_c, &expected_c, &[batch, hidden_size], 1e-5);
+        assert_close(&*new_h, &expected_h, &[batch, hidden_size], 1e-5);
+        Ok(())
+    }
+
+    #[test]
+    fn test_lstm_sequence_shapes() -> MlResult<()> {
+        let lstm = LSTMCell::new(3, 5, Init::XavierNormal { gain: 1.0 }, &mut Rng::new(0))?;
+        let input = Tensor::<f32>::from_vec((0..24).map(|x| x as f32 * 0.1).collect(), &[4, 2, 3])?;
+        let zeros = Tensor::<f32>::from_vec(vec![0.0; 10], &[2, 5])?;
+        let (outputs, h, c) = lstm.forward_sequence(&*input, &*zeros, &*zeros)?;
+
+        assert_eq!(outputs.shape(), &[4, 2, 5]);
+        assert_eq!(h.shape(), &[2, 5]);
+        assert_eq!(c.shape(), &[2, 5]);
+        assert_eq!(&outputs.data()[30..], h.data());
+        assert_eq!(lstm.parameters().len(), 2);
+
+        let wrong_hidden = Tensor::<f32>::from_vec(vec![0.0; 8], &[2, 4])?;
+        assert!(lstm.forward(&*input.select(0, 0)?, &*wrong_hidden, &*wrong_hidden).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_gru_step() -> MlResult<()> {
+        let (batch, input_size, hidden_size) = (2, 3, 2);
+        let gru = GRUCell::new(input_size, hidden_size, Init::XavierUniform { gain: 1.0 }, &mut Rng::new(9))?;
+        let x = Tensor::<f32>::from_vec(vec![0.5, -1.0, 2.0, 0.0, 1.0, -0.5], &[batch, input_size])?;
+        let h = Tensor::<f32>::from_vec(vec![0.1, -0.2, 0.3, 0.4], &[batch, hidden_size])?;
+        let new_h = gru.forward(&*x, &*h)?;
+
+        let params = gru.parameters();
+        let (w_ih, w_hh) = (params[0].read().unwrap().data().to_vec(), params[1].read().unwrap().data().to_vec());
+        let dot = |w: &[f32], row: usize, v: &[f32]| -> f32 { v.iter().enumerate().map(|(i, x)| w[row * v.len() + i] * x).sum() };
+        let mut expected = Vec::with_capacity(batch * hidden_size);
+        for b in 0..batch {
+            let (xb, hb) = (&x.data()[b * input_size..(b + 1) * input_size], &h.data()[b * hidden_size..(b + 1) * hidden_size]);
+            for j in 0..hidden_size {
+                let r = sigmoid(dot(&w_ih, j, xb) + dot(&w_hh, j, hb));
+                let z = sigmoid(dot(&w_ih, hidden_size + j, xb) + dot(&w_hh, hidden_size + j, hb));
+                let n = (dot(&w_ih, 2 * hidden_size + j, xb) + r * dot(&w_hh, 2 * hidden_size + j, hb)).tanh();
+                expected.push((1.0 - z) * n + z * hb[j]);
+            }
+        }
+        assert_close(&*new_h, &expected, &[batch, hidden_size], 1e-5);
+
+        let input = Tensor::<f32>::from_vec(vec![0.0; 3 * batch * input_size], &[3, batch, input_size])?;
+        let (outputs, last) = gru.forward_sequence(&*input, &*h)?;
+        assert_eq!(outputs.shape(), &[3, batch, hidden_size]);
+        assert_eq!(last.shape(), &[batch, hidden_size]);
+        Ok(())
+    }
+}
@@ -0,0 +1,43 @@
+use crate::nn::{Module, Parameter};
+
+/// An ordered list of modules whose parameters are named by position.
+///
+/// The parameters of the module at index `i` are named `{i}.{name}`, so a
+/// `Sequential` nested as the second layer of another gives names like `1.0.weight`.
+pub struct Sequential {
+    modules: Vec<Box<dyn Module>>,
+}
+
+impl Sequential {
+    /// Creates a container from a list of modules
+    pub fn new(modules: Vec<Box<dyn Module>>) -> Self {
+        Self { modules }
+    }
+
+    /// Returns the module at `index`, if any
+    pub fn get(&self, index: usize) -> Option<&dyn Module> {
+        self.modules.get(index).map(|m| m.as_ref())
+    }
+
+    /// Returns the number of modules
+    pub fn len(&self) -> usize {
+        self.modules.len()
+    }
+
+    /// Returns whether the container holds no modules
+    pub fn is_empty(&self) -> bool {
+        self.modules.is_empty()
+    }
+}
+
+impl Module for Sequential {
+    fn parameters(&self) -> Vec<Parameter> {
+        self.modules.iter().flat_map(|m| m.parameters()).collect()
+    }
+
+    fn named_parameters(&self) -> Vec<(String, Parameter)> {
+        self.modules.iter().enumerate()
+            .flat_map(|(i, m)| m.named_parameters().into_iter().map(move |(name, p)| (format!("{}.{}", i, name), p)))
+            .collect()
+    }
+}
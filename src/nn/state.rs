@@ -0,0 +1,147 @@
+use std::collections::BTreeMap;
+
+use crate::{MlError, MlResult};
+use crate::nn::{read_param, write_param, Module};
+use crate::tensor::{ArcTensor, Tensor, TensorBase, TensorError};
+
+/// Copies every parameter of a module into a map keyed by its dotted name
+///
+/// The map can be written to disk with `save_safetensors` or `save_npz` and read
+/// back with the matching loader.
+///
+/// # Arguments
+/// * `module` - The module whose `named_parameters` are copied
+pub fn state_dict(module: &dyn Module) -> MlResult<BTreeMap<String, ArcTensor<f32>>> {
+    module.named_parameters().into_iter().map(|(name, param)| {
+        let param = read_param(&param)?;
+        Ok((name, Tensor::<f32>::from_vec(param.data().to_vec(), param.shape())?))
+    }).collect()
+}
+
+/// Copies tensors from a map into the parameters of a module with the same names
+///
+/// Nothing is written unless every matched tensor has the right shape.
+///
+/// # Arguments
+/// * `module` - The module to update; parameters are shared, so the module itself is not borrowed mutably
+/// * `state` - The tensors to load, keyed by dotted parameter name
+/// * `strict` - Whether parameters without a tensor and tensors without a parameter are errors
+///
+/// # Returns
+/// * `Ok(())` on success
+/// * `Err(MlError::TensorError)` with `InvalidOperation` listing the mismatched shapes, or
+///   when `strict` is set, the missing and unexpected keys
+pub fn load_state_dict(module: &dyn Module, state: &BTreeMap<String, ArcTensor<f32>>, strict: bool) -> MlResult<()> {
+    let params = module.named_parameters();
+
+    if strict {
+        let missing: Vec<&str> = params.iter()
+            .filter(|(name, _)| !state.contains_key(name))
+            .map(|(name, _)| name.as_str())
+            .collect();
+        let unexpected: Vec<&str> = state.keys()
+            .filter(|key| !params.iter().any(|(name, _)| name == *key))
+            .map(String::as_str)
+            .collect();
+        if !missing.is_empty() || !unexpected.is_empty() {
+            return Err(load_error(format!("missing keys {:?}, unexpected keys {:?}", missing, unexpected)));
+        }
+    }
+
+    let mut mismatched = Vec::new();
+    for (name, param) in &params {
+        if let Some(tensor) = state.get(name) {
+            let expected = read_param(param)?.shape().to_vec();
+            if tensor.shape() != expected {
+                mismatched.push(format!("'{}' expected {:?}, got {:?}", name, expected, tensor.shape()));
+            }
+        }
+    }
+    if !mismatched.is_empty() {
+        return Err(load_error(format!("shape mismatch for {}", mismatched.join(", "))));
+    }
+
+    for (name, param) in &params {
+        if let Some(tensor) = state.get(name) {
+            write_param(param)?.data_mut().copy_from_slice(tensor.data());
+        }
+    }
+    Ok(())
+}
+
+fn load_error(reason: String) -> MlError {
+    MlError::TensorError(TensorError::InvalidOperation { op: "load_state_dict", reason })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::MlResult;
+    use crate::nn::init::Init;
+    use crate::nn::*;
+    use crate::tensor::*;
+
+    fn model(seed: u64) -> MlResult<Sequential> {
+        let mut rng = Rng::new(seed);
+        Ok(Sequential::new(vec![
+            Box::new(LSTMCell::new(3, 2, Init::XavierUniform { gain: 1.0 }, &mut rng)?),
+            Box::new(GRUCell::new(2, 4, Init::XavierUniform { gain: 1.0 }, &mut rng)?),
+        ]))
+    }
+
+    #[test]
+    fn test_state_dict_round_trip() -> MlResult<()> {
+        let source = model(1)?;
+        let state = state_dict(&source)?;
+        assert_eq!(
+            state.keys().collect::<Vec<_>>(),
+            vec!["0.bias", "0.weight", "1.bias_hh", "1.bias_ih", "1.weight_hh", "1.weight_ih"]
+        );
+        assert_eq!(state["0.weight"].shape(), &[8, 5]);
+
+        let path = std::env::temp_dir().join(format!("mit_{}_state.safetensors", std::process::id())).to_string_lossy().into_owned();
+        let named: Vec<(&str, &dyn TensorBase<f32>)> = state.iter().map(|(k, v)| (k.as_str(), &**v)).collect();
+        save_safetensors(&path, &named)?;
+        let loaded = load_safetensors(&path)?.into_iter().collect();
+        std::fs::remove_file(&path).unwrap();
+
+        let target = model(2)?;
+        assert_ne!(state_dict(&target)?["1.weight_ih"].data(), state["1.weight_ih"].data());
+        load_state_dict(&target, &loaded, true)?;
+        for (name, tensor) in state_dict(&target)? {
+            assert_eq!(tensor.data(), state[&name].data());
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_load_state_dict_partial() -> MlResult<()> {
+        let source = state_dict(&model(1)?)?;
+        let target = model(2)?;
+        let before = state_dict(&target)?;
+
+        let mut partial = std::collections::BTreeMap::new();
+        partial.insert("0.weight".to_string(), source["0.weight"].contiguous());
+        partial.insert("extra.weight".to_string(), Tensor::<f32>::scalar(1.0));
+
+        let message = load_state_dict(&target, &partial, true).err().unwrap().to_string();
+        assert!(message.contains("\"0.bias\"") && message.contains("\"1.weight_ih\"") && message.contains("\"extra.weight\""));
+        assert_eq!(state_dict(&target)?["0.weight"].data(), before["0.weight"].data());
+
+        load_state_dict(&target, &partial, false)?;
+        let after = state_dict(&target)?;
+        assert_eq!(after["0.weight"].data(), source["0.weight"].data());
+        assert_eq!(after["1.weight_ih"].data(), before["1.weight_ih"].data());
+        Ok(())
+    }
+
+    #[test]
+    fn test_load_state_dict_shape_mismatch() -> MlResult<()> {
+        let target = model(2)?;
+        let mut state = state_dict(&target)?;
+        state.insert("0.bias".to_string(), Tensor::<f32>::from_vec(vec![0.0; 4], &[4])?);
+
+        let message = load_state_dict(&target, &state, false).err().unwrap().to_string();
+        assert!(message.contains("'0.bias' expected [8], got [4]"));
+        Ok(())
+    }
+}
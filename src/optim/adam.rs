@@ -0,0 +1,289 @@
+use std::collections::BTreeMap;
+
+use crate::{MlError, MlResult};
+use crate::nn::Parameter;
+use crate::nn::write_param;
+use crate::tensor::{ArcTensor, Tensor, TensorBase, TensorError};
+
+/// Per-parameter state of the Adam optimizer.
+pub struct AdamState {
+    /// Number of steps taken for this parameter
+    pub step: usize,
+    /// Exponential moving average of the gradient (first moment)
+    pub exp_avg: Vec<f32>,
+    /// Exponential moving average of the squared gradient (second moment)
+    pub exp_avg_sq: Vec<f32>,
+}
+
+/// Adam optimizer with bias correction, optionally with AdamW-style decoupled weight decay.
+///
+/// The hyperparameters are public fields and can be adjusted between steps.
+pub struct Adam {
+    params: Vec<Parameter>,
+    pub lr: f32,
+    pub betas: (f32, f32),
+    pub eps: f32,
+    pub weight_decay: f32,
+    /// Applies weight decay directly to the parameters (AdamW) instead of
+    /// adding `weight_decay * p` to the gradient
+    pub decoupled_weight_decay: bool,
+    state: Vec<Option<AdamState>>,
+}
+
+impl Adam {
+    /// Creates an Adam optimizer with `betas = (0.9, 0.999)` and `eps = 1e-8`
+    ///
+    /// # Arguments
+    /// * `params` - The parameters to optimize
+    /// * `lr` - The learning rate
+    pub fn new(params: Vec<Parameter>, lr: f32) -> Self {
+        let state = params.iter().map(|_| None).collect();
+        Self {
+            params,
+            lr,
+            betas: (0.9, 0.999),
+            eps: 1e-8,
+            weight_decay: 0.0,
+            decoupled_weight_decay: false,
+            state,
+        }
+    }
+
+    /// Creates an AdamW optimizer, i.e. Adam with decoupled weight decay
+    ///
+    /// # Arguments
+    /// * `params` - The parameters to optimize
+    /// * `lr` - The learning rate
+    /// * `weight_decay` - The decoupled weight decay coefficient
+    pub fn adamw(params: Vec<Parameter>, lr: f32, weight_decay: f32) -> Self {
+        let mut adam = Self::new(params, lr);
+        adam.weight_decay = weight_decay;
+        adam.decoupled_weight_decay = true;
+        adam
+    }
+
+    /// Returns the parameters being optimized
+    pub fn params(&self) -> &[Parameter] {
+        &self.params
+    }
+
+    /// Returns the state of each parameter, `None` until its first step
+    pub fn state(&self) -> &[Option<AdamState>] {
+        &self.state
+    }
+
+    /// Updates every parameter that has a gradient in place
+    ///
+    /// Parameters without a gradient are skipped.
+    pub fn step(&mut self) -> MlResult<()> {
+        let (beta1, beta2) = self.betas;
+
+        for (param, state) in self.params.iter().zip(self.state.iter_mut()) {
+            let mut param = write_param(param)?;
+            let mut grad = match param.grad() {
+                Some(grad) => grad.data().to_vec(),
+                None => continue,
+            };
+
+            if self.weight_decay != 0.0 {
+                if self.decoupled_weight_decay {
+                    let decay = 1.0 - self.lr * self.weight_decay;
+                    param.data_mut().iter_mut().for_each(|p| *p *= decay);
+                } else {
+                    grad.iter_mut().zip(param.data_mut().iter()).for_each(|(g, &p)| *g += self.weight_decay * p);
+                }
+            }
+
+            let state = state.get_or_insert_with(|| AdamState {
+                step: 0,
+                exp_avg: vec![0.0; grad.len()],
+                exp_avg_sq: vec![0.0; grad.len()],
+            });
+            state.step += 1;
+
+            let bias_correction1 = 1.0 - beta1.powi(state.step as i32);
+            let bias_correction2 = 1.0 - beta2.powi(state.step as i32);
+            let step_size = self.lr / bias_correction1;
+
+            for (((p, &g), m), v) in param.data_mut().iter_mut()
+                .zip(&grad)
+                .zip(state.exp_avg.iter_mut())
+                .zip(state.exp_avg_sq.iter_mut())
+            {
+                *m = beta1 * *m + (1.0 - beta1) * g;
+                *v = beta2 * *v + (1.0 - beta2) * g * g;
+                let denom = (*v / bias_correction2).sqrt() + self.eps;
+                *p -= step_size * *m / denom;
+            }
+        }
+        Ok(())
+    }
+
+    /// Clears the gradients of all parameters
+    pub fn zero_grad(&self) -> MlResult<()> {
+        for param in &self.params {
+            write_param(param)?.zero_grad();
+        }
+        Ok(())
+    }
+
+    /// Exports the optimizer state as named tensors
+    ///
+    /// For the `i`-th parameter the keys are `"{i}.step"`, `"{i}.exp_avg"` and
+    /// `"{i}.exp_avg_sq"`. Parameters that have not taken a step are omitted.
+    pub fn state_dict(&self) -> MlResult<BTreeMap<String, ArcTensor<f32>>> {
+        let mut map = BTreeMap::new();
+        for (i, (param, state)) in self.params.iter().zip(&self.state).enumerate() {
+            if let Some(state) = state {
+                let shape = write_param(param)?.shape().to_vec();
+                map.insert(format!("{}.step", i), Tensor::<f32>::scalar(state.step as f32));
+                map.insert(format!("{}.exp_avg", i), Tensor::<f32>::from_vec(state.exp_avg.clone(), &shape)?);
+                map.insert(format!("{}.exp_avg_sq", i), Tensor::<f32>::from_vec(state.exp_avg_sq.clone(), &shape)?);
+            }
+        }
+        Ok(map)
+    }
+
+    /// Restores the optimizer state from tensors produced by `state_dict`
+    ///
+    /// # Returns
+    /// * `Ok(())` on success
+    /// * `Err(MlError::TensorError)` if a moment tensor does not match its parameter's shape
+    pub fn load_state_dict(&mut self, map: &BTreeMap<String, ArcTensor<f32>>) -> MlResult<()> {
+        for (i, (param, state)) in self.params.iter().zip(self.state.iter_mut()).enumerate() {
+            let (step, exp_avg, exp_avg_sq) = match (
+                map.get(&format!("{}.step", i)),
+                map.get(&format!("{}.exp_avg", i)),
+                map.get(&format!("{}.exp_avg_sq", i)),
+            ) {
+                (Some(step), Some(exp_avg), Some(exp_avg_sq)) => (step, exp_avg, exp_avg_sq),
+                _ => {
+                    *state = None;
+                    continue;
+                }
+            };
+
+            let param = write_param(param)?;
+            param.chk_shape(&**exp_avg)?;
+            param.chk_shape(&**exp_avg_sq)?;
+            let step = match step.data().first() {
+                Some(&step) if step >= 0.0 => step as usize,
+                _ => {
+                    return Err(MlError::TensorError(TensorError::InvalidOperation {
+                        op: "adam",
+                        reason: format!("invalid step count for parameter {}", i),
+                    }));
+                }
+            };
+
+            *state = Some(AdamState {
+                step,
+                exp_avg: exp_avg.data().to_vec(),
+                exp_avg_sq: exp_avg_sq.data().to_vec(),
+            });
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::MlResult;
+    use crate::nn::{mse_loss, mse_loss_backward, parameter, Reduction};
+    use crate::optim::{Adam, SGD};
+    use crate::tensor::{Tensor, TensorBase};
+
+    #[test]
+    fn test_adam_first_steps() -> MlResult<()> {
+        let p = parameter(vec![1.0, -2.0], &[2])?;
+        let mut adam = Adam::new(vec![p.clone()], 0.1);
+
+        // reference: bias-corrected moments after each step
+        let grads = [[0.5f32, -1.0], [0.25, 3.0]];
+        let mut expected = [1.0f64, -2.0];
+        let (mut m, mut v) = ([0.0f64; 2], [0.0f64; 2]);
+        for (t, g) in grads.iter().enumerate() {
+            p.write().unwrap().set_grad(&*Tensor::<f32>::from_vec(g.to_vec(), &[2])?)?;
+            adam.step()?;
+
+            for i in 0..2 {
+                let g = g[i] as f64;
+                m[i] = 0.9 * m[i] + 0.1 * g;
+                v[i] = 0.999 * v[i] + 0.001 * g * g;
+                let m_hat = m[i] / (1.0 - 0.9f64.powi(t as i32 + 1));
+                let v_hat = v[i] / (1.0 - 0.999f64.powi(t as i32 + 1));
+                expected[i] -= 0.1 * m_hat / (v_hat.sqrt() + 1e-8);
+            }
+            let data = p.read().unwrap().data().to_vec();
+            for i in 0..2 {
+                assert!((data[i] as f64 - expected[i]).abs() < 1e-6);
+            }
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_adamw_decoupled_decay() -> MlResult<()> {
+        let p = parameter(vec![2.0], &[])?;
+        p.write().unwrap().set_grad(&*Tensor::<f32>::scalar(0.0))?;
+        let mut adam = Adam::adamw(vec![p.clone()], 0.1, 0.5);
+        adam.step()?;
+
+        assert!((p.read().unwrap().data()[0] - 2.0 * (1.0 - 0.1 * 0.5)).abs() < 1e-6);
+        Ok(())
+    }
+
+    #[test]
+    fn test_adam_state_dict_round_trip() -> MlResult<()> {
+        let p = parameter(vec![1.0, 2.0, 3.0, 4.0], &[2, 2])?;
+        let mut adam = Adam::new(vec![p.clone()], 0.01);
+        p.write().unwrap().set_grad(&*Tensor::<f32>::from_vec(vec![1.0, -1.0, 2.0, 0.0], &[2, 2])?)?;
+        adam.step()?;
+
+        let state = adam.state_dict()?;
+        assert_eq!(state["0.step"].data(), &[1.0]);
+        assert_eq!(state["0.exp_avg"].shape(), &[2, 2]);
+
+        let mut restored = Adam::new(vec![p.clone()], 0.01);
+        restored.load_state_dict(&state)?;
+        let restored_state = restored.state()[0].as_ref().unwrap();
+        assert_eq!(restored_state.step, 1);
+        assert_eq!(restored_state.exp_avg, adam.state()[0].as_ref().unwrap().exp_avg);
+        Ok(())
+    }
+
+    fn iterations_to_converge(adam: bool) -> MlResult<usize> {
+        let xs: Vec<f32> = (0..8).map(|i| i as f32 / 8.0).collect();
+        let target = Tensor::<f32>::from_vec(xs.iter().map(|x| 3.0 * x + 2.0).collect(), &[8])?;
+        let w = parameter(vec![0.0], &[])?;
+        let b = parameter(vec![0.0], &[])?;
+        let mut adam_opt = Adam::new(vec![w.clone(), b.clone()], 0.2);
+        let mut sgd_opt = SGD::new(vec![w.clone(), b.clone()], 0.2);
+
+        for iteration in 0..1000 {
+            let (wv, bv) = (w.read().unwrap().data()[0], b.read().unwrap().data()[0]);
+            let pred = Tensor::<f32>::from_vec(xs.iter().map(|x| wv * x + bv).collect(), &[8])?;
+            if mse_loss(&*pred, &*target, Reduction::Mean)?.data()[0] < 1e-2 {
+                return Ok(iteration);
+            }
+
+            let grad = mse_loss_backward(&*pred, &*target, Reduction::Mean)?;
+            let dw: f32 = grad.data().iter().zip(&xs).map(|(g, x)| g * x).sum();
+            let db: f32 = grad.data().iter().sum();
+            w.write().unwrap().set_grad(&*Tensor::<f32>::scalar(dw))?;
+            b.write().unwrap().set_grad(&*Tensor::<f32>::scalar(db))?;
+            if adam { adam_opt.step()? } else { sgd_opt.step()? }
+        }
+        Ok(1000)
+    }
+
+    #[test]
+    fn test_adam_converges_faster_than_sgd() -> MlResult<()> {
+        let adam = iterations_to_converge(true)?;
+        let sgd = iterations_to_converge(false)?;
+
+        assert!(adam < 1000);
+        assert!(adam < sgd);
+        Ok(())
+    }
+}
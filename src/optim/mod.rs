@@ -0,0 +1,5 @@
+mod adam;
+mod sgd;
+
+pub use adam::{Adam, AdamState};
+pub use sgd::SGD;
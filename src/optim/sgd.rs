@@ -0,0 +1,178 @@
+use crate::{MlError, MlResult};
+use crate::nn::Parameter;
+use crate::nn::write_param;
+use crate::tensor::{TensorBase, TensorError};
+
+/// Stochastic gradient descent with optional momentum, Nesterov momentum and weight decay.
+///
+/// The hyperparameters are public fields and can be adjusted between steps.
+pub struct SGD {
+    params: Vec<Parameter>,
+    pub lr: f32,
+    pub momentum: f32,
+    pub nesterov: bool,
+    pub weight_decay: f32,
+    /// Applies weight decay directly to the parameters (`p *= 1 - lr * weight_decay`)
+    /// instead of adding `weight_decay * p` to the gradient
+    pub decoupled_weight_decay: bool,
+    momentum_buffers: Vec<Option<Vec<f32>>>,
+}
+
+impl SGD {
+    /// Creates a plain SGD optimizer without momentum or weight decay
+    ///
+    /// # Arguments
+    /// * `params` - The parameters to optimize
+    /// * `lr` - The learning rate
+    pub fn new(params: Vec<Parameter>, lr: f32) -> Self {
+        let momentum_buffers = vec![None; params.len()];
+        Self {
+            params,
+            lr,
+            momentum: 0.0,
+            nesterov: false,
+            weight_decay: 0.0,
+            decoupled_weight_decay: false,
+            momentum_buffers,
+        }
+    }
+
+    /// Returns the parameters being optimized
+    pub fn params(&self) -> &[Parameter] {
+        &self.params
+    }
+
+    /// Updates every parameter that has a gradient in place
+    ///
+    /// Parameters without a gradient are skipped.
+    ///
+    /// # Returns
+    /// * `Ok(())` on success
+    /// * `Err(MlError::TensorError)` if Nesterov momentum is requested without momentum
+    pub fn step(&mut self) -> MlResult<()> {
+        if self.nesterov && self.momentum <= 0.0 {
+            return Err(MlError::TensorError(TensorError::InvalidOperation {
+                op: "sgd",
+                reason: "nesterov momentum requires a momentum greater than 0".to_string(),
+            }));
+        }
+
+        for (param, buffer) in self.params.iter().zip(self.momentum_buffers.iter_mut()) {
+            let mut param = write_param(param)?;
+            let mut grad = match param.grad() {
+                Some(grad) => grad.data().to_vec(),
+                None => continue,
+            };
+
+            if self.weight_decay != 0.0 {
+                if self.decoupled_weight_decay {
+                    let decay = 1.0 - self.lr * self.weight_decay;
+                    param.data_mut().iter_mut().for_each(|p| *p *= decay);
+                } else {
+                    grad.iter_mut().zip(param.data_mut().iter()).for_each(|(g, &p)| *g += self.weight_decay * p);
+                }
+            }
+
+            if self.momentum != 0.0 {
+                let buf = match buffer {
+                    Some(buf) => {
+                        buf.iter_mut().zip(&grad).for_each(|(b, &g)| *b = self.momentum * *b + g);
+                        buf
+                    }
+                    None => buffer.insert(grad.clone()),
+                };
+                if self.nesterov {
+                    grad.iter_mut().zip(buf.iter()).for_each(|(g, &b)| *g += self.momentum * b);
+                } else {
+                    grad.copy_from_slice(buf);
+                }
+            }
+
+            param.data_mut().iter_mut().zip(&grad).for_each(|(p, &g)| *p -= self.lr * g);
+        }
+        Ok(())
+    }
+
+    /// Clears the gradients of all parameters
+    pub fn zero_grad(&self) -> MlResult<()> {
+        for param in &self.params {
+            write_param(param)?.zero_grad();
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::MlResult;
+    use crate::nn::{mse_loss, mse_loss_backward, parameter, Reduction};
+    use crate::optim::SGD;
+    use crate::tensor::{Tensor, TensorBase};
+
+    #[test]
+    fn test_sgd_single_step() -> MlResult<()> {
+        let p = parameter(vec![1.0, 2.0], &[2])?;
+        p.write().unwrap().set_grad(&*Tensor::<f32>::from_vec(vec![0.5, -1.0], &[2])?)?;
+
+        let mut sgd = SGD::new(vec![p.clone()], 0.1);
+        sgd.step()?;
+        let data = p.read().unwrap().data().to_vec();
+        assert!((data[0] - 0.95).abs() < 1e-6);
+        assert!((data[1] - 2.1).abs() < 1e-6);
+
+        sgd.weight_decay = 0.5;
+        sgd.step()?;
+        let data = p.read().unwrap().data().to_vec();
+        assert!((data[0] - (0.95 - 0.1 * (0.5 + 0.5 * 0.95))).abs() < 1e-6);
+
+        sgd.zero_grad()?;
+        assert!(p.read().unwrap().grad().is_none());
+        Ok(())
+    }
+
+    #[test]
+    fn test_sgd_momentum() -> MlResult<()> {
+        let p = parameter(vec![0.0], &[])?;
+        let mut sgd = SGD::new(vec![p.clone()], 0.1);
+        sgd.momentum = 0.9;
+
+        for _ in 0..3 {
+            p.write().unwrap().set_grad(&*Tensor::<f32>::scalar(1.0))?;
+            sgd.step()?;
+        }
+        // buffers: 1.0, 1.9, 2.71
+        assert!((p.read().unwrap().data()[0] + 0.1 * (1.0 + 1.9 + 2.71)).abs() < 1e-6);
+
+        sgd.nesterov = true;
+        sgd.momentum = 0.0;
+        assert!(sgd.step().is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_sgd_linear_regression() -> MlResult<()> {
+        let xs: Vec<f32> = (0..8).map(|i| i as f32 / 8.0).collect();
+        let target = Tensor::<f32>::from_vec(xs.iter().map(|x| 3.0 * x + 2.0).collect(), &[8])?;
+        let w = parameter(vec![0.0], &[])?;
+        let b = parameter(vec![0.0], &[])?;
+        let mut sgd = SGD::new(vec![w.clone(), b.clone()], 0.5);
+        sgd.momentum = 0.5;
+
+        let mut losses = Vec::new();
+        for _ in 0..100 {
+            let (wv, bv) = (w.read().unwrap().data()[0], b.read().unwrap().data()[0]);
+            let pred = Tensor::<f32>::from_vec(xs.iter().map(|x| wv * x + bv).collect(), &[8])?;
+            losses.push(mse_loss(&*pred, &*target, Reduction::Mean)?.data()[0]);
+
+            let grad = mse_loss_backward(&*pred, &*target, Reduction::Mean)?;
+            let dw: f32 = grad.data().iter().zip(&xs).map(|(g, x)| g * x).sum();
+            let db: f32 = grad.data().iter().sum();
+            w.write().unwrap().set_grad(&*Tensor::<f32>::scalar(dw))?;
+            b.write().unwrap().set_grad(&*Tensor::<f32>::scalar(db))?;
+            sgd.step()?;
+        }
+
+        assert!(losses[99] < losses[0] * 1e-2);
+        Ok(())
+    }
+}
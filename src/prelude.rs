@@ -0,0 +1,21 @@
+//! Everything needed to work with tensors in one import.
+//!
+//! ```
+//! use MIT::prelude::*;
+//!
+//! # fn main() -> MlResult<()> {
+//! let a = Tensor::<f32>::new(vec![vec![1.0, 2.0], vec![3.0, 4.0]])?;
+//! let b = ops!(a, Matmul, a)?;
+//! assert_shape_eq!(b, [2, 2]);
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! `ops!` calls `deref()` on its operands, so `std::ops::Deref` is re-exported here as well.
+
+pub use std::ops::Deref;
+
+pub use crate::{MlError, MlResult};
+pub use crate::tensor::{ArcTensor, AsTensor, CollectTensor, FormatError, Function, Tensor, TensorBase, TensorBuilder, TensorError};
+pub use crate::tensor::{Abs, Add, Concat, Div, Exp, Hardtanh, Log, Matmax, Matmul, Mul, Neg, Pow, Relu6, Sqrt, Square, Sub, Topk, Var};
+pub use crate::{assert_shape_eq, assert_tensor_close, ops, scalar_ops};
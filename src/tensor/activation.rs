@@ -0,0 +1,390 @@
+use std::str::FromStr;
+
+use crate::{MlError, MlResult};
+use crate::tensor::{normalize_dim, ArcTensor, Tensor, TensorBase, TensorError};
+
+impl dyn TensorBase<f32> + '_ {
+    /// Computes the softmax along a dimension
+    ///
+    /// The maximum of each slice is subtracted before exponentiating, so large
+    /// inputs do not overflow. Slices that are entirely `-inf` yield zeros.
+    ///
+    /// # Arguments
+    /// * `dim` - The dimension to normalize over; negative values index from the end
+    ///
+    /// # Returns
+    /// A new tensor with the same shape whose slices along `dim` sum to 1
+    pub fn softmax(&self, dim: i32) -> MlResult<ArcTensor<f32>> {
+        let dim = normalize_dim(dim, self.ndim())?;
+        let stride: usize = self.shape()[dim + 1..].iter().product();
+        let outer_dims: usize = self.shape()[..dim].iter().product();
+        let dim_size = self.shape()[dim];
+
+        let mut result = vec![0.0; self.data().len()];
+        for i in 0..outer_dims {
+            for j in 0..stride {
+                let offsets = (0..dim_size).map(|k| (i * dim_size + k) * stride + j);
+                let max = offsets.clone().map(|idx| self.data()[idx]).fold(f32::NEG_INFINITY, f32::max);
+                if max == f32::NEG_INFINITY {
+                    continue;
+                }
+
+                let mut sum = 0.0;
+                for idx in offsets.clone() {
+                    result[idx] = (self.data()[idx] - max).exp();
+                    sum += result[idx];
+                }
+                offsets.for_each(|idx| result[idx] /= sum);
+            }
+        }
+
+        Tensor::<f32>::from_vec(result, self.shape())
+    }
+
+    /// Computes the gradient of the input of `softmax` from the gradient of its output
+    ///
+    /// `self` is the softmax output. Uses the closed form
+    /// `grad_in = output * (grad - sum(grad * output, dim))`, which avoids building the
+    /// `[n, n]` Jacobian of each slice.
+    ///
+    /// # Arguments
+    /// * `grad` - The gradient of the softmax output, with the same shape as `self`
+    /// * `dim` - The dimension softmax normalized over; negative values index from the end
+    ///
+    /// # Returns
+    /// * `Ok(ArcTensor)` with the same shape as `self`
+    /// * `Err(MlError::TensorError)` if the shapes differ or `dim` is out of range
+    pub fn softmax_backward(&self, grad: &dyn TensorBase<f32>, dim: i32) -> MlResult<ArcTensor<f32>> {
+        self.chk_shape(grad)?;
+        let dim = normalize_dim(dim, self.ndim())?;
+        let stride: usize = self.shape()[dim + 1..].iter().product();
+        let outer_dims: usize = self.shape()[..dim].iter().product();
+        let dim_size = self.shape()[dim];
+        let (output, grad) = (self.data(), grad.data());
+
+        let mut result = vec![0.0; output.len()];
+        for i in 0..outer_dims {
+            for j in 0..stride {
+                let offsets = (0..dim_size).map(|k| (i * dim_size + k) * stride + j);
+                let dot: f32 = offsets.clone().map(|idx| grad[idx] * output[idx]).sum();
+                offsets.for_each(|idx| result[idx] = output[idx] * (grad[idx] - dot));
+            }
+        }
+
+        Tensor::<f32>::from_vec(result, self.shape())
+    }
+
+    /// Keeps the most probable elements along `dim` whose cumulative probability reaches `p`
+    ///
+    /// This is the filtering step of nucleus (top-p) sampling. Probabilities are filtered
+    /// by setting elements to 0; logits are ranked by their softmax and filtered elements
+    /// are set to `-inf`. The most probable element of each slice is always kept.
+    ///
+    /// # Arguments
+    /// * `p` - The cumulative probability to keep, in `(0, 1]`
+    /// * `dim` - The dimension to filter along; negative values index from the end
+    /// * `input` - Whether `self` holds logits or probabilities
+    ///
+    /// # Returns
+    /// * `Ok(ArcTensor)` with the same shape as `self`
+    /// * `Err(MlError::TensorError)` with `InvalidOperation` if `p` is out of range, or
+    ///   `InvalidAxis` if `dim` is out of range
+    pub fn top_p_filter(&self, p: f32, dim: i32, input: TopPInput) -> MlResult<ArcTensor<f32>> {
+        if !(p > 0.0 && p <= 1.0) {
+            return Err(MlError::TensorError(TensorError::InvalidOperation {
+                op: "top_p_filter",
+                reason: format!("p must be in (0, 1], got {}", p),
+            }));
+        }
+        let dim_index = normalize_dim(dim, self.ndim())?;
+        let stride: usize = self.shape()[dim_index + 1..].iter().product();
+        let outer_dims: usize = self.shape()[..dim_index].iter().product();
+        let dim_size = self.shape()[dim_index];
+        let slice_offsets = |i: usize, j: usize| (0..dim_size).map(move |k| (i * dim_size + k) * stride + j);
+
+        let (probs, filler) = match input {
+            TopPInput::Probs => (self.data().to_vec(), 0.0),
+            TopPInput::Logits => (self.softmax(dim)?.data().to_vec(), f32::NEG_INFINITY),
+        };
+
+        let mut result = self.data().to_vec();
+        for i in 0..outer_dims {
+            for j in 0..stride {
+                let mut offsets: Vec<usize> = slice_offsets(i, j).collect();
+                offsets.sort_by(|&a, &b| probs[b].total_cmp(&probs[a]));
+                let mut cumulative = 0.0;
+                for idx in offsets {
+                    if cumulative >= p {
+                        result[idx] = filler;
+                    }
+                    cumulative += probs[idx];
+                }
+            }
+        }
+
+        Tensor::<f32>::from_vec(result, self.shape())
+    }
+
+    /// Applies the logistic sigmoid `1 / (1 + exp(-x))` element-wise
+    pub fn sigmoid(&self) -> MlResult<ArcTensor<f32>> {
+        let data = self.data().iter().map(|&x| 1.0 / (1.0 + (-x).exp())).collect();
+        Tensor::<f32>::from_vec(data, self.shape())
+    }
+
+    /// Applies the hyperbolic tangent element-wise
+    pub fn tanh(&self) -> MlResult<ArcTensor<f32>> {
+        let data = self.data().iter().map(|&x| x.tanh()).collect();
+        Tensor::<f32>::from_vec(data, self.shape())
+    }
+
+    /// Applies `max(x, 0)` element-wise
+    pub fn relu(&self) -> MlResult<ArcTensor<f32>> {
+        let data = self.data().iter().map(|&x| x.max(0.0)).collect();
+        Tensor::<f32>::from_vec(data, self.shape())
+    }
+
+    /// Applies the Gaussian error linear unit element-wise
+    ///
+    /// Uses the tanh approximation `0.5 * x * (1 + tanh(sqrt(2 / pi) * (x + 0.044715 * x^3)))`,
+    /// which stays within about `1e-3` of the exact `x * Phi(x)`.
+    pub fn gelu(&self) -> MlResult<ArcTensor<f32>> {
+        let scale = (2.0 / std::f32::consts::PI).sqrt();
+        let data = self.data().iter().map(|&x| 0.5 * x * (1.0 + (scale * (x + 0.044715 * x * x * x)).tanh())).collect();
+        Tensor::<f32>::from_vec(data, self.shape())
+    }
+
+    /// Applies the sigmoid linear unit `x * sigmoid(x)` element-wise, also known as swish
+    pub fn silu(&self) -> MlResult<ArcTensor<f32>> {
+        let data = self.data().iter().map(|&x| x / (1.0 + (-x).exp())).collect();
+        Tensor::<f32>::from_vec(data, self.shape())
+    }
+}
+
+/// What the input of `top_p_filter` holds, which decides how filtered elements are filled
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TopPInput {
+    /// Unnormalized scores; filtered elements become `-inf`
+    Logits,
+    /// Probabilities along the filtered dimension; filtered elements become 0
+    Probs,
+}
+
+/// Activation functions that can be selected by name, e.g. from a model config
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Activation {
+    Relu,
+    Gelu,
+    Tanh,
+    Sigmoid,
+    Silu,
+}
+
+impl Activation {
+    /// Applies the activation to `tensor`
+    pub fn apply(self, tensor: &dyn TensorBase<f32>) -> MlResult<ArcTensor<f32>> {
+        match self {
+            Activation::Relu => tensor.relu(),
+            Activation::Gelu => tensor.gelu(),
+            Activation::Tanh => tensor.tanh(),
+            Activation::Sigmoid => tensor.sigmoid(),
+            Activation::Silu => tensor.silu(),
+        }
+    }
+}
+
+impl FromStr for Activation {
+    type Err = MlError;
+
+    /// Parses a case-insensitive activation name such as `"relu"` or `"GELU"`; `"swish"` is accepted for `Silu`
+    fn from_str(s: &str) -> MlResult<Self> {
+        match s.to_ascii_lowercase().as_str() {
+            "relu" => Ok(Activation::Relu),
+            "gelu" => Ok(Activation::Gelu),
+            "tanh" => Ok(Activation::Tanh),
+            "sigmoid" => Ok(Activation::Sigmoid),
+            "silu" | "swish" => Ok(Activation::Silu),
+            _ => Err(MlError::TensorError(TensorError::InvalidOperation {
+                op: "activation_by_name",
+                reason: format!("unknown activation '{}'", s),
+            })),
+        }
+    }
+}
+
+/// A plain function applying an activation, as returned by [`activation_by_name`]
+pub type ActivationFn = fn(&dyn TensorBase<f32>) -> MlResult<ArcTensor<f32>>;
+
+/// Looks up an activation function by name
+///
+/// # Arguments
+/// * `name` - A case-insensitive name: `"relu"`, `"gelu"`, `"tanh"`, `"sigmoid"` or `"silu"`
+///
+/// # Returns
+/// * `Ok(fn)` applying the activation
+/// * `Err(MlError::TensorError)` with `InvalidOperation` naming the activation if it is unknown
+pub fn activation_by_name(name: &str) -> MlResult<ActivationFn> {
+    Ok(match name.parse()? {
+        Activation::Relu => |t| t.relu(),
+        Activation::Gelu => |t| t.gelu(),
+        Activation::Tanh => |t| t.tanh(),
+        Activation::Sigmoid => |t| t.sigmoid(),
+        Activation::Silu => |t| t.silu(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{MlError, MlResult};
+    use crate::tensor::*;
+
+    #[test]
+    fn test_softmax() -> MlResult<()> {
+        let tensor = Tensor::<f32>::new(vec![vec![1.0, 2.0, 3.0], vec![1000.0, 1000.0, f32::NEG_INFINITY]])?;
+        let result = tensor.softmax(-1)?;
+        let total = 1.0 + 1f32.exp() + 2f32.exp();
+
+        assert!((result.data()[0] - 1.0 / total).abs() < 1e-6);
+        assert!((result.data()[2] - 2f32.exp() / total).abs() < 1e-6);
+        assert_eq!(&result.data()[3..], &[0.5, 0.5, 0.0]);
+
+        let columns = tensor.softmax(0)?;
+        assert_eq!(columns.data()[0], 0.0);
+        assert_eq!(columns.data()[3], 1.0);
+        Ok(())
+    }
+
+    #[test]
+    fn test_softmax_fully_masked() -> MlResult<()> {
+        let tensor = Tensor::<f32>::from_vec(vec![f32::NEG_INFINITY; 2], &[1, 2])?;
+
+        assert_eq!(tensor.softmax(1)?.data(), &[0.0, 0.0]);
+        assert!(tensor.softmax(2).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_softmax_backward_finite_differences() -> MlResult<()> {
+        let input = vec![0.3, -1.2, 2.0, 0.5, 0.1, 0.9, -0.4, 1.5];
+        let upstream = Tensor::<f32>::from_vec(vec![0.7, -0.2, 1.1, 0.4, -0.9, 0.3, 0.5, -0.6], &[2, 4])?;
+        // loss = sum(upstream * softmax(input)), so d loss / d output = upstream
+        let loss = |data: &[f32], dim: i32| -> MlResult<f32> {
+            let output = Tensor::<f32>::from_vec(data.to_vec(), &[2, 4])?.softmax(dim)?;
+            Ok(output.data().iter().zip(upstream.data()).map(|(o, g)| o * g).sum())
+        };
+
+        for dim in [1, 0] {
+            let output = Tensor::<f32>::from_vec(input.clone(), &[2, 4])?.softmax(dim)?;
+            let analytic = output.softmax_backward(&*upstream, dim)?;
+            assert_eq!(analytic.shape(), &[2, 4]);
+
+            let eps = 1e-2;
+            for i in 0..input.len() {
+                let (mut plus, mut minus) = (input.clone(), input.clone());
+                plus[i] += eps;
+                minus[i] -= eps;
+                let numeric = (loss(&plus, dim)? - loss(&minus, dim)?) / (2.0 * eps);
+                assert!((analytic.data()[i] - numeric).abs() < 1e-3, "dim {} element {}: {} vs {}", dim, i, analytic.data()[i], numeric);
+            }
+        }
+
+        let output = Tensor::<f32>::from_vec(input.clone(), &[2, 4])?.softmax(1)?;
+        let wrong = Tensor::<f32>::from_vec(vec![1.0; 4], &[4])?;
+        assert!(output.softmax_backward(&*wrong, 1).is_err());
+        assert!(output.softmax_backward(&*upstream, 2).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_top_p_filter_probabilities() -> MlResult<()> {
+        let probs = Tensor::<f32>::new(vec![vec![0.1, 0.5, 0.15, 0.25], vec![0.7, 0.1, 0.1, 0.1]])?;
+
+        let filtered = probs.top_p_filter(0.7, -1, TopPInput::Probs)?;
+        assert_eq!(filtered.data(), &[0.0, 0.5, 0.0, 0.25, 0.7, 0.0, 0.0, 0.0]);
+        assert_eq!(probs.top_p_filter(1.0, 1, TopPInput::Probs)?.data(), probs.data());
+        // the most probable element survives even for a tiny p
+        assert_eq!(probs.top_p_filter(0.01, 1, TopPInput::Probs)?.data(), &[0.0, 0.5, 0.0, 0.0, 0.7, 0.0, 0.0, 0.0]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_top_p_filter_logits() -> MlResult<()> {
+        // softmax of these logits is roughly [0.64, 0.24, 0.09, 0.03]
+        let logits = Tensor::<f32>::from_vec(vec![3.0, 2.0, 1.0, 0.0], &[4])?;
+        let filtered = logits.top_p_filter(0.8, 0, TopPInput::Logits)?;
+        assert_eq!(filtered.data(), &[3.0, 2.0, f32::NEG_INFINITY, f32::NEG_INFINITY]);
+
+        let columns = Tensor::<f32>::from_vec(vec![0.0, 5.0, 5.0, 0.0], &[2, 2])?;
+        assert_eq!(columns.top_p_filter(0.5, 0, TopPInput::Logits)?.data(), &[f32::NEG_INFINITY, 5.0, 5.0, f32::NEG_INFINITY]);
+
+        // non-negative logits summing to 1 are still logits
+        let uniform = Tensor::<f32>::from_vec(vec![1.0, 0.0, 0.0], &[3])?;
+        let filtered = uniform.top_p_filter(0.5, 0, TopPInput::Logits)?;
+        assert_eq!(filtered.data(), &[1.0, f32::NEG_INFINITY, f32::NEG_INFINITY]);
+        let halves = Tensor::<f32>::from_vec(vec![0.5, 0.5], &[2])?;
+        assert_eq!(halves.top_p_filter(0.5, 0, TopPInput::Logits)?.data(), &[0.5, f32::NEG_INFINITY]);
+
+        assert!(logits.top_p_filter(0.0, 0, TopPInput::Logits).is_err());
+        assert!(logits.top_p_filter(1.5, 0, TopPInput::Logits).is_err());
+        assert!(logits.top_p_filter(f32::NAN, 0, TopPInput::Logits).is_err());
+        assert!(logits.top_p_filter(0.5, 1, TopPInput::Logits).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_sigmoid_tanh() -> MlResult<()> {
+        let tensor = Tensor::<f32>::from_vec(vec![0.0, 2.0, -100.0], &[3])?;
+
+        let sigmoid = tensor.sigmoid()?;
+        assert_eq!(sigmoid.data()[0], 0.5);
+        assert!((sigmoid.data()[1] - 0.880797).abs() < 1e-6);
+        assert_eq!(sigmoid.data()[2], 0.0);
+
+        let tanh = tensor.tanh()?;
+        assert_eq!(tanh.data(), &[0.0, 2f32.tanh(), -1.0]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_relu_gelu_silu() -> MlResult<()> {
+        let tensor = Tensor::<f32>::from_vec(vec![-2.0, 0.0, 1.0, 3.0], &[2, 2])?;
+
+        assert_eq!(tensor.relu()?.data(), &[0.0, 0.0, 1.0, 3.0]);
+        // x * Phi(x) for the same inputs
+        let gelu = tensor.gelu()?;
+        for (a, e) in gelu.data().iter().zip([-0.04550, 0.0, 0.84134, 2.99595]) {
+            assert!((a - e).abs() < 1e-3, "{:?}", gelu.data());
+        }
+        let silu = tensor.silu()?;
+        assert_eq!(silu.shape(), &[2, 2]);
+        assert!((silu.data()[2] - 0.731059).abs() < 1e-6);
+        assert!((silu.data()[0] + 0.238406).abs() < 1e-6);
+        Ok(())
+    }
+
+    #[test]
+    fn test_activation_by_name() -> MlResult<()> {
+        let tensor = Tensor::<f32>::from_vec(vec![-1.0, 0.5], &[2])?;
+
+        for (name, expected) in [
+            ("relu", tensor.relu()?),
+            ("GELU", tensor.gelu()?),
+            ("tanh", tensor.tanh()?),
+            ("Sigmoid", tensor.sigmoid()?),
+            ("silu", tensor.silu()?),
+            ("swish", tensor.silu()?),
+        ] {
+            let activation = activation_by_name(name)?;
+            assert_eq!(activation(&*tensor)?.data(), expected.data(), "{}", name);
+            assert_eq!(name.parse::<Activation>()?.apply(&*tensor)?.data(), expected.data());
+        }
+
+        match activation_by_name("mish").err().unwrap() {
+            MlError::TensorError(TensorError::InvalidOperation { op, reason }) => {
+                assert_eq!(op, "activation_by_name");
+                assert!(reason.contains("'mish'"), "{}", reason);
+            }
+            e => panic!("unexpected error {}", e),
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,81 @@
+use ::approx::{AbsDiffEq, RelativeEq};
+
+use crate::tensor::{ArcTensor, AsTensor, Tensor};
+
+/// Implements the `approx` comparison traits for a tensor type
+///
+/// Tensors of different shapes are never equal; otherwise every pair of elements must
+/// be within the tolerances, as for the element type.
+macro_rules! impl_approx {
+    ($tensor:ty, $elem:ty) => {
+        impl AbsDiffEq for $tensor {
+            type Epsilon = $elem;
+
+            fn default_epsilon() -> $elem {
+                <$elem as AbsDiffEq>::default_epsilon()
+            }
+
+            fn abs_diff_eq(&self, other: &Self, epsilon: $elem) -> bool {
+                let (a, b) = (AsTensor::<$elem>::as_tensor(self), AsTensor::<$elem>::as_tensor(other));
+                a.shape() == b.shape() && a.iter().zip(b.iter()).all(|(x, y)| x.abs_diff_eq(y, epsilon))
+            }
+        }
+
+        impl RelativeEq for $tensor {
+            fn default_max_relative() -> $elem {
+                <$elem as RelativeEq>::default_max_relative()
+            }
+
+            fn relative_eq(&self, other: &Self, epsilon: $elem, max_relative: $elem) -> bool {
+                let (a, b) = (AsTensor::<$elem>::as_tensor(self), AsTensor::<$elem>::as_tensor(other));
+                a.shape() == b.shape() && a.iter().zip(b.iter()).all(|(x, y)| x.relative_eq(y, epsilon, max_relative))
+            }
+        }
+    };
+}
+
+impl_approx!(Tensor<f32>, f32);
+impl_approx!(Tensor<f64>, f64);
+impl_approx!(ArcTensor<f32>, f32);
+impl_approx!(ArcTensor<f64>, f64);
+
+#[cfg(test)]
+mod tests {
+    use ::approx::{abs_diff_eq, assert_abs_diff_eq, assert_relative_eq, relative_eq};
+
+    use crate::MlResult;
+    use crate::tensor::*;
+
+    #[test]
+    fn test_approx_arc_tensor() -> MlResult<()> {
+        let a = Tensor::<f32>::from_vec(vec![1.0, 100.0], &[2])?;
+        let b = Tensor::<f32>::from_vec(vec![1.0 + 1e-6, 100.0 + 1e-4], &[2])?;
+
+        assert_relative_eq!(a, b, max_relative = 1e-5);
+        assert!(!relative_eq!(a, b, max_relative = 1e-8));
+        assert_abs_diff_eq!(a, b, epsilon = 1e-3);
+        assert!(!abs_diff_eq!(a, b, epsilon = 1e-5));
+
+        // same elements, different shape
+        let reshaped = Tensor::<f32>::from_vec(vec![1.0, 100.0], &[1, 2])?;
+        assert!(!abs_diff_eq!(a, reshaped, epsilon = 1.0));
+        assert!(!relative_eq!(a, reshaped, max_relative = 1.0));
+        Ok(())
+    }
+
+    #[test]
+    fn test_approx_owned_tensor() -> MlResult<()> {
+        let a = Tensor::<f64>::from_vec_owned(vec![0.1 + 0.2, 1.0], &[2])?;
+        let b = Tensor::<f64>::from_vec_owned(vec![0.3, 1.0], &[2])?;
+        assert_ne!(a, b);
+        assert_relative_eq!(a, b);
+        assert_abs_diff_eq!(a, b);
+        assert!(!abs_diff_eq!(a, b, epsilon = 0.0));
+
+        let c = Tensor::<f32>::from_vec_owned(vec![2.0], &[1])?;
+        let d = Tensor::<f32>::from_vec_owned(vec![2.5], &[1])?;
+        assert_abs_diff_eq!(c, d, epsilon = 0.5);
+        assert!(!relative_eq!(c, d, max_relative = 0.1));
+        Ok(())
+    }
+}
@@ -0,0 +1,87 @@
+use std::fmt::Debug;
+
+use ndarray::{ArrayD, ArrayViewD, IxDyn};
+
+use crate::tensor::{ArcTensor, Tensor, TensorBase};
+
+/// Copies the tensor into a row-major `ndarray` array of the same shape
+fn to_array<T: Debug + Clone + 'static>(tensor: &dyn TensorBase<T>) -> ArrayD<T> {
+    ArrayD::from_shape_vec(IxDyn(tensor.shape()), tensor.data().to_vec())
+        .expect("tensor data always matches its shape")
+}
+
+/// Copies an array of any memory layout into a tensor, in logical (row-major) order
+fn from_array_view<T: Debug + Clone + 'static>(array: ArrayViewD<'_, T>) -> Tensor<T> {
+    let data = array.as_standard_layout().iter().cloned().collect();
+    Tensor::<T>::from_vec_owned(data, array.shape())
+        .expect("array data always matches its shape")
+}
+
+impl<T: Debug + Clone + 'static> From<&Tensor<T>> for ArrayD<T> {
+    fn from(tensor: &Tensor<T>) -> Self {
+        to_array(tensor)
+    }
+}
+
+impl<T: Debug + Clone + 'static> From<&ArcTensor<T>> for ArrayD<T> {
+    fn from(tensor: &ArcTensor<T>) -> Self {
+        to_array(&**tensor)
+    }
+}
+
+impl<T: Debug + Clone + 'static> From<ArrayD<T>> for Tensor<T> {
+    fn from(array: ArrayD<T>) -> Self {
+        from_array_view(array.view())
+    }
+}
+
+impl<T: Debug + Clone + 'static> From<ArrayViewD<'_, T>> for Tensor<T> {
+    fn from(array: ArrayViewD<'_, T>) -> Self {
+        from_array_view(array)
+    }
+}
+
+impl<T: Debug + Clone + 'static> From<ArrayD<T>> for ArcTensor<T> {
+    fn from(array: ArrayD<T>) -> Self {
+        ArcTensor::new(Tensor::from(array))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ndarray::{ArrayD, IxDyn};
+
+    use crate::MlResult;
+    use crate::tensor::*;
+
+    #[test]
+    fn test_tensor_to_array() -> MlResult<()> {
+        let tensor = Tensor::<f32>::from_vec((0..24).map(|x| x as f32).collect(), &[2, 3, 4])?;
+        let array = ArrayD::from(&tensor);
+
+        assert_eq!(array.shape(), &[2, 3, 4]);
+        for (i, j, k) in (0..2).flat_map(|i| (0..3).flat_map(move |j| (0..4).map(move |k| (i, j, k)))) {
+            assert_eq!(array[[i, j, k]], tensor.data()[tensor.index(&[i, j, k]).unwrap()]);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_array_to_tensor() {
+        let array = ArrayD::from_shape_fn(IxDyn(&[2, 3, 4]), |idx| (idx[0] * 100 + idx[1] * 10 + idx[2]) as f32);
+        let tensor = Tensor::from(array.clone());
+        assert_eq!(tensor.shape(), &[2, 3, 4]);
+        assert_eq!(ArrayD::from(&tensor), array);
+
+        // permuted axes give a non-standard layout that must be copied in logical order
+        let permuted = array.view().permuted_axes(IxDyn(&[2, 0, 1]));
+        let tensor = Tensor::from(permuted.clone());
+        assert_eq!(tensor.shape(), &[4, 2, 3]);
+        for (k, i, j) in (0..4).flat_map(|k| (0..2).flat_map(move |i| (0..3).map(move |j| (k, i, j)))) {
+            assert_eq!(tensor.data()[tensor.index(&[k, i, j]).unwrap()], permuted[[k, i, j]]);
+        }
+
+        let arc = ArcTensor::from(array.clone());
+        assert_eq!(ArrayD::from(&arc), array);
+    }
+}
@@ -0,0 +1,213 @@
+use std::sync::Arc;
+
+use arrow_array::cast::AsArray;
+use arrow_array::types::{Float32Type, Float64Type, Int16Type, Int32Type, Int64Type, Int8Type, UInt16Type, UInt32Type, UInt64Type, UInt8Type};
+use arrow_array::{Array, ArrayRef, ArrowPrimitiveType, FixedSizeListArray, Float32Array, PrimitiveArray, RecordBatch};
+use arrow_schema::{DataType, Field, Schema};
+
+use crate::{MlError, MlResult};
+use crate::tensor::{ArcTensor, Tensor, TensorBase};
+
+/// How null values in Arrow data are converted
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum NullPolicy {
+    /// Fail with an error naming the column and row of the first null
+    #[default]
+    Error,
+    /// Replace every null with the given value
+    Fill(f32),
+}
+
+impl Tensor<f32> {
+    /// Converts an Arrow array into a tensor
+    ///
+    /// A primitive numeric array of length `n` becomes a `[n]` tensor and a
+    /// `FixedSizeListArray` of `n` lists of `k` numbers becomes `[n, k]`. Integer and
+    /// `Float64` values are converted to `f32`.
+    ///
+    /// # Arguments
+    /// * `array` - The array to convert
+    /// * `nulls` - What to do with null values; a null list fills its whole row
+    ///
+    /// # Returns
+    /// * `Ok(ArcTensor)` on success
+    /// * `Err(MlError::StringError)` for a null under `NullPolicy::Error` or a non-numeric type
+    pub fn from_arrow(array: &dyn Array, nulls: NullPolicy) -> MlResult<ArcTensor<f32>> {
+        match array.data_type() {
+            DataType::FixedSizeList(field, size) => {
+                let lists = array.as_fixed_size_list();
+                let size = *size as usize;
+                let mut data = Vec::with_capacity(lists.len() * size);
+                for row in 0..lists.len() {
+                    match (lists.is_null(row), nulls) {
+                        (false, _) => data.extend(column_values(field.name(), &lists.value(row), nulls)?),
+                        (true, NullPolicy::Fill(value)) => data.extend(std::iter::repeat_n(value, size)),
+                        (true, NullPolicy::Error) => return Err(null_error(field.name(), row)),
+                    }
+                }
+                Tensor::<f32>::from_vec(data, &[lists.len(), size])
+            }
+            _ => Tensor::<f32>::from_vec(column_values("array", array, nulls)?, &[array.len()]),
+        }
+    }
+
+    /// Converts the numeric columns of a record batch into a `[rows, columns]` tensor
+    ///
+    /// # Arguments
+    /// * `batch` - The record batch; every column must be numeric
+    /// * `nulls` - What to do with null values
+    ///
+    /// # Returns
+    /// * `Ok(ArcTensor)` with the columns in schema order
+    /// * `Err(MlError::StringError)` naming the column of a null or of a non-numeric type
+    pub fn from_record_batch(batch: &RecordBatch, nulls: NullPolicy) -> MlResult<ArcTensor<f32>> {
+        let (rows, cols) = (batch.num_rows(), batch.num_columns());
+        let columns = batch.schema().fields().iter().zip(batch.columns())
+            .map(|(field, column)| column_values(field.name(), column, nulls))
+            .collect::<MlResult<Vec<_>>>()?;
+
+        let data = (0..rows * cols).map(|i| columns[i % cols][i / cols]).collect();
+        Tensor::<f32>::from_vec(data, &[rows, cols])
+    }
+}
+
+impl dyn TensorBase<f32> + '_ {
+    /// Converts a 1-D tensor into a `Float32Array` without nulls
+    pub fn to_arrow(&self) -> MlResult<Float32Array> {
+        match self.shape() {
+            [_] => Ok(Float32Array::from(self.data().to_vec())),
+            shape => Err(arrow_error(format!("expected a 1-D tensor, got shape {:?}", shape))),
+        }
+    }
+
+    /// Converts a `[n, k]` tensor into a `FixedSizeListArray` of `n` lists of `k` values
+    pub fn to_arrow_fixed_size_list(&self) -> MlResult<FixedSizeListArray> {
+        let size = match self.shape() {
+            [_, k] => *k as i32,
+            shape => return Err(arrow_error(format!("expected a 2-D tensor, got shape {:?}", shape))),
+        };
+        let field = Arc::new(Field::new_list_field(DataType::Float32, false));
+        let values: ArrayRef = Arc::new(Float32Array::from(self.data().to_vec()));
+        FixedSizeListArray::try_new(field, size, values, None).map_err(|e| arrow_error(e.to_string()))
+    }
+
+    /// Converts a `[rows, columns]` tensor into a record batch of `Float32` columns
+    ///
+    /// # Arguments
+    /// * `column_names` - One name per column
+    pub fn to_record_batch(&self, column_names: &[&str]) -> MlResult<RecordBatch> {
+        let cols = match self.shape() {
+            [_, cols] if *cols == column_names.len() => *cols,
+            shape => return Err(arrow_error(format!(
+                "expected a 2-D tensor with {} columns, got shape {:?}", column_names.len(), shape
+            ))),
+        };
+        let fields: Vec<Field> = column_names.iter().map(|name| Field::new(*name, DataType::Float32, false)).collect();
+        let columns: Vec<ArrayRef> = (0..cols).map(|c| {
+            Arc::new(Float32Array::from_iter_values(self.data().iter().skip(c).step_by(cols).copied())) as ArrayRef
+        }).collect();
+        RecordBatch::try_new(Arc::new(Schema::new(fields)), columns).map_err(|e| arrow_error(e.to_string()))
+    }
+}
+
+/// Converts a numeric Arrow array into `f32` values, applying the null policy
+fn column_values(name: &str, array: &dyn Array, nulls: NullPolicy) -> MlResult<Vec<f32>> {
+    match array.data_type() {
+        DataType::Float32 => primitive_values(name, array.as_primitive::<Float32Type>(), nulls, |v| v),
+        DataType::Float64 => primitive_values(name, array.as_primitive::<Float64Type>(), nulls, |v| v as f32),
+        DataType::Int8 => primitive_values(name, array.as_primitive::<Int8Type>(), nulls, |v| v as f32),
+        DataType::Int16 => primitive_values(name, array.as_primitive::<Int16Type>(), nulls, |v| v as f32),
+        DataType::Int32 => primitive_values(name, array.as_primitive::<Int32Type>(), nulls, |v| v as f32),
+        DataType::Int64 => primitive_values(name, array.as_primitive::<Int64Type>(), nulls, |v| v as f32),
+        DataType::UInt8 => primitive_values(name, array.as_primitive::<UInt8Type>(), nulls, |v| v as f32),
+        DataType::UInt16 => primitive_values(name, array.as_primitive::<UInt16Type>(), nulls, |v| v as f32),
+        DataType::UInt32 => primitive_values(name, array.as_primitive::<UInt32Type>(), nulls, |v| v as f32),
+        DataType::UInt64 => primitive_values(name, array.as_primitive::<UInt64Type>(), nulls, |v| v as f32),
+        other => Err(arrow_error(format!("column '{}' has Arrow type {}, which is not numeric", name, other))),
+    }
+}
+
+fn primitive_values<P: ArrowPrimitiveType>(
+    name: &str,
+    array: &PrimitiveArray<P>,
+    nulls: NullPolicy,
+    convert: impl Fn(P::Native) -> f32,
+) -> MlResult<Vec<f32>> {
+    array.iter().enumerate().map(|(row, value)| match (value, nulls) {
+        (Some(value), _) => Ok(convert(value)),
+        (None, NullPolicy::Fill(fill)) => Ok(fill),
+        (None, NullPolicy::Error) => Err(null_error(name, row)),
+    }).collect()
+}
+
+fn null_error(name: &str, row: usize) -> MlError {
+    arrow_error(format!("column '{}' has a null at row {}", name, row))
+}
+
+fn arrow_error(reason: String) -> MlError {
+    MlError::StringError(format!("invalid Arrow data: {}", reason))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use arrow_array::{ArrayRef, Float32Array, Float64Array, Int32Array, RecordBatch, StringArray};
+
+    use crate::MlResult;
+    use crate::tensor::*;
+
+    fn batch_with_nulls() -> RecordBatch {
+        RecordBatch::try_from_iter(vec![
+            ("x", Arc::new(Float32Array::from(vec![Some(1.0), None, Some(3.0)])) as ArrayRef),
+            ("y", Arc::new(Float64Array::from(vec![0.5, 1.5, 2.5])) as ArrayRef),
+            ("count", Arc::new(Int32Array::from(vec![7, 8, 9])) as ArrayRef),
+        ]).unwrap()
+    }
+
+    #[test]
+    fn test_record_batch_nulls() -> MlResult<()> {
+        let batch = batch_with_nulls();
+        let message = Tensor::<f32>::from_record_batch(&batch, NullPolicy::default()).err().unwrap().to_string();
+        assert!(message.contains("'x'") && message.contains("row 1"));
+
+        let tensor = Tensor::<f32>::from_record_batch(&batch, NullPolicy::Fill(-1.0))?;
+        assert_eq!(tensor.shape(), &[3, 3]);
+        assert_eq!(tensor.data(), &[1.0, 0.5, 7.0, -1.0, 1.5, 8.0, 3.0, 2.5, 9.0]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_record_batch_type_mismatch() {
+        let batch = RecordBatch::try_from_iter(vec![
+            ("label", Arc::new(StringArray::from(vec!["a", "b"])) as ArrayRef),
+        ]).unwrap();
+        let message = Tensor::<f32>::from_record_batch(&batch, NullPolicy::Fill(0.0)).err().unwrap().to_string();
+        assert!(message.contains("'label'") && message.contains("Utf8"));
+    }
+
+    #[test]
+    fn test_arrow_round_trip() -> MlResult<()> {
+        let tensor = Tensor::<f32>::from_vec(vec![1.0, -2.0, 3.5, 4.0, f32::MAX, 1e-30], &[3, 2])?;
+
+        let batch = tensor.to_record_batch(&["a", "b"])?;
+        assert_eq!(batch.schema().field(1).name(), "b");
+        let restored = Tensor::<f32>::from_record_batch(&batch, NullPolicy::Error)?;
+        assert_eq!(restored.shape(), tensor.shape());
+        assert_eq!(restored.data(), tensor.data());
+
+        let lists = tensor.to_arrow_fixed_size_list()?;
+        let restored = Tensor::<f32>::from_arrow(&lists, NullPolicy::Error)?;
+        assert_eq!(restored.shape(), &[3, 2]);
+        assert_eq!(restored.data(), tensor.data());
+
+        let vector = Tensor::<f32>::from_vec(vec![0.25, -0.5], &[2])?;
+        let restored = Tensor::<f32>::from_arrow(&vector.to_arrow()?, NullPolicy::Error)?;
+        assert_eq!(restored.data(), vector.data());
+
+        assert!(tensor.to_record_batch(&["a"]).is_err());
+        assert!(tensor.to_arrow().is_err());
+        assert!(vector.to_arrow_fixed_size_list().is_err());
+        Ok(())
+    }
+}
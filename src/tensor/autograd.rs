@@ -0,0 +1,170 @@
+use std::fmt::Debug;
+use std::sync::Arc;
+
+use crate::MlResult;
+use crate::tensor::{ArcTensor, Tensor, TensorBase};
+
+/// A node of the autograd graph recording how a tensor was computed.
+///
+/// Nodes are `'static` and hold their inputs through shared handles to the input
+/// buffers, unlike the op structs implementing [`Function`](crate::tensor::Function),
+/// which only borrow their inputs for the duration of a forward pass. Writing to an
+/// input afterwards copies its buffer, so a node keeps the values it was built from.
+pub trait GradFn<T: Debug + 'static>: Send + Sync {
+    /// Returns the name of the op that produced the tensor
+    fn name(&self) -> &'static str;
+    /// Returns the inputs of the op, each carrying its own `grad_fn`
+    fn inputs(&self) -> &[Arc<Tensor<T>>];
+
+    /// Reruns the forward pass of a node that did not keep its intermediate graph
+    ///
+    /// Returns `None` for nodes that record their graph directly. A checkpointed node
+    /// returns the output again with the full graph below it, ready for the backward pass.
+    fn recompute(&self) -> Option<MlResult<ArcTensor<T>>> {
+        None
+    }
+}
+
+/// The graph node recorded by the built-in ops.
+pub struct OpNode<T: Debug + 'static> {
+    name: &'static str,
+    inputs: Vec<Arc<Tensor<T>>>,
+}
+
+impl<T: Debug + 'static> OpNode<T> {
+    /// Creates a node for the op `name`, sharing the buffers of its inputs
+    pub fn new(name: &'static str, inputs: &[&dyn TensorBase<T>]) -> Self {
+        let inputs = inputs.iter().map(|input| {
+            Arc::new(Tensor {
+                data: input.shared_data(),
+                shape: input.shape().to_vec(),
+                requires_grad: input.requires_grad(),
+                grad: None,
+                grad_fn: input.grad_fn(),
+            })
+        }).collect();
+        Self { name, inputs }
+    }
+}
+
+impl<T: Debug + Send + Sync + 'static> GradFn<T> for OpNode<T> {
+    fn name(&self) -> &'static str {
+        self.name
+    }
+
+    fn inputs(&self) -> &[Arc<Tensor<T>>] {
+        &self.inputs
+    }
+}
+
+/// The function recomputed by a [`CheckpointNode`]
+pub(crate) type CheckpointFn = Arc<dyn Fn(&dyn TensorBase<f32>) -> MlResult<ArcTensor<f32>> + Send + Sync>;
+
+/// The graph node recorded by [`checkpoint`](crate::tensor::checkpoint).
+///
+/// It keeps only the input and the function, and rebuilds the graph in between with
+/// [`GradFn::recompute`].
+pub struct CheckpointNode {
+    node: OpNode<f32>,
+    function: CheckpointFn,
+}
+
+impl CheckpointNode {
+    pub(crate) fn new(input: &dyn TensorBase<f32>, function: CheckpointFn) -> Self {
+        Self { node: OpNode::new("checkpoint", &[input]), function }
+    }
+}
+
+impl GradFn<f32> for CheckpointNode {
+    fn name(&self) -> &'static str {
+        self.node.name()
+    }
+
+    fn inputs(&self) -> &[Arc<Tensor<f32>>] {
+        self.node.inputs()
+    }
+
+    fn recompute(&self) -> Option<MlResult<ArcTensor<f32>>> {
+        Some((self.function)(&*self.node.inputs()[0]))
+    }
+}
+
+/// Attaches an [`OpNode`] to a freshly created op output
+///
+/// Nothing is recorded when no input requires a gradient. The output must not be
+/// shared yet; shared outputs are returned unchanged.
+pub(crate) fn with_grad_fn<T: Debug + Send + Sync + 'static>(mut tensor: ArcTensor<T>, name: &'static str, inputs: &[&dyn TensorBase<T>]) -> ArcTensor<T> {
+    if !inputs.iter().any(|input| input.requires_grad()) {
+        return tensor;
+    }
+    if let Some(output) = Arc::get_mut(&mut tensor.0) {
+        output.set_grad_fn(Box::new(OpNode::new(name, inputs)));
+    }
+    tensor
+}
+
+#[cfg(test)]
+mod tests {
+    use std::ops::Deref;
+
+    use crate::MlResult;
+    use crate::tensor::*;
+
+    #[test]
+    fn test_ops_record_grad_fn() -> MlResult<()> {
+        let a = Tensor::<f32>::from_vec(vec![1.0, 2.0], &[2])?;
+        let b = Tensor::<f32>::from_vec(vec![3.0, 4.0], &[2])?;
+        assert!(a.grad_fn().is_none());
+
+        let sum = Add::new(a.deref(), Some(b.deref()))?.forward()?;
+        let node = sum.grad_fn().unwrap();
+        assert_eq!(node.name(), "add");
+        assert_eq!(node.inputs().len(), 2);
+        assert_eq!(node.inputs()[1].data(), b.data());
+
+        let exp = Exp::new(sum.deref(), None)?.forward()?;
+        let node = exp.grad_fn().unwrap();
+        assert_eq!(node.name(), "exp");
+        assert_eq!(node.inputs()[0].grad_fn().unwrap().name(), "add");
+        assert!(node.inputs()[0].grad_fn().unwrap().inputs()[0].grad_fn().is_none());
+        Ok(())
+    }
+
+    #[test]
+    fn test_nodes_share_input_buffers() -> MlResult<()> {
+        let a = Tensor::<f32>::from_vec(vec![1.0, 2.0], &[2])?;
+        let mut b = Tensor::<f32>::from_vec_owned(vec![3.0, 4.0], &[2])?;
+
+        let sum = Add::new(a.deref(), Some(&b))?.forward()?;
+        let node = sum.grad_fn().unwrap();
+        assert!(std::ptr::eq(node.inputs()[0].data().as_ptr(), a.data().as_ptr()));
+        assert!(std::ptr::eq(node.inputs()[1].data().as_ptr(), b.data().as_ptr()));
+
+        // writing to an input afterwards leaves the recorded values alone
+        b.fill_(0.0)?;
+        assert_eq!(node.inputs()[1].data(), &[3.0, 4.0]);
+        assert_eq!(b.data(), &[0.0, 0.0]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_no_grad_fn_without_requires_grad() -> MlResult<()> {
+        let a = TensorBuilder::new().shape(&[2]).data(vec![1.0, 2.0]).requires_grad(false).build()?;
+        let b = TensorBuilder::new().shape(&[2]).data(vec![3.0, 4.0]).requires_grad(false).build()?;
+        assert!(Add::new(a.deref(), Some(b.deref()))?.forward()?.grad_fn().is_none());
+
+        let c = Tensor::<f32>::from_vec(vec![5.0, 6.0], &[2])?;
+        assert!(Add::new(a.deref(), Some(c.deref()))?.forward()?.grad_fn().is_some());
+        Ok(())
+    }
+
+    #[test]
+    fn test_set_grad_fn() -> MlResult<()> {
+        let a = Tensor::<f32>::from_vec(vec![1.0], &[1])?;
+        let mut tensor = Tensor::<f32>::from_vec_owned(vec![2.0], &[1])?;
+        tensor.set_grad_fn(Box::new(OpNode::new("custom", &[a.deref()])));
+
+        assert_eq!(tensor.grad_fn().unwrap().name(), "custom");
+        Ok(())
+    }
+}
@@ -1,59 +1,201 @@
-// use crate::tensor::{BroadcastLayer, Tensor};
-//
-// impl<Type> BroadcastLayer for Tensor<Type> {
-//     fn can_broadcast(&self, other: &Self) -> bool {
-//         if self.shape.len() != other.shape.len() {
-//             return false;
-//         }
-//         // 각 차원을 뒤에서부터 비교
-//         self.shape.iter().zip(other.shape.iter()).all(|(&a, &right)| {
-//             a == right || a == 1 || right == 1
-//         })
-//     }
-//
-//     fn broadcast_shape(&self, other: &Self) -> Vec<usize> {
-//         self.shape
-//             .iter()
-//             .zip(&other.shape)
-//             .map(|(&left, &right)| std::cmp::max(left, right))
-//             .collect()
-//     }
-//
-//     fn broadcasting<F>(self, other: Self, op: F) -> Option<Self> // todo: 브로드케스팅 연산 구조 변경 필요
-//     where
-//         F: Fn(f32, f32) -> f32,
-//         Self: Sized
-//     {
-//         // let shape: Vec<usize> = self.broadcast_shape(&other);
-//         // let size = shape.iter().product();
-//         // let mut data = Vec::with_capacity(size);
-//         //
-//         // for idx in 0..size {
-//         //     let (self_idx, other_idx) = self.calculate_broadcast_indices(&other, idx, &shape)?;
-//         //     data.push(op(self.data[self_idx], other.data[other_idx]));
-//         // }
-//         //
-//         // Some(Self { data, shape, grad: None, grad_fn: None, requires_grad: false })
-//         //todo!(브로드케스팅 연산 구조 변경 필요)
-//         Some(self)
-//     }
-//
-//     fn calculate_broadcast_indices(&self, other: &Self, idx: usize, shape: &[usize]) -> Option<(usize, usize)> {
-//         let mut remaining = idx;
-//         let mut self_idx = 0;
-//         let mut other_idx = 0;
-//
-//         for (i, (_, (self_dim, other_dim))) in shape.into_iter()
-//             .zip(self.shape.iter().zip(&other.shape))
-//             .enumerate()
-//         {
-//             let pos = remaining / shape[i+1..].iter().product::<usize>().max(1);
-//             remaining %= shape[i+1..].iter().product::<usize>().max(1);
-//
-//             self_idx = self_idx * self_dim + if self_dim == &1 { 0 } else { pos };
-//             other_idx = other_idx * other_dim + if other_dim == &1 { 0 } else { pos };
-//         }
-//
-//         Some((self_idx, other_idx))
-//     }
-// }
\ No newline at end of file
+use crate::{MlError, MlResult};
+use crate::tensor::{ArcTensor, Tensor, TensorBase, TensorError};
+
+/// Checks whether two shapes broadcast against each other
+///
+/// Shapes are aligned from the end, as if the shorter one had size-1 dimensions
+/// prepended, and each aligned pair must be equal or contain a 1.
+pub(crate) fn can_broadcast(left: &[usize], right: &[usize]) -> bool {
+    left.iter().rev().zip(right.iter().rev()).all(|(&l, &r)| l == r || l == 1 || r == 1)
+}
+
+/// Computes the shape two tensors broadcast to, aligning dimensions from the end
+///
+/// # Returns
+/// * `Ok(Vec<usize>)` with the broadcast shape
+/// * `Err(MlError::TensorError)` with `InvalidShape` if a dimension pair is neither equal nor 1
+pub(crate) fn broadcast_shape(left: &[usize], right: &[usize]) -> MlResult<Vec<usize>> {
+    if !can_broadcast(left, right) {
+        return Err(MlError::TensorError(TensorError::InvalidShape {
+            expected: left.to_vec(),
+            got: right.to_vec(),
+        }));
+    }
+    let ndim = left.len().max(right.len());
+    let padded = |shape: &[usize], i: usize| if i < ndim - shape.len() { 1 } else { shape[i - (ndim - shape.len())] };
+    // a size-1 dimension takes the other size, which may be 0
+    Ok((0..ndim).map(|i| if padded(left, i) == 1 { padded(right, i) } else { padded(left, i) }).collect())
+}
+
+/// Maps a flat index into the broadcast `shape` to the flat index into a tensor of `source` shape
+pub(crate) fn broadcast_index(mut idx: usize, shape: &[usize], source: &[usize]) -> usize {
+    let offset = shape.len() - source.len();
+    let mut source_idx = 0;
+    let mut stride = 1;
+    for i in (0..shape.len()).rev() {
+        let pos = idx % shape[i];
+        idx /= shape[i];
+        if i >= offset {
+            let dim = source[i - offset];
+            if dim != 1 {
+                source_idx += pos * stride;
+            }
+            stride *= dim;
+        }
+    }
+    source_idx
+}
+
+/// Strides of `source` laid out along the dimensions of the broadcast `shape`
+///
+/// Prepended and size-1 dimensions get a stride of 0, so stepping along them stays on
+/// the same element of `source`.
+fn broadcast_strides(shape: &[usize], source: &[usize]) -> Vec<usize> {
+    let offset = shape.len() - source.len();
+    let mut strides = vec![0; shape.len()];
+    let mut stride = 1;
+    for i in (0..source.len()).rev() {
+        if source[i] != 1 {
+            strides[offset + i] = stride;
+        }
+        stride *= source[i];
+    }
+    strides
+}
+
+/// Applies `f` element-wise after broadcasting `left` and `right` to a common shape
+///
+/// The operand strides are computed once, and each step of the row-major walk over the
+/// output adjusts the two source offsets instead of decomposing the flat index again.
+///
+/// # Returns
+/// * `Ok(ArcTensor)` with the broadcast shape
+/// * `Err(MlError::TensorError)` with `InvalidShape` if the shapes cannot be broadcast
+pub(crate) fn broadcast_op(
+    left: &dyn TensorBase<f32>,
+    right: &dyn TensorBase<f32>,
+    f: impl Fn(f32, f32) -> f32,
+) -> MlResult<ArcTensor<f32>> {
+    let shape = broadcast_shape(left.shape(), right.shape())?;
+    let left_strides = broadcast_strides(&shape, left.shape());
+    let right_strides = broadcast_strides(&shape, right.shape());
+    let size: usize = shape.iter().product();
+
+    let mut data = Vec::with_capacity(size);
+    let mut index = vec![0; shape.len()];
+    let (mut l, mut r) = (0, 0);
+    for _ in 0..size {
+        data.push(f(left.data()[l], right.data()[r]));
+        for d in (0..shape.len()).rev() {
+            index[d] += 1;
+            l += left_strides[d];
+            r += right_strides[d];
+            if index[d] < shape[d] {
+                break;
+            }
+            index[d] = 0;
+            l -= left_strides[d] * shape[d];
+            r -= right_strides[d] * shape[d];
+        }
+    }
+    Tensor::<f32>::from_vec(data, &shape)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::MlResult;
+    use crate::tensor::Rng;
+    use super::*;
+
+    #[test]
+    fn test_broadcast_shape() -> MlResult<()> {
+        assert_eq!(broadcast_shape(&[2, 3], &[3])?, vec![2, 3]);
+        assert_eq!(broadcast_shape(&[4, 1, 3], &[2, 1])?, vec![4, 2, 3]);
+        assert!(broadcast_shape(&[2, 3], &[2]).is_err());
+
+        // 0-dimensional tensors broadcast against any shape
+        assert_eq!(broadcast_shape(&[], &[2, 3])?, vec![2, 3]);
+        assert_eq!(broadcast_shape(&[4], &[])?, vec![4]);
+        assert_eq!(broadcast_index(5, &[2, 3], &[]), 0);
+        Ok(())
+    }
+
+    #[test]
+    fn test_broadcast_across_ranks() -> MlResult<()> {
+        // a [n] bias against a [batch, n] matrix, in either order
+        assert!(can_broadcast(&[3], &[4, 3]));
+        assert_eq!(broadcast_shape(&[3], &[4, 3])?, vec![4, 3]);
+        assert_eq!(broadcast_shape(&[4, 3], &[3])?, vec![4, 3]);
+
+        // [1] broadcasts against anything
+        for shape in [&[][..], &[5], &[2, 3], &[2, 0, 4]] {
+            assert!(can_broadcast(&[1], shape));
+            assert_eq!(broadcast_shape(&[1], shape)?, if shape.is_empty() { vec![1] } else { shape.to_vec() });
+        }
+
+        // rank 3 against rank 1
+        assert_eq!(broadcast_shape(&[2, 3, 4], &[4])?, vec![2, 3, 4]);
+        assert_eq!(broadcast_shape(&[2, 3, 1], &[4])?, vec![2, 3, 4]);
+        let expanded: Vec<usize> = (0..8).map(|i| broadcast_index(i, &[2, 1, 4], &[4])).collect();
+        assert_eq!(expanded, vec![0, 1, 2, 3, 0, 1, 2, 3]);
+
+        // trailing mismatches are rejected rather than taking the larger size
+        assert!(!can_broadcast(&[2, 3], &[4, 3]));
+        assert!(broadcast_shape(&[2, 3], &[4, 3]).is_err());
+        assert!(broadcast_shape(&[2, 3, 4], &[3]).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_broadcast_index() {
+        let shape = [2, 3];
+        let row: Vec<usize> = (0..6).map(|i| broadcast_index(i, &shape, &[3])).collect();
+        let column: Vec<usize> = (0..6).map(|i| broadcast_index(i, &shape, &[2, 1])).collect();
+
+        assert_eq!(row, vec![0, 1, 2, 0, 1, 2]);
+        assert_eq!(column, vec![0, 0, 0, 1, 1, 1]);
+    }
+
+    /// Reads `tensor` at the broadcast multi-index `idx` of the output with get()
+    fn broadcast_get(tensor: &dyn TensorBase<f32>, idx: &[usize]) -> f32 {
+        let offset = idx.len() - tensor.ndim();
+        let source: Vec<usize> = tensor.shape().iter().enumerate()
+            .map(|(d, &dim)| if dim == 1 { 0 } else { idx[offset + d] })
+            .collect();
+        *tensor.get(&source).unwrap()
+    }
+
+    /// Draws a shape that broadcasts to `shape`, dropping leading dims and setting some to 1
+    fn random_operand(rng: &mut Rng, shape: &[usize]) -> MlResult<ArcTensor<f32>> {
+        let rank = (rng.next_u64() % (shape.len() as u64 + 1)) as usize;
+        let dims: Vec<usize> = shape[shape.len() - rank..].iter()
+            .map(|&d| if rng.next_u64().is_multiple_of(3) { 1 } else { d })
+            .collect();
+        let size = dims.iter().product();
+        Tensor::<f32>::from_vec((0..size).map(|x| x as f32).collect(), &dims)
+    }
+
+    #[test]
+    fn test_broadcast_op_matches_reference() -> MlResult<()> {
+        let mut rng = Rng::new(7);
+        for _ in 0..200 {
+            let rank = (rng.next_u64() % 5) as usize;
+            let shape: Vec<usize> = (0..rank).map(|_| (rng.next_u64() % 4) as usize).collect();
+            let left = random_operand(&mut rng, &shape)?;
+            let right = random_operand(&mut rng, &shape)?;
+
+            let result = broadcast_op(&*left, &*right, |a, b| a * 100.0 + b)?;
+            assert_eq!(result.shape(), broadcast_shape(left.shape(), right.shape())?.as_slice());
+            for (idx, &value) in result.indexed_iter() {
+                let expected = broadcast_get(&*left, &idx) * 100.0 + broadcast_get(&*right, &idx);
+                assert_eq!(value, expected, "{:?} and {:?} at {:?}", left.shape(), right.shape(), idx);
+            }
+            for (i, &value) in result.data().iter().enumerate() {
+                let l = left.data()[broadcast_index(i, result.shape(), left.shape())];
+                let r = right.data()[broadcast_index(i, result.shape(), right.shape())];
+                assert_eq!(value, l * 100.0 + r);
+            }
+        }
+        Ok(())
+    }
+}
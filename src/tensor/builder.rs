@@ -0,0 +1,208 @@
+use std::fmt::Debug;
+
+use crate::{MlError, MlResult};
+use crate::nn::init::Init;
+use crate::tensor::{ArcTensor, Rng, Tensor, TensorError};
+
+/// Produces the elements of a tensor from its shape and the builder's seed
+type Generator<T> = Box<dyn FnOnce(&[usize], u64) -> MlResult<Vec<T>>>;
+
+/// Where the elements of a built tensor come from, either given or generated from the shape
+enum Source<T> {
+    Data(Vec<T>),
+    Generate(&'static str, Generator<T>),
+}
+
+impl<T> Source<T> {
+    fn name(&self) -> &'static str {
+        match self {
+            Source::Data(_) => "data",
+            Source::Generate(name, _) => name,
+        }
+    }
+}
+
+/// Builds a tensor from named settings instead of positional arguments
+///
+/// Exactly one of [`data`](Self::data), [`fill`](Self::fill) or [`init`](Self::init) must
+/// provide the elements, and [`shape`](Self::shape) is always required.
+///
+/// ```
+/// use MIT::prelude::*;
+/// use MIT::nn::init::Init;
+///
+/// # fn main() -> MlResult<()> {
+/// let weight = TensorBuilder::<f32>::new()
+///     .shape(&[4, 3])
+///     .init(Init::XavierUniform { gain: 1.0 })
+///     .seed(7)
+///     .requires_grad(true)
+///     .build()?;
+/// assert_eq!(weight.shape(), &[4, 3]);
+/// assert!(weight.requires_grad());
+/// # Ok(())
+/// # }
+/// ```
+pub struct TensorBuilder<T> {
+    shape: Option<Vec<usize>>,
+    source: Option<Source<T>>,
+    conflict: Option<(&'static str, &'static str)>,
+    requires_grad: Option<bool>,
+    seed: u64,
+}
+
+impl<T: Debug + 'static> TensorBuilder<T> {
+    /// Creates a builder with nothing set
+    pub fn new() -> Self {
+        Self { shape: None, source: None, conflict: None, requires_grad: None, seed: 0 }
+    }
+
+    /// Sets the shape of the tensor
+    pub fn shape(mut self, shape: &[usize]) -> Self {
+        self.shape = Some(shape.to_vec());
+        self
+    }
+
+    /// Uses `data` as the elements in row-major order
+    pub fn data(self, data: Vec<T>) -> Self {
+        self.source(Source::Data(data))
+    }
+
+    /// Sets whether gradients are tracked; defaults to whether backpropagation is enabled
+    pub fn requires_grad(mut self, requires_grad: bool) -> Self {
+        self.requires_grad = Some(requires_grad);
+        self
+    }
+
+    /// Creates the tensor
+    ///
+    /// # Returns
+    /// * `Ok(ArcTensor)` with the requested shape, elements and flags
+    /// * `Err(MlError::TensorError)` with `InvalidOperation` if the shape is missing, no
+    ///   source or more than one source of elements was set, or `InvalidDataLength` if the
+    ///   data does not match the shape
+    pub fn build(self) -> MlResult<ArcTensor<T>> {
+        Ok(ArcTensor::new(self.build_owned()?))
+    }
+
+    /// Creates the tensor without wrapping it in an `ArcTensor`, see [`build`](Self::build)
+    pub fn build_owned(self) -> MlResult<Tensor<T>> {
+        if let Some((first, second)) = self.conflict {
+            return Err(builder_error(format!("both {} and {} were set, use only one", first, second)));
+        }
+        let shape = self.shape.ok_or_else(|| builder_error("shape was not set".to_string()))?;
+        let data = match self.source {
+            Some(Source::Data(data)) => data,
+            Some(Source::Generate(_, generate)) => generate(&shape, self.seed)?,
+            None => return Err(builder_error("one of data, fill or init must be set".to_string())),
+        };
+
+        let mut tensor = Tensor::<T>::from_vec_owned(data, &shape)?;
+        if let Some(requires_grad) = self.requires_grad {
+            tensor.requires_grad = requires_grad;
+        }
+        Ok(tensor)
+    }
+
+    fn source(mut self, source: Source<T>) -> Self {
+        match &self.source {
+            Some(existing) if self.conflict.is_none() => self.conflict = Some((existing.name(), source.name())),
+            _ => self.source = Some(source),
+        }
+        self
+    }
+}
+
+impl<T: Debug + Clone + 'static> TensorBuilder<T> {
+    /// Sets every element to `value`
+    pub fn fill(self, value: T) -> Self {
+        self.source(Source::Generate("fill", Box::new(move |shape, _| Ok(vec![value; shape.iter().product()]))))
+    }
+}
+
+impl TensorBuilder<f32> {
+    /// Initializes the elements with a weight initialization scheme
+    ///
+    /// The random number generator is seeded with [`seed`](Self::seed), 0 by default.
+    pub fn init(self, init: Init) -> Self {
+        self.source(Source::Generate("init", Box::new(move |shape, seed| {
+            Ok(init.init(shape, &mut Rng::new(seed))?.data().to_vec())
+        })))
+    }
+
+    /// Sets the seed used by [`init`](Self::init), in either order relative to it
+    pub fn seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+}
+
+impl<T: Debug + 'static> Default for TensorBuilder<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn builder_error(reason: String) -> MlError {
+    MlError::TensorError(TensorError::InvalidOperation { op: "TensorBuilder", reason })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{MlError, MlResult};
+    use crate::nn::init::Init;
+    use crate::tensor::*;
+
+    fn reason(result: MlResult<ArcTensor<f32>>) -> String {
+        match result.err().unwrap() {
+            MlError::TensorError(TensorError::InvalidOperation { op: "TensorBuilder", reason }) => reason,
+            e => panic!("unexpected error {}", e),
+        }
+    }
+
+    #[test]
+    fn test_builder() -> MlResult<()> {
+        let tensor = TensorBuilder::new().shape(&[2, 2]).data(vec![1.0, 2.0, 3.0, 4.0]).requires_grad(true).build()?;
+        assert_eq!(tensor.shape(), &[2, 2]);
+        assert_eq!(tensor.data(), &[1.0, 2.0, 3.0, 4.0]);
+        assert!(tensor.requires_grad());
+
+        let filled = TensorBuilder::new().fill(7usize).shape(&[3]).requires_grad(false).build()?;
+        assert_eq!(filled.data(), &[7, 7, 7]);
+        assert!(!filled.requires_grad());
+
+        let default = TensorBuilder::new().shape(&[1]).fill(0.0f32).build()?;
+        assert_eq!(default.requires_grad(), cfg!(feature = "enable_backpropagation"));
+
+        let init = Init::XavierUniform { gain: 1.0 };
+        let weight = TensorBuilder::new().shape(&[4, 3]).seed(3).init(init).build()?;
+        assert_eq!(weight.data(), init.init(&[4, 3], &mut Rng::new(3))?.data());
+        Ok(())
+    }
+
+    #[test]
+    fn test_builder_seed_order() -> MlResult<()> {
+        let init = Init::XavierUniform { gain: 1.0 };
+        let seed_first = TensorBuilder::new().shape(&[4, 3]).seed(7).init(init).build()?;
+        let init_first = TensorBuilder::new().shape(&[4, 3]).init(init).seed(7).build()?;
+        assert_eq!(seed_first.data(), init_first.data());
+        assert_ne!(init_first.data(), TensorBuilder::new().shape(&[4, 3]).init(init).build()?.data());
+        Ok(())
+    }
+
+    #[test]
+    fn test_builder_invalid() -> MlResult<()> {
+        let both = TensorBuilder::new().shape(&[2]).data(vec![1.0, 2.0]).fill(0.0).build();
+        assert_eq!(reason(both), "both data and fill were set, use only one");
+        let init_and_data = TensorBuilder::new().shape(&[2, 2]).init(Init::Zeros).data(vec![0.0; 4]).build();
+        assert!(reason(init_and_data).contains("init and data"));
+
+        assert_eq!(reason(TensorBuilder::new().data(vec![1.0]).build()), "shape was not set");
+        assert!(reason(TensorBuilder::new().shape(&[1]).build()).contains("must be set"));
+        assert!(matches!(
+            TensorBuilder::new().shape(&[3]).data(vec![1.0, 2.0]).build(),
+            Err(MlError::TensorError(TensorError::InvalidDataLength { expected: 3, got: 2 }))
+        ));
+        Ok(())
+    }
+}
@@ -0,0 +1,112 @@
+#[cfg(feature = "enable_backpropagation")]
+use std::sync::Arc;
+
+use crate::MlResult;
+use crate::tensor::{ArcTensor, TensorBase};
+#[cfg(feature = "enable_backpropagation")]
+use crate::tensor::{CheckpointNode, Tensor};
+
+/// Runs `f` on `input` without keeping the autograd graph inside it
+///
+/// This is gradient checkpointing. Normally every op in `f` records a node that keeps its
+/// inputs alive, so memory grows with the depth of `f`. Here the output instead records a
+/// single [`CheckpointNode`] holding only `input` and `f`, and the graph in between is
+/// dropped. The node is recomputable: [`GradFn::recompute`](crate::tensor::GradFn::recompute)
+/// runs `f` again and returns the output with its full graph, for a backward engine to walk
+/// when it reaches the node. The crate has no such engine yet, so for now the trade is
+/// only visible as the smaller graph.
+///
+/// `f` must be deterministic so the recomputed graph matches the original output. Without
+/// the `enable_backpropagation` feature this simply returns `f(input)`.
+///
+/// # Arguments
+/// * `f` - The block to run, e.g. a few layers of a model
+/// * `input` - The input of the block
+///
+/// # Returns
+/// * `Ok(ArcTensor)` with the output of `f`
+/// * `Err(MlError)` if `f` fails
+pub fn checkpoint<F>(f: F, input: &dyn TensorBase<f32>) -> MlResult<ArcTensor<f32>>
+where
+    F: Fn(&dyn TensorBase<f32>) -> MlResult<ArcTensor<f32>> + Send + Sync + 'static,
+{
+    let output = f(input)?;
+
+    #[cfg(feature = "enable_backpropagation")]
+    let output = {
+        let node = Box::new(CheckpointNode::new(input, Arc::new(f)));
+        let mut output = output;
+        match Arc::get_mut(&mut output.0) {
+            Some(tensor) => tensor.set_grad_fn(node),
+            None => {
+                // `f` kept a handle on its output, so wrap the same buffer in a new tensor
+                let mut tensor = Tensor {
+                    data: output.shared_data(),
+                    shape: output.shape().to_vec(),
+                    requires_grad: output.requires_grad(),
+                    grad: None,
+                    grad_fn: None,
+                };
+                tensor.set_grad_fn(node);
+                output = ArcTensor::new(tensor);
+            }
+        }
+        output
+    };
+    Ok(output)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::MlResult;
+    use crate::tensor::*;
+
+    fn block(x: &dyn TensorBase<f32>) -> MlResult<ArcTensor<f32>> {
+        let e = x.exp()?;
+        e.mul(&e)?.add(x)
+    }
+
+    #[test]
+    fn test_checkpoint_output() -> MlResult<()> {
+        let input = Tensor::<f32>::from_vec(vec![0.0, 1.0, -1.0], &[3])?;
+        let output = checkpoint(block, &*input)?;
+        assert_eq!(output.shape(), &[3]);
+        assert_eq!(output.data(), block(&*input)?.data());
+        assert!(checkpoint(|x| x.matmul(x), &*Tensor::<f32>::from_vec(vec![1.0; 6], &[2, 3])?).is_err());
+        Ok(())
+    }
+
+    #[cfg(feature = "enable_backpropagation")]
+    #[test]
+    fn test_checkpoint_drops_intermediate_graph() -> MlResult<()> {
+        let input = Tensor::<f32>::from_vec(vec![0.5, 2.0], &[2])?;
+        let output = checkpoint(block, &*input)?;
+
+        // only the input is kept, not the exp and mul outputs in between
+        let node = output.grad_fn().unwrap();
+        assert_eq!(node.name(), "checkpoint");
+        assert_eq!(node.inputs().len(), 1);
+        assert_eq!(node.inputs()[0].data(), input.data());
+        assert!(node.inputs()[0].grad_fn().is_none());
+
+        let recomputed = node.recompute().unwrap()?;
+        assert_eq!(recomputed.data(), output.data());
+        let add = recomputed.grad_fn().unwrap();
+        assert_eq!(add.name(), "add");
+        assert_eq!(add.inputs()[0].grad_fn().unwrap().name(), "mul");
+
+        let plain = block(&*input)?;
+        assert!(plain.grad_fn().unwrap().recompute().is_none());
+
+        // the output buffer is reused, even when `f` still holds on to it
+        thread_local! {
+            static CACHED: ArcTensor<f32> = Tensor::<f32>::from_vec(vec![1.0, 2.0], &[2]).unwrap();
+        }
+        let cached = CACHED.with(|cached| cached.clone());
+        let output = checkpoint(|_| Ok(CACHED.with(|cached| cached.clone())), &*input)?;
+        assert_eq!(output.data().as_ptr(), cached.data().as_ptr());
+        assert_eq!(output.grad_fn().unwrap().name(), "checkpoint");
+        assert!(cached.grad_fn().is_none());
+        Ok(())
+    }
+}
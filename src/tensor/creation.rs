@@ -1,43 +1,46 @@
+use std::fmt::Debug;
 use crate::{MlError, MlResult};
-use crate::tensor::{TensorBase, Tensor, TensorError, ArcTensor};
+use crate::tensor::{TensorBase, Tensor, TensorBuilder, TensorError, ArcTensor};
+use std::sync::Arc;
+#[cfg(feature = "enable_backpropagation")]
+use crate::tensor::GradFn;
 
 
 impl  Tensor<f32> {
+    /// Creates a 0-dimensional tensor holding `0.0`, the same as `scalar(0.0)`
     pub fn zeros() -> ArcTensor<f32> {
-        ArcTensor::new(Self {
-            data: vec![],
-            shape: vec![],
-            requires_grad: cfg!(feature = "enable_backpropagation"),
-
-            #[cfg(feature = "enable_backpropagation")]
-            grad: None,
-            #[cfg(feature = "enable_backpropagation")]
-            grad_fn: None,
-        })
+        Self::scalar(0.0)
     }
 
+    /// Creates a 0-dimensional tensor holding a single value
+    ///
+    /// The shape is empty (`[]`), unlike a one-element vector of shape `[1]`.
     pub fn scalar(scalar: f32) -> ArcTensor<f32> {
-        ArcTensor::new(Self {
-            data: vec![scalar],
-            shape: vec![1],
-            requires_grad: cfg!(feature = "enable_backpropagation"),
-
-            #[cfg(feature = "enable_backpropagation")]
-            grad: None,
-            #[cfg(feature = "enable_backpropagation")]
-            grad_fn: None,
-        })
+        TensorBuilder::new().shape(&[]).data(vec![scalar]).build().expect("one value always matches shape []")
     }
 }
 
-impl TensorBase<f32> for Tensor<f32> {
-    fn new(data: Vec<Vec<f32>>) -> ArcTensor<f32>  {
-        let shape = vec![data.len(), data[0].len()];
-        let data: Vec<f32> = data.into_iter().flatten().collect();
+impl<T: Debug + 'static> Tensor<T> {
+    /// Creates an owned tensor that is not wrapped in an `ArcTensor`
+    ///
+    /// Owned tensors can be mutated in place, which makes them suitable for
+    /// long-lived state such as parameters and optimizer buffers.
+    ///
+    /// # Returns
+    /// * `Ok(Tensor)` if the data length matches the product of the shape
+    /// * `Err(MlError::TensorError)` with `InvalidDataLength` otherwise
+    pub fn from_vec_owned(data: Vec<T>, shape: &[usize]) -> MlResult<Self> {
+        let expected_len: usize = shape.iter().product();
+        if data.len() != expected_len {
+            return Err(MlError::TensorError(TensorError::InvalidDataLength {
+                expected: expected_len,
+                got: data.len(),
+            }));
+        }
 
-        ArcTensor::new(Self {
-            data,
-            shape,
+        Ok(Self {
+            data: Arc::new(data),
+            shape: shape.to_vec(),
             requires_grad: cfg!(feature = "enable_backpropagation"),
 
             #[cfg(feature = "enable_backpropagation")]
@@ -47,18 +50,229 @@ impl TensorBase<f32> for Tensor<f32> {
         })
     }
 
-    fn from_vec(data: Vec<f32>, shape: &[usize]) -> MlResult<ArcTensor<f32>> {
-        let expected_len: usize = shape.iter().product();
-        if data.len() != expected_len {
-            return Err(MlError::TensorError(TensorError::InvalidDataLength {
-                expected: expected_len,
-                got: data.len(),
+    /// Creates a tensor by calling `f` with the coordinate of each element
+    ///
+    /// Elements are generated in row-major order, so `f` is called with `[0, 0]`,
+    /// `[0, 1]`, ... for a 2-D shape.
+    ///
+    /// # Arguments
+    /// * `shape` - The shape of the tensor
+    /// * `f` - Computes the element at a coordinate
+    ///
+    /// # Returns
+    /// * `Ok(ArcTensor)` with the given shape
+    /// * `Err(MlError::TensorError)` with `EmptyTensor` if the shape has no elements
+    pub fn from_fn(shape: &[usize], f: impl Fn(&[usize]) -> T) -> MlResult<ArcTensor<T>> {
+        let size: usize = shape.iter().product();
+        if size == 0 {
+            return Err(MlError::TensorError(TensorError::EmptyTensor));
+        }
+
+        let mut index = vec![0; shape.len()];
+        let mut data = Vec::with_capacity(size);
+        for _ in 0..size {
+            data.push(f(&index));
+            for (i, &dim) in index.iter_mut().zip(shape).rev() {
+                *i += 1;
+                if *i < dim {
+                    break;
+                }
+                *i = 0;
+            }
+        }
+        Ok(ArcTensor::new(Self::from_vec_owned(data, shape)?))
+    }
+
+    /// Returns the underlying data as a mutable slice
+    ///
+    /// # Panics
+    /// If the tensor is part of an autograd graph
+    pub fn data_mut(&mut self) -> &mut [T]
+    where
+        T: Clone,
+    {
+        if let Err(e) = self.check_in_place("data_mut") {
+            panic!("{}", e);
+        }
+        self.elements_mut()
+    }
+
+    /// Iterates mutably over the elements in row-major order
+    ///
+    /// # Panics
+    /// If the tensor is part of an autograd graph
+    pub fn iter_mut(&mut self) -> std::slice::IterMut<'_, T>
+    where
+        T: Clone,
+    {
+        if let Err(e) = self.check_in_place("iter_mut") {
+            panic!("{}", e);
+        }
+        self.elements_mut().iter_mut()
+    }
+
+    /// Sets every element to `value`
+    ///
+    /// # Returns
+    /// * `Ok(())` on success
+    /// * `Err(MlError::TensorError)` with `InvalidOperation` if the tensor is part of an autograd graph
+    pub fn fill_(&mut self, value: T) -> MlResult<()>
+    where
+        T: Clone,
+    {
+        self.check_in_place("fill_")?;
+        self.elements_mut().fill(value);
+        Ok(())
+    }
+
+    /// Sets every element to zero (`T::default()`)
+    ///
+    /// # Returns
+    /// * `Ok(())` on success
+    /// * `Err(MlError::TensorError)` with `InvalidOperation` if the tensor is part of an autograd graph
+    pub fn zero_(&mut self) -> MlResult<()>
+    where
+        T: Clone + Default,
+    {
+        self.fill_(T::default())
+    }
+
+    /// Overwrites the elements with those of `src`, keeping this tensor's storage
+    ///
+    /// Elements are copied in the logical row-major order of `src`.
+    ///
+    /// # Returns
+    /// * `Ok(())` on success
+    /// * `Err(MlError::TensorError)` with `InvalidShape` if the shapes differ, or
+    ///   `InvalidOperation` if the tensor is part of an autograd graph
+    pub fn copy_from(&mut self, src: &dyn TensorBase<T>) -> MlResult<()>
+    where
+        T: Clone,
+    {
+        self.check_in_place("copy_from")?;
+        self.chk_shape(src)?;
+        self.elements_mut().iter_mut().zip(src.iter()).for_each(|(x, y)| *x = y.clone());
+        Ok(())
+    }
+
+    /// Returns a mutable reference to the element at `indices`
+    ///
+    /// Every index is checked against its own dimension, so `[0, 3]` on a `[2, 3]`
+    /// tensor is rejected instead of reaching the first element of the next row.
+    ///
+    /// # Returns
+    /// * `Some(&mut T)` if every index is in range
+    /// * `None` if an index is out of range, the number of indices does not match the
+    ///   rank, or the tensor is part of an autograd graph
+    pub fn get_mut(&mut self, indices: &[usize]) -> Option<&mut T>
+    where
+        T: Clone,
+    {
+        self.check_in_place("get_mut").ok()?;
+        let offset = self.index(indices)?;
+        self.elements_mut().get_mut(offset)
+    }
+
+    /// Writes `value` to the element at `indices`
+    ///
+    /// # Returns
+    /// * `Ok(())` on success
+    /// * `Err(MlError::TensorError)` with `InvalidOperation` naming the offending index and
+    ///   dimension if it is out of range, or if the tensor is part of an autograd graph
+    pub fn set(&mut self, indices: &[usize], value: T) -> MlResult<()>
+    where
+        T: Clone,
+    {
+        self.check_in_place("set")?;
+        if indices.len() != self.shape.len() {
+            return Err(MlError::TensorError(TensorError::InvalidOperation {
+                op: "set",
+                reason: format!("got {} indices for a tensor with {} dimensions", indices.len(), self.shape.len()),
+            }));
+        }
+        if let Some((d, (&i, &dim))) = indices.iter().zip(&self.shape).enumerate().find(|(_, (&i, &dim))| i >= dim) {
+            return Err(MlError::TensorError(TensorError::InvalidOperation {
+                op: "set",
+                reason: format!("index {} is out of range for dimension {} with size {}", i, d, dim),
+            }));
+        }
+        let offset = self.index(indices).expect("indices were checked against the shape");
+        self.elements_mut()[offset] = value;
+        Ok(())
+    }
+
+    /// Returns the element buffer for writing
+    ///
+    /// Graph nodes that recorded this tensor as an input share its buffer, so it is copied
+    /// first if any of them is still alive and they keep the values they saw.
+    pub(crate) fn elements_mut(&mut self) -> &mut Vec<T>
+    where
+        T: Clone,
+    {
+        Arc::make_mut(&mut self.data)
+    }
+
+    /// Refuses to mutate a tensor produced by a recorded op, whose `grad_fn` would go stale
+    #[cfg_attr(not(feature = "enable_backpropagation"), allow(unused_variables))]
+    pub(crate) fn check_in_place(&self, op: &'static str) -> MlResult<()> {
+        #[cfg(feature = "enable_backpropagation")]
+        if self.grad_fn.is_some() {
+            return Err(MlError::TensorError(TensorError::InvalidOperation {
+                op,
+                reason: "cannot modify a tensor that is part of an autograd graph in place".to_string(),
+            }));
+        }
+        Ok(())
+    }
+
+    /// Replaces the gradient of this tensor with a copy of `grad`
+    ///
+    /// # Returns
+    /// * `Ok(())` if `grad` has the same shape as the tensor
+    /// * `Err(MlError::TensorError)` with `InvalidShape` otherwise
+    #[cfg(feature = "enable_backpropagation")]
+    pub fn set_grad(&mut self, grad: &dyn TensorBase<T>) -> MlResult<()>
+    where
+        T: Clone + Send + Sync,
+    {
+        if self.shape != grad.shape() {
+            return Err(MlError::TensorError(TensorError::InvalidShape {
+                expected: self.shape.to_vec(),
+                got: grad.shape().to_vec(),
+            }));
+        }
+        self.grad = Some(Box::new(Self::from_vec_owned(grad.data().to_vec(), grad.shape())?));
+        Ok(())
+    }
+
+    /// Clears the gradient of this tensor
+    #[cfg(feature = "enable_backpropagation")]
+    pub fn zero_grad(&mut self) {
+        self.grad = None;
+    }
+}
+
+impl<T: Debug + 'static> TensorBase<T> for Tensor<T> {
+    /// Builds a 2-D tensor from rows, all of which must have the length of the first
+    ///
+    /// Fails with `EmptyTensor` when there are no rows and with `RaggedInput` naming
+    /// the first row of a different length. Rows that are all empty are accepted and
+    /// give shape `[n, 0]`, matching what `from_vec(vec![], &[n, 0])` builds.
+    fn new(data: Vec<Vec<T>>) -> MlResult<ArcTensor<T>>  {
+        let cols = data.first().ok_or(MlError::TensorError(TensorError::EmptyTensor))?.len();
+        if let Some((row, values)) = data.iter().enumerate().find(|(_, values)| values.len() != cols) {
+            return Err(MlError::TensorError(TensorError::RaggedInput {
+                row,
+                expected: cols,
+                got: values.len(),
             }));
         }
+        let shape = vec![data.len(), cols];
+        let data: Vec<T> = data.into_iter().flatten().collect();
 
         Ok(ArcTensor::new(Self {
-            data,
-            shape: shape.to_vec(),
+            data: Arc::new(data),
+            shape,
             requires_grad: cfg!(feature = "enable_backpropagation"),
 
             #[cfg(feature = "enable_backpropagation")]
@@ -68,15 +282,19 @@ impl TensorBase<f32> for Tensor<f32> {
         }))
     }
 
+    fn from_vec(data: Vec<T>, shape: &[usize]) -> MlResult<ArcTensor<T>> {
+        TensorBuilder::new().shape(shape).data(data).build()
+    }
+
     fn shape(&self) -> &[usize] {
         &self.shape
     }
 
-    fn data(&self) -> &[f32] {
+    fn data(&self) -> &[T] {
         &self.data
     }
 
-    fn get(&self, indices: &[usize]) -> Option<&f32> {
+    fn get(&self, indices: &[usize]) -> Option<&T> {
         self.data.get(self.index(indices)?)
     }
 
@@ -84,12 +302,10 @@ impl TensorBase<f32> for Tensor<f32> {
         if indices.len() != self.shape.len() {
             return None;
         }
-        Some(
-            indices
-                .iter()
-                .zip(&self.shape)
-                .fold(0, |acc, (&i, &dim)| acc * dim + i),
-        )
+        indices
+            .iter()
+            .zip(&self.shape)
+            .try_fold(0, |acc, (&i, &dim)| (i < dim).then_some(acc * dim + i))
     }
 
     /// Verifies if two tensors can perform element-wise operations
@@ -100,7 +316,7 @@ impl TensorBase<f32> for Tensor<f32> {
     /// # Returns
     /// * `Ok(())` if the shapes match
     /// * `Err(MlError::TensorError)` if shapes don't match
-    fn chk_shape(&self, other: &dyn TensorBase<f32>) -> MlResult<()> {
+    fn chk_shape(&self, other: &dyn TensorBase<T>) -> MlResult<()> {
         if self.shape != other.shape() {
             return Err(MlError::TensorError(TensorError::InvalidShape {
                 expected: self.shape.to_vec(),
@@ -114,13 +330,108 @@ impl TensorBase<f32> for Tensor<f32> {
         self.requires_grad
     }
 
-    // #[cfg(feature = "enable_backpropagation")]
-    // fn set_grad_fn(&mut self, grad_fn: Box<dyn crate::tensor::Function<'static, f32, Forwarded=(), Gradiant=()>>) {
-    //     self.grad_fn = Some(grad_fn);
-    // }
-    //
-    // #[cfg(feature = "enable_backpropagation")]
-    // fn grad(&self) -> Option<&dyn TensorBase<f32>> {
-    //     self.grad.as_ref().map(|g| g.as_ref())
-    // }
-}
\ No newline at end of file
+    #[cfg(feature = "enable_backpropagation")]
+    fn set_grad_fn(&mut self, grad_fn: Box<dyn GradFn<T>>) {
+        self.grad_fn = Some(Arc::from(grad_fn));
+    }
+
+    #[cfg(feature = "enable_backpropagation")]
+    fn grad_fn(&self) -> Option<Arc<dyn GradFn<T>>> {
+        self.grad_fn.clone()
+    }
+
+    #[cfg(feature = "enable_backpropagation")]
+    fn grad(&self) -> Option<&dyn TensorBase<T>> {
+        self.grad.as_deref().map(|grad| grad as &dyn TensorBase<T>)
+    }
+
+    #[cfg(feature = "enable_backpropagation")]
+    fn shared_data(&self) -> Arc<Vec<T>> {
+        Arc::clone(&self.data)
+    }
+}
+
+/// Copies the data, shape and `requires_grad` flag
+///
+/// The buffer itself is shared until either tensor is written to, which copies it.
+///
+/// The gradient and the `grad_fn` link to the graph are not copied, so the clone is a
+/// fresh leaf that does not share autograd state with the original.
+impl<T: Debug + Clone + 'static> Clone for Tensor<T> {
+    fn clone(&self) -> Self {
+        Self {
+            data: self.data.clone(),
+            shape: self.shape.clone(),
+            requires_grad: self.requires_grad,
+
+            #[cfg(feature = "enable_backpropagation")]
+            grad: None,
+            #[cfg(feature = "enable_backpropagation")]
+            grad_fn: None,
+        }
+    }
+}
+
+/// Creates an empty 1-D tensor with shape `[0]` and no gradient state
+impl<T: Debug + 'static> Default for Tensor<T> {
+    fn default() -> Self {
+        Self::from_vec_owned(Vec::new(), &[0]).expect("an empty vector always matches shape [0]")
+    }
+}
+
+impl<'a, T: Debug + 'static> IntoIterator for &'a Tensor<T> {
+    type Item = &'a T;
+    type IntoIter = std::slice::Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.data.iter()
+    }
+}
+
+/// Consumes the tensor, yielding its elements by value in row-major order
+///
+/// The data buffer is moved into the iterator rather than copied, unless a graph node
+/// still shares it, and collecting it again with [`CollectTensor::collect_tensor`] can
+/// reuse the same allocation.
+impl<T: Debug + Clone + 'static> IntoIterator for Tensor<T> {
+    type Item = T;
+    type IntoIter = std::vec::IntoIter<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        Arc::unwrap_or_clone(self.data).into_iter()
+    }
+}
+
+/// Collects elements into a 1-D tensor
+impl<T: Debug + 'static> FromIterator<T> for Tensor<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let data: Vec<T> = iter.into_iter().collect();
+        let len = data.len();
+        Self::from_vec_owned(data, &[len]).expect("a vector always matches its own length")
+    }
+}
+
+/// Collects an iterator into a tensor of a given shape
+///
+/// ```
+/// use MIT::prelude::*;
+///
+/// # fn main() -> MlResult<()> {
+/// let tensor = Tensor::<f32>::from_vec_owned(vec![1.0, 2.0, 3.0, 4.0], &[2, 2])?;
+/// let doubled = tensor.into_iter().map(|x| x * 2.0).collect_tensor(&[2, 2])?;
+/// assert_eq!(doubled.data(), &[2.0, 4.0, 6.0, 8.0]);
+/// # Ok(())
+/// # }
+/// ```
+pub trait CollectTensor<T: Debug + 'static>: Iterator<Item = T> + Sized {
+    /// Collects the elements in row-major order into a tensor with `shape`
+    ///
+    /// # Returns
+    /// * `Ok(Tensor)` if the number of elements matches the product of the shape
+    /// * `Err(MlError::TensorError)` with `InvalidDataLength` otherwise
+    fn collect_tensor(self, shape: &[usize]) -> MlResult<Tensor<T>> {
+        Tensor::<T>::from_vec_owned(self.collect(), shape)
+    }
+}
+
+impl<T: Debug + 'static, I: Iterator<Item = T>> CollectTensor<T> for I {}
@@ -0,0 +1,158 @@
+use std::fs::File;
+use std::io::{BufRead, BufReader, Read};
+
+use crate::{MlError, MlResult};
+use crate::tensor::{ArcTensor, Tensor, TensorBase};
+
+/// Options for reading CSV files with `Tensor::<f32>::from_csv`
+#[derive(Debug, Clone)]
+pub struct CsvOptions {
+    /// The field separator
+    pub delimiter: char,
+    /// Whether the first line holds column names instead of data
+    pub has_header: bool,
+    /// The indices of the columns to keep, in output order; `None` keeps every column
+    pub columns: Option<Vec<usize>>,
+}
+
+impl Default for CsvOptions {
+    fn default() -> Self {
+        Self { delimiter: ',', has_header: false, columns: None }
+    }
+}
+
+impl Tensor<f32> {
+    /// Loads a CSV file into a `[rows, cols]` tensor
+    ///
+    /// # Arguments
+    /// * `path` - The file to read
+    /// * `options` - The delimiter, header and column selection
+    ///
+    /// # Returns
+    /// The tensor and, if `options.has_header` is set, the names of the selected columns
+    pub fn from_csv(path: &str, options: &CsvOptions) -> MlResult<(ArcTensor<f32>, Option<Vec<String>>)> {
+        let file = File::open(path).map_err(|e| MlError::StringError(format!("I/O error: {}", e)))?;
+        Self::from_csv_reader(file, options)
+    }
+
+    /// Reads CSV data into a `[rows, cols]` tensor
+    ///
+    /// Fields are trimmed and parsed as `f32`; quoted fields are not supported.
+    /// Blank lines are skipped.
+    ///
+    /// # Arguments
+    /// * `reader` - The source of the CSV text
+    /// * `options` - The delimiter, header and column selection
+    ///
+    /// # Returns
+    /// * `Ok((ArcTensor, Option<Vec<String>>))` with the tensor and the selected column names
+    /// * `Err(MlError::StringError)` naming the line (and column) of a ragged row, a
+    ///   field that is not a number, or a selected column that does not exist
+    pub fn from_csv_reader(reader: impl Read, options: &CsvOptions) -> MlResult<(ArcTensor<f32>, Option<Vec<String>>)> {
+        let mut header = None;
+        let mut width = None;
+        let mut rows = 0;
+        let mut data = Vec::new();
+
+        for (i, line) in BufReader::new(reader).lines().enumerate() {
+            let line = line.map_err(|e| MlError::StringError(format!("I/O error: {}", e)))?;
+            let line_number = i + 1;
+            if line.trim().is_empty() {
+                continue;
+            }
+            let fields: Vec<&str> = line.split(options.delimiter).map(str::trim).collect();
+
+            let expected = *width.get_or_insert(fields.len());
+            if fields.len() != expected {
+                return Err(csv_error(line_number, format!("expected {} fields, got {}", expected, fields.len())));
+            }
+            let selected = select_columns(&fields, options.columns.as_deref(), line_number)?;
+
+            if options.has_header && header.is_none() {
+                header = Some(selected.iter().map(|name| name.to_string()).collect());
+                continue;
+            }
+            for (column, field) in selected.iter().enumerate() {
+                let value = field.parse::<f32>().map_err(|_| csv_error(
+                    line_number,
+                    format!("column {}: cannot parse '{}' as a number", column + 1, field),
+                ))?;
+                data.push(value);
+            }
+            rows += 1;
+        }
+
+        let cols = match &options.columns {
+            Some(columns) => columns.len(),
+            None => width.unwrap_or(0),
+        };
+        Ok((Tensor::<f32>::from_vec(data, &[rows, cols])?, header))
+    }
+}
+
+fn select_columns<'a>(fields: &[&'a str], columns: Option<&[usize]>, line_number: usize) -> MlResult<Vec<&'a str>> {
+    match columns {
+        None => Ok(fields.to_vec()),
+        Some(columns) => columns.iter().map(|&c| {
+            fields.get(c).copied().ok_or_else(|| csv_error(
+                line_number,
+                format!("column index {} is out of range for {} fields", c, fields.len()),
+            ))
+        }).collect(),
+    }
+}
+
+fn csv_error(line_number: usize, reason: String) -> MlError {
+    MlError::StringError(format!("invalid CSV at line {}: {}", line_number, reason))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::MlResult;
+    use crate::tensor::*;
+
+    fn fixture() -> String {
+        format!("{}/testdata/measurements.csv", env!("CARGO_MANIFEST_DIR"))
+    }
+
+    #[test]
+    fn test_from_csv_with_header() -> MlResult<()> {
+        let options = CsvOptions { has_header: true, ..CsvOptions::default() };
+        let (tensor, header) = Tensor::<f32>::from_csv(&fixture(), &options)?;
+
+        assert_eq!(tensor.shape(), &[3, 4]);
+        assert_eq!(tensor.data(), &[1.0, 1.5, 2.25, 0.0, 2.0, -3.0, 4.0, 1.0, 3.0, 100.0, 0.5, 1.0]);
+        assert_eq!(header.unwrap(), vec!["id", "height", "width", "label"]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_from_csv_selected_columns() -> MlResult<()> {
+        let options = CsvOptions { has_header: true, columns: Some(vec![2, 1]), ..CsvOptions::default() };
+        let (tensor, header) = Tensor::<f32>::from_csv(&fixture(), &options)?;
+
+        assert_eq!(tensor.shape(), &[3, 2]);
+        assert_eq!(tensor.data(), &[2.25, 1.5, 4.0, -3.0, 0.5, 100.0]);
+        assert_eq!(header.unwrap(), vec!["width", "height"]);
+
+        let options = CsvOptions { delimiter: ';', ..CsvOptions::default() };
+        let (tensor, header) = Tensor::<f32>::from_csv_reader("1;2\n\n3;4\n".as_bytes(), &options)?;
+        assert_eq!(tensor.shape(), &[2, 2]);
+        assert!(header.is_none());
+        Ok(())
+    }
+
+    #[test]
+    fn test_from_csv_malformed() {
+        let options = CsvOptions::default();
+        let message = Tensor::<f32>::from_csv_reader("1,2,3\n4,5\n".as_bytes(), &options).err().unwrap().to_string();
+        assert!(message.contains("line 2"));
+
+        let message = Tensor::<f32>::from_csv_reader("1,2\n3,x\n".as_bytes(), &options).err().unwrap().to_string();
+        assert!(message.contains("line 2") && message.contains("column 2") && message.contains("'x'"));
+
+        let options = CsvOptions { columns: Some(vec![5]), ..CsvOptions::default() };
+        assert!(Tensor::<f32>::from_csv_reader("1,2\n".as_bytes(), &options).is_err());
+        assert!(Tensor::<f32>::from_csv("missing.csv", &options).is_err());
+    }
+}
@@ -0,0 +1,145 @@
+use std::str::FromStr;
+
+use crate::{MlError, MlResult};
+use crate::tensor::{Abs, Add, ArcTensor, Div, Exp, Function, Log, Matmul, Mul, Neg, Sqrt, Square, Sub, TensorBase, TensorError};
+
+/// Unary operations that can be selected at runtime.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum UnaryOpKind {
+    Exp,
+    Neg,
+    Sqrt,
+    Abs,
+    Square,
+    Log,
+}
+
+/// Binary operations that can be selected at runtime.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum BinaryOpKind {
+    Add,
+    Sub,
+    Mul,
+    Div,
+    Matmul,
+}
+
+impl FromStr for UnaryOpKind {
+    type Err = MlError;
+
+    /// Parses a case-insensitive operation name such as `"sqrt"` or `"Log"`
+    fn from_str(s: &str) -> MlResult<Self> {
+        match s.to_ascii_lowercase().as_str() {
+            "exp" => Ok(UnaryOpKind::Exp),
+            "neg" => Ok(UnaryOpKind::Neg),
+            "sqrt" => Ok(UnaryOpKind::Sqrt),
+            "abs" => Ok(UnaryOpKind::Abs),
+            "square" => Ok(UnaryOpKind::Square),
+            "log" => Ok(UnaryOpKind::Log),
+            _ => Err(MlError::TensorError(TensorError::InvalidOperation {
+                op: "apply_unary",
+                reason: format!("unknown unary operation '{}'", s),
+            })),
+        }
+    }
+}
+
+impl FromStr for BinaryOpKind {
+    type Err = MlError;
+
+    /// Parses a case-insensitive operation name such as `"add"` or `"Matmul"`
+    fn from_str(s: &str) -> MlResult<Self> {
+        match s.to_ascii_lowercase().as_str() {
+            "add" => Ok(BinaryOpKind::Add),
+            "sub" => Ok(BinaryOpKind::Sub),
+            "mul" => Ok(BinaryOpKind::Mul),
+            "div" => Ok(BinaryOpKind::Div),
+            "matmul" => Ok(BinaryOpKind::Matmul),
+            _ => Err(MlError::TensorError(TensorError::InvalidOperation {
+                op: "apply_binary",
+                reason: format!("unknown binary operation '{}'", s),
+            })),
+        }
+    }
+}
+
+/// Applies a unary operation chosen at runtime
+///
+/// # Arguments
+/// * `op` - The operation to apply
+/// * `tensor` - The input tensor
+///
+/// # Returns
+/// The forward result of the selected operation
+pub fn apply_unary(op: UnaryOpKind, tensor: &dyn TensorBase<f32>) -> MlResult<ArcTensor<f32>> {
+    match op {
+        UnaryOpKind::Exp => Exp::new(tensor, None)?.forward(),
+        UnaryOpKind::Neg => Neg::new(tensor, None)?.forward(),
+        UnaryOpKind::Sqrt => Sqrt::new(tensor, None)?.forward(),
+        UnaryOpKind::Abs => Abs::new(tensor, None)?.forward(),
+        UnaryOpKind::Square => Square::new(tensor, None)?.forward(),
+        UnaryOpKind::Log => Log::new(tensor, None)?.forward(),
+    }
+}
+
+/// Applies a binary operation chosen at runtime
+///
+/// # Arguments
+/// * `op` - The operation to apply
+/// * `first` - The left-hand tensor
+/// * `second` - The right-hand tensor
+///
+/// # Returns
+/// The forward result of the selected operation
+pub fn apply_binary(op: BinaryOpKind, first: &dyn TensorBase<f32>, second: &dyn TensorBase<f32>) -> MlResult<ArcTensor<f32>> {
+    match op {
+        BinaryOpKind::Add => Add::new(first, Some(second))?.forward(),
+        BinaryOpKind::Sub => Sub::new(first, Some(second))?.forward(),
+        BinaryOpKind::Mul => Mul::new(first, Some(second))?.forward(),
+        BinaryOpKind::Div => Div::new(first, Some(second))?.forward(),
+        BinaryOpKind::Matmul => Matmul::new(first, Some(second))?.forward(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::ops::Deref;
+    use crate::MlResult;
+    use crate::ops;
+    use crate::tensor::*;
+
+    #[test]
+    fn test_apply_unary() -> MlResult<()> {
+        let tensor = Tensor::<f32>::new(vec![vec![1.0, -4.0]])?;
+        let kind: UnaryOpKind = "Abs".parse()?;
+        let result = apply_unary(kind, tensor.deref())?;
+        let expected = ops!(tensor, Abs)?;
+
+        assert_eq!(result.data(), expected.data());
+        assert_eq!(result.shape(), expected.shape());
+        Ok(())
+    }
+
+    #[test]
+    fn test_apply_binary() -> MlResult<()> {
+        let first = Tensor::<f32>::new(vec![vec![1.0, 2.0]])?;
+        let second = Tensor::<f32>::new(vec![vec![3.0], vec![4.0]])?;
+        let kind: BinaryOpKind = "matmul".parse()?;
+        let result = apply_binary(kind, first.deref(), second.deref())?;
+
+        assert_eq!(result.data(), &[11.0]);
+        // [1, 2] and [2, 1] broadcast to [2, 2]
+        let sum = apply_binary(BinaryOpKind::Add, first.deref(), second.deref())?;
+        assert_eq!(sum.shape(), &[2, 2]);
+        assert_eq!(sum.data(), &[4.0, 5.0, 5.0, 6.0]);
+        let third = Tensor::<f32>::new(vec![vec![1.0, 2.0, 3.0]])?;
+        assert!(apply_binary(BinaryOpKind::Add, first.deref(), third.deref()).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_unknown_op_name() {
+        assert!("gelu".parse::<UnaryOpKind>().is_err());
+        assert!("pow".parse::<BinaryOpKind>().is_err());
+    }
+}
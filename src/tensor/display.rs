@@ -0,0 +1,201 @@
+use std::fmt::{Debug, Display, Formatter, Result};
+use std::sync::RwLock;
+
+use crate::tensor::{ArcTensor, Tensor, TensorBase};
+
+/// Controls how much of a tensor is printed by `Display` and `Debug`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PrintOptions {
+    /// Tensors with more elements than this are summarized with `...`
+    pub threshold: usize,
+    /// The number of items printed at each end of a summarized dimension
+    pub edge_items: usize,
+}
+
+impl PrintOptions {
+    const DEFAULT: PrintOptions = PrintOptions { threshold: 1000, edge_items: 3 };
+}
+
+impl Default for PrintOptions {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+static PRINT_OPTIONS: RwLock<PrintOptions> = RwLock::new(PrintOptions::DEFAULT);
+
+/// Sets the print options used by every subsequent tensor format
+pub fn set_print_options(options: PrintOptions) {
+    *PRINT_OPTIONS.write().unwrap_or_else(|e| e.into_inner()) = options;
+}
+
+/// Returns the current print options
+pub fn print_options() -> PrintOptions {
+    *PRINT_OPTIONS.read().unwrap_or_else(|e| e.into_inner())
+}
+
+const PREFIX: &str = "tensor(";
+
+/// Writes `tensor(<nested rows>, shape=[..], dtype=.., requires_grad=..)`
+///
+/// Elements are right-aligned to the widest printed element, and rows of higher
+/// dimensions are separated by blank lines, one per extra dimension.
+fn format_tensor<T>(f: &mut Formatter<'_>, tensor: &dyn TensorBase<T>, element: impl Fn(&T) -> String) -> Result
+where
+    T: Debug + 'static,
+{
+    let options = print_options();
+    let summarize = tensor.data().len() > options.threshold;
+    let shape = tensor.shape();
+
+    let mut shown = Vec::new();
+    collect_shown(shape, 0, 0, summarize, options.edge_items, &mut shown);
+    let cells: Vec<String> = shown.iter().map(|&i| element(&tensor.data()[i])).collect();
+    let width = cells.iter().map(|c| c.chars().count()).max().unwrap_or(0);
+
+    let mut out = String::from(PREFIX);
+    let mut cells = cells.iter().map(|c| format!("{:>width$}", c));
+    write_dim(&mut out, shape, 0, summarize, options.edge_items, &mut cells);
+    write!(
+        f, "{}, shape={:?}, dtype={}, requires_grad={})",
+        out, shape, std::any::type_name::<T>(), tensor.requires_grad()
+    )
+}
+
+/// Returns the indices printed along a dimension, with `None` marking the elision
+fn shown_indices(size: usize, summarize: bool, edge_items: usize) -> Vec<Option<usize>> {
+    match summarize && size > 2 * edge_items {
+        true => (0..edge_items).map(Some)
+            .chain(std::iter::once(None))
+            .chain((size - edge_items..size).map(Some))
+            .collect(),
+        false => (0..size).map(Some).collect(),
+    }
+}
+
+/// Collects the flat offsets of the printed elements in printing order
+fn collect_shown(shape: &[usize], dim: usize, offset: usize, summarize: bool, edge_items: usize, shown: &mut Vec<usize>) {
+    if dim == shape.len() {
+        shown.push(offset);
+        return;
+    }
+    let stride: usize = shape[dim + 1..].iter().product();
+    for i in shown_indices(shape[dim], summarize, edge_items).into_iter().flatten() {
+        collect_shown(shape, dim + 1, offset + i * stride, summarize, edge_items, shown);
+    }
+}
+
+fn write_dim(out: &mut String, shape: &[usize], dim: usize, summarize: bool, edge_items: usize, cells: &mut impl Iterator<Item = String>) {
+    if dim == shape.len() {
+        out.push_str(&cells.next().unwrap_or_default());
+        return;
+    }
+
+    let separator = match dim + 1 == shape.len() {
+        true => ", ".to_string(),
+        false => format!(",{}{}", "\n".repeat(shape.len() - dim - 1), " ".repeat(PREFIX.len() + dim + 1)),
+    };
+    out.push('[');
+    for (n, i) in shown_indices(shape[dim], summarize, edge_items).into_iter().enumerate() {
+        if n > 0 {
+            out.push_str(&separator);
+        }
+        match i {
+            Some(_) => write_dim(out, shape, dim + 1, summarize, edge_items, cells),
+            None => out.push_str("..."),
+        }
+    }
+    out.push(']');
+}
+
+fn display_element<T: Display>(f: &Formatter<'_>) -> impl Fn(&T) -> String {
+    let precision = f.precision();
+    move |x| match precision {
+        Some(p) => format!("{:.*}", p, x),
+        None => x.to_string(),
+    }
+}
+
+impl<T: Debug + Display + 'static> Display for dyn TensorBase<T> + '_ {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        let element = display_element(f);
+        format_tensor(f, self, element)
+    }
+}
+
+impl<T: Debug + Display + 'static> Display for Tensor<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        Display::fmt(self as &dyn TensorBase<T>, f)
+    }
+}
+
+impl<T: Debug + Display + 'static> Display for ArcTensor<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        Display::fmt(&**self, f)
+    }
+}
+
+impl<T: Debug + 'static> Debug for Tensor<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        format_tensor(f, self, |x| format!("{:?}", x))
+    }
+}
+
+impl<T: Debug + 'static> Debug for ArcTensor<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        format_tensor(f, &**self, |x| format!("{:?}", x))
+    }
+}
+
+impl Debug for &dyn TensorBase<f32> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        format_tensor(f, *self, |x| format!("{:?}", x))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::MlResult;
+    use crate::tensor::*;
+
+    #[test]
+    fn test_display_2d() -> MlResult<()> {
+        let tensor = Tensor::<f32>::from_vec(vec![1.0, -2.5, 3.0, 10.0, 0.5, -6.0], &[2, 3])?;
+        assert_eq!(
+            format!("{}", tensor),
+            "tensor([[   1, -2.5,    3],\n        [  10,  0.5,   -6]], shape=[2, 3], dtype=f32, requires_grad=false)"
+                .replace("false", &cfg!(feature = "enable_backpropagation").to_string())
+        );
+        assert!(format!("{:.2}", tensor).starts_with("tensor([[ 1.00, -2.50,  3.00],\n        [10.00,  0.50, -6.00]]"));
+        assert!(format!("{:?}", tensor).starts_with("tensor([[ 1.0, -2.5,  3.0],"));
+        assert!(format!("{}", Tensor::<f32>::scalar(2.0)).starts_with("tensor(2, shape=[],"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_display_3d() -> MlResult<()> {
+        let tensor = Tensor::<usize>::from_vec((0..12).collect(), &[2, 2, 3])?;
+        assert_eq!(
+            format!("{}", tensor),
+            "tensor([[[ 0,  1,  2],\n         [ 3,  4,  5]],\n\n        [[ 6,  7,  8],\n         [ 9, 10, 11]]], \
+             shape=[2, 2, 3], dtype=usize, requires_grad=false)"
+                .replace("false", &cfg!(feature = "enable_backpropagation").to_string())
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_display_summarized() -> MlResult<()> {
+        let tensor = Tensor::<usize>::from_vec((0..2000).collect(), &[50, 40])?;
+        let text = format!("{:?}", tensor);
+        assert_eq!(text.lines().count(), 7);
+        assert_eq!(text.lines().next().unwrap(), "tensor([[   0,    1,    2, ...,   37,   38,   39],");
+        assert_eq!(text.lines().nth(3).unwrap(), "        ...,");
+        assert!(text.ends_with("[1960, 1961, 1962, ..., 1997, 1998, 1999]], shape=[50, 40], dtype=usize, requires_grad=false)"
+            .replace("false", &cfg!(feature = "enable_backpropagation").to_string()).as_str()));
+
+        let small = Tensor::<usize>::from_vec((0..8).collect(), &[8])?;
+        assert!(format!("{}", small).starts_with("tensor([0, 1, 2, 3, 4, 5, 6, 7],"));
+        Ok(())
+    }
+}
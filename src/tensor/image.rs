@@ -0,0 +1,154 @@
+use ::image::ColorType;
+
+use crate::{MlError, MlResult};
+use crate::tensor::{ArcTensor, Tensor, TensorBase, TensorError};
+
+/// The order of the dimensions of an image tensor
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImageLayout {
+    /// `[channels, height, width]`, as used by convolutions
+    Chw,
+    /// `[height, width, channels]`, the order pixels are stored in
+    Hwc,
+}
+
+impl Tensor<f32> {
+    /// Decodes a PNG or JPEG image into a tensor with values in `[0, 1]`
+    ///
+    /// Grayscale images have 1 channel, grayscale with alpha 2, RGB 3 and RGBA 4.
+    /// Higher bit depths are scaled to the same range.
+    ///
+    /// # Arguments
+    /// * `path` - The image file; the format is detected from its contents
+    /// * `layout` - Whether the channels come first or last
+    pub fn from_image(path: &str, layout: ImageLayout) -> MlResult<ArcTensor<f32>> {
+        let image = ::image::open(path).map_err(image_error)?;
+        let (width, height) = (image.width() as usize, image.height() as usize);
+        let color = image.color();
+        let (channels, pixels) = match (color.has_color(), color.has_alpha()) {
+            (false, false) => (1, image.to_luma32f().into_raw()),
+            (false, true) => (2, image.to_luma_alpha32f().into_raw()),
+            (true, false) => (3, image.to_rgb32f().into_raw()),
+            (true, true) => (4, image.to_rgba32f().into_raw()),
+        };
+
+        let hwc = Tensor::<f32>::from_vec(pixels, &[height, width, channels])?;
+        match layout {
+            ImageLayout::Hwc => Ok(hwc),
+            ImageLayout::Chw => hwc.permute(&[2, 0, 1]),
+        }
+    }
+}
+
+impl dyn TensorBase<f32> + '_ {
+    /// Saves a tensor as an 8-bit image
+    ///
+    /// Values are clamped to `[0, 1]` and scaled to `0..=255`. The format is chosen
+    /// from the file extension; JPEG does not support the alpha channel.
+    ///
+    /// # Arguments
+    /// * `path` - The file to write
+    /// * `layout` - Whether the channels come first or last in the tensor
+    ///
+    /// # Returns
+    /// * `Ok(())` on success
+    /// * `Err(MlError::TensorError)` with `InvalidOperation` if the tensor is not 3-D with 1 to 4 channels
+    /// * `Err(MlError::StringError)` if the image cannot be encoded or written
+    pub fn save_image(&self, path: &str, layout: ImageLayout) -> MlResult<()> {
+        let hwc = match layout {
+            ImageLayout::Hwc => Tensor::<f32>::from_vec(self.data().to_vec(), self.shape())?,
+            ImageLayout::Chw => self.permute(&[1, 2, 0])?,
+        };
+        let (height, width, channels) = match hwc.shape() {
+            &[h, w, c] if (1..=4).contains(&c) => (h, w, c),
+            _ => return Err(MlError::TensorError(TensorError::InvalidOperation {
+                op: "save_image",
+                reason: format!("expected a 3-D tensor with 1 to 4 channels in {:?} layout, got shape {:?}", layout, self.shape()),
+            })),
+        };
+
+        let bytes = hwc.to_u8();
+        let color = [ColorType::L8, ColorType::La8, ColorType::Rgb8, ColorType::Rgba8][channels - 1];
+        ::image::save_buffer(path, bytes.data(), width as u32, height as u32, color).map_err(image_error)
+    }
+}
+
+fn image_error(e: ::image::ImageError) -> MlError {
+    MlError::StringError(format!("image error: {}", e))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::MlResult;
+    use crate::tensor::*;
+    use crate::testing::temp_path;
+
+    /// A `[height, width, channels]` gradient with distinct values per channel
+    fn gradient(height: usize, width: usize, channels: usize) -> MlResult<ArcTensor<f32>> {
+        let data = (0..height * width * channels).map(|i| {
+            let (y, x, c) = (i / (width * channels), i / channels % width, i % channels);
+            (y * width + x) as f32 / (height * width) as f32 * (c + 1) as f32 / channels as f32
+        }).collect();
+        Tensor::<f32>::from_vec(data, &[height, width, channels])
+    }
+
+    #[test]
+    fn test_image_round_trip() -> MlResult<()> {
+        for channels in 1..=4 {
+            let path = temp_path(&format!("gradient_{}.png", channels));
+            let image = gradient(4, 5, channels)?;
+            image.save_image(&path, ImageLayout::Hwc)?;
+
+            let loaded = Tensor::<f32>::from_image(&path, ImageLayout::Hwc)?;
+            assert_eq!(loaded.shape(), &[4, 5, channels]);
+            assert!(loaded.data().iter().zip(image.data()).all(|(a, b)| (a - b).abs() <= 0.5 / 255.0 + 1e-6));
+            std::fs::remove_file(&path).unwrap();
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_image_layout() -> MlResult<()> {
+        let path = temp_path("layout.png");
+        let image = gradient(2, 3, 3)?;
+        image.save_image(&path, ImageLayout::Hwc)?;
+
+        let hwc = Tensor::<f32>::from_image(&path, ImageLayout::Hwc)?;
+        let chw = Tensor::<f32>::from_image(&path, ImageLayout::Chw)?;
+        assert_eq!(chw.shape(), &[3, 2, 3]);
+        for (y, x, c) in (0..2).flat_map(|y| (0..3).flat_map(move |x| (0..3).map(move |c| (y, x, c)))) {
+            assert_eq!(chw.data()[chw.index(&[c, y, x]).unwrap()], hwc.data()[hwc.index(&[y, x, c]).unwrap()]);
+        }
+
+        // saving from CHW writes the same pixels
+        chw.save_image(&path, ImageLayout::Chw)?;
+        assert_eq!(Tensor::<f32>::from_image(&path, ImageLayout::Hwc)?.data(), hwc.data());
+        std::fs::remove_file(&path).unwrap();
+
+        // out-of-range values are clamped
+        let bright = Tensor::<f32>::from_vec(vec![-1.0, 0.5, 2.0], &[1, 3, 1])?;
+        bright.save_image(&path, ImageLayout::Hwc)?;
+        assert_eq!(Tensor::<f32>::from_image(&path, ImageLayout::Hwc)?.data(), &[0.0, 128.0 / 255.0, 1.0]);
+        std::fs::remove_file(&path).unwrap();
+
+        assert!(gradient(2, 3, 5)?.save_image(&path, ImageLayout::Hwc).is_err());
+        assert!(Tensor::<f32>::from_vec(vec![0.0; 4], &[2, 2])?.save_image(&path, ImageLayout::Hwc).is_err());
+        assert!(Tensor::<f32>::from_image(&temp_path("missing.png"), ImageLayout::Hwc).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_jpeg_round_trip() -> MlResult<()> {
+        let path = temp_path("gradient.jpg");
+        let image = gradient(8, 8, 3)?;
+        image.save_image(&path, ImageLayout::Hwc)?;
+
+        let loaded = Tensor::<f32>::from_image(&path, ImageLayout::Hwc)?;
+        assert_eq!(loaded.shape(), &[8, 8, 3]);
+        // JPEG is lossy, so only check that the image is close on average
+        let error: f32 = loaded.data().iter().zip(image.data()).map(|(a, b)| (a - b).abs()).sum::<f32>() / 192.0;
+        assert!(error < 0.05);
+        std::fs::remove_file(&path).unwrap();
+        Ok(())
+    }
+}
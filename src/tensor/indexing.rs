@@ -0,0 +1,1114 @@
+use std::fmt::Debug;
+
+use crate::{MlError, MlResult};
+use crate::tensor::broadcast::{broadcast_index, broadcast_shape};
+use crate::tensor::{normalize_dim, ArcTensor, Tensor, TensorBase, TensorError};
+
+impl<T: Debug + Clone + 'static> dyn TensorBase<T> + '_ {
+    /// Returns the value of a tensor holding exactly one element
+    ///
+    /// Works for 0-dimensional tensors (shape `[]`) as well as shapes such as `[1]` or `[1, 1]`.
+    ///
+    /// # Returns
+    /// * `Ok(T)` with the single element
+    /// * `Err(MlError::TensorError)` with `InvalidOperation` naming the shape if the tensor
+    ///   does not have exactly one element
+    pub fn item(&self) -> MlResult<T> {
+        match self.data() {
+            [value] => Ok(value.clone()),
+            _ => Err(MlError::TensorError(TensorError::InvalidOperation {
+                op: "item",
+                reason: format!("expected a single element, got a tensor with shape {:?}", self.shape()),
+            })),
+        }
+    }
+
+    /// Returns the elements of a tensor holding exactly `N` elements as an array
+    ///
+    /// Handy for small fixed-size results, e.g. `let [loss, accuracy] = metrics.to_scalar_array()?`.
+    ///
+    /// # Returns
+    /// * `Ok([T; N])` with the elements in row-major order
+    /// * `Err(MlError::TensorError)` with `InvalidOperation` naming the shape if the tensor
+    ///   does not have exactly `N` elements
+    pub fn to_scalar_array<const N: usize>(&self) -> MlResult<[T; N]> {
+        if self.data().len() != N {
+            return Err(MlError::TensorError(TensorError::InvalidOperation {
+                op: "to_scalar_array",
+                reason: format!("expected {} elements, got a tensor with shape {:?}", N, self.shape()),
+            }));
+        }
+        Ok(std::array::from_fn(|i| self.data()[i].clone()))
+    }
+
+    /// Returns an owned copy of the elements in row-major order
+    pub fn to_vec(&self) -> Vec<T> {
+        self.iter().cloned().collect()
+    }
+
+    /// Converts a 2-D tensor into nested rows, the inverse of `Tensor::new`
+    ///
+    /// # Returns
+    /// * `Ok(Vec<Vec<T>>)` with one inner vector per row
+    /// * `Err(MlError::TensorError)` with `InvalidOperation` naming the shape if the tensor is not 2-D
+    pub fn to_vec2d(&self) -> MlResult<Vec<Vec<T>>> {
+        let [rows, cols] = self.nested_shape::<2>("to_vec2d")?;
+        let data = self.to_vec();
+        Ok((0..rows).map(|r| data[r * cols..(r + 1) * cols].to_vec()).collect())
+    }
+
+    /// Converts a 3-D tensor into nested vectors indexed as `[i][j][k]`
+    ///
+    /// # Returns
+    /// * `Ok(Vec<Vec<Vec<T>>>)` preserving row-major order
+    /// * `Err(MlError::TensorError)` with `InvalidOperation` naming the shape if the tensor is not 3-D
+    pub fn to_vec3d(&self) -> MlResult<Vec<Vec<Vec<T>>>> {
+        let [depth, rows, cols] = self.nested_shape::<3>("to_vec3d")?;
+        let data = self.to_vec();
+        Ok((0..depth).map(|d| {
+            (0..rows).map(|r| {
+                let start = (d * rows + r) * cols;
+                data[start..start + cols].to_vec()
+            }).collect()
+        }).collect())
+    }
+
+    fn nested_shape<const N: usize>(&self, op: &'static str) -> MlResult<[usize; N]> {
+        self.shape().try_into().map_err(|_| MlError::TensorError(TensorError::InvalidOperation {
+            op,
+            reason: format!("expected a {}-D tensor, got shape {:?}", N, self.shape()),
+        }))
+    }
+
+    /// Repeats a 1-D tensor cyclically to exactly `length` elements
+    ///
+    /// The last copy is truncated, so `[1, 2, 3]` tiled to 7 gives `[1, 2, 3, 1, 2, 3, 1]`.
+    ///
+    /// # Arguments
+    /// * `length` - The number of elements in the result
+    ///
+    /// # Returns
+    /// * `Ok(ArcTensor)` with shape `[length]`
+    /// * `Err(MlError::TensorError)` with `InvalidOperation` if the tensor is not 1-D and
+    ///   non-empty, or `length` is 0
+    pub fn tile_to(&self, length: usize) -> MlResult<ArcTensor<T>> {
+        let reason = match (self.shape(), length) {
+            ([], _) | ([_, _, ..], _) => Some(format!("expected a 1-D tensor, got shape {:?}", self.shape())),
+            ([0], _) => Some("cannot tile an empty tensor".to_string()),
+            (_, 0) => Some("length must be greater than 0".to_string()),
+            _ => None,
+        };
+        if let Some(reason) = reason {
+            return Err(MlError::TensorError(TensorError::InvalidOperation { op: "tile_to", reason }));
+        }
+
+        let data = self.data().iter().cycle().take(length).cloned().collect();
+        Tensor::<T>::from_vec(data, &[length])
+    }
+
+    /// Inserts a new axis at `dim` and repeats the tensor `size` times along it
+    ///
+    /// This is unsqueeze followed by expand in one call, e.g. turning a single `[3, 4]`
+    /// sample into a `[size, 3, 4]` batch with `dim = 0`. The copies are materialized.
+    ///
+    /// # Arguments
+    /// * `dim` - Where to insert the new axis, in `-(ndim + 1)..=ndim`
+    /// * `size` - The length of the new axis
+    ///
+    /// # Returns
+    /// * `Ok(ArcTensor)` with rank one higher than `self`
+    /// * `Err(MlError::TensorError)` with `InvalidAxis` if `dim` is out of range
+    pub fn unsqueeze_expand(&self, dim: i32, size: usize) -> MlResult<ArcTensor<T>> {
+        let dim = normalize_dim(dim, self.ndim() + 1)?;
+        let inner: usize = self.shape()[dim..].iter().product();
+
+        let mut data = Vec::with_capacity(self.numel() * size);
+        if inner > 0 {
+            for chunk in self.data().chunks(inner) {
+                for _ in 0..size {
+                    data.extend_from_slice(chunk);
+                }
+            }
+        }
+
+        let mut shape = self.shape().to_vec();
+        shape.insert(dim, size);
+        Tensor::<T>::from_vec(data, &shape)
+    }
+
+    /// Gathers elements at a list of full N-dimensional coordinates
+    ///
+    /// # Arguments
+    /// * `coords` - A `[k, ndim]` tensor where each row is the coordinate of one element
+    ///
+    /// # Returns
+    /// A new `[k]` tensor holding the selected elements in the order of `coords`
+    pub fn gather_nd(&self, coords: &dyn TensorBase<usize>) -> MlResult<ArcTensor<T>> {
+        let ndim = self.shape().len();
+        if coords.shape().len() != 2 || coords.shape()[1] != ndim {
+            return Err(MlError::TensorError(TensorError::InvalidShape {
+                expected: vec![coords.shape().first().copied().unwrap_or(0), ndim],
+                got: coords.shape().to_vec(),
+            }));
+        }
+
+        let k = coords.shape()[0];
+        let mut data = Vec::with_capacity(k);
+        for row in 0..k {
+            let coord = &coords.data()[row * ndim..(row + 1) * ndim];
+            if coord.iter().zip(self.shape()).any(|(&i, &dim)| i >= dim) {
+                return Err(MlError::TensorError(TensorError::InvalidOperation {
+                    op: "gather_nd",
+                    reason: format!("coordinate {:?} is out of bounds for shape {:?}", coord, self.shape()),
+                }));
+            }
+            let offset = coord.iter().zip(self.shape()).fold(0, |acc, (&i, &dim)| acc * dim + i);
+            data.push(self.data()[offset].clone());
+        }
+
+        Tensor::<T>::from_vec(data, &[k])
+    }
+
+    /// Replaces the elements where `mask` is true with `value`
+    ///
+    /// # Arguments
+    /// * `mask` - A boolean tensor broadcastable to the shape of `self`
+    /// * `value` - The value to write at masked positions
+    ///
+    /// # Returns
+    /// * `Ok(ArcTensor)` with the same shape as `self`
+    /// * `Err(MlError::TensorError)` with `InvalidShape` if the mask does not broadcast to `self`
+    pub fn masked_fill(&self, mask: &dyn TensorBase<bool>, value: T) -> MlResult<ArcTensor<T>> {
+        if broadcast_shape(self.shape(), mask.shape())? != self.shape() {
+            return Err(MlError::TensorError(TensorError::InvalidShape {
+                expected: self.shape().to_vec(),
+                got: mask.shape().to_vec(),
+            }));
+        }
+
+        let data = self.data().iter().enumerate().map(|(i, x)| {
+            match mask.data()[broadcast_index(i, self.shape(), mask.shape())] {
+                true => value.clone(),
+                false => x.clone(),
+            }
+        }).collect();
+        Tensor::<T>::from_vec(data, self.shape())
+    }
+
+    /// Selects a single slice along a dimension, removing that dimension
+    ///
+    /// # Arguments
+    /// * `dim` - The dimension to index; negative values index from the end
+    /// * `index` - The position of the slice along `dim`
+    ///
+    /// # Returns
+    /// * `Ok(ArcTensor)` with rank reduced by one
+    /// * `Err(MlError::TensorError)` with `InvalidAxis` if `dim` or `index` is out of range
+    pub fn select(&self, dim: i32, index: usize) -> MlResult<ArcTensor<T>> {
+        let dim = normalize_dim(dim, self.ndim())?;
+        let dim_size = self.shape()[dim];
+        if index >= dim_size {
+            return Err(MlError::TensorError(TensorError::InvalidAxis {
+                axis: dim as i32,
+                ndim: self.ndim(),
+            }));
+        }
+
+        let stride: usize = self.shape()[dim + 1..].iter().product();
+        let outer_dims: usize = self.shape()[..dim].iter().product();
+        let mut data = Vec::with_capacity(outer_dims * stride);
+        for i in 0..outer_dims {
+            let start = (i * dim_size + index) * stride;
+            data.extend_from_slice(&self.data()[start..start + stride]);
+        }
+
+        let mut shape = self.shape().to_vec();
+        shape.remove(dim);
+        Tensor::<T>::from_vec(data, &shape)
+    }
+
+    /// Selects slices at a list of positions along a dimension
+    ///
+    /// Positions may repeat and appear in any order, so this reorders, subsamples
+    /// or duplicates slices.
+    ///
+    /// # Arguments
+    /// * `dim` - The dimension to index; negative values index from the end
+    /// * `indices` - A 1-D tensor of positions along `dim`
+    ///
+    /// # Returns
+    /// * `Ok(ArcTensor)` with the size of `dim` replaced by the number of indices
+    /// * `Err(MlError::TensorError)` with `InvalidShape` if `indices` is not 1-D, or
+    ///   `InvalidAxis` if `dim` or any index is out of range
+    pub fn index_select(&self, dim: i32, indices: &dyn TensorBase<usize>) -> MlResult<ArcTensor<T>> {
+        let dim = normalize_dim(dim, self.ndim())?;
+        if indices.shape().len() != 1 {
+            return Err(MlError::TensorError(TensorError::InvalidShape {
+                expected: vec![indices.data().len()],
+                got: indices.shape().to_vec(),
+            }));
+        }
+        let dim_size = self.shape()[dim];
+        if indices.data().iter().any(|&index| index >= dim_size) {
+            return Err(MlError::TensorError(TensorError::InvalidAxis {
+                axis: dim as i32,
+                ndim: self.ndim(),
+            }));
+        }
+
+        let stride: usize = self.shape()[dim + 1..].iter().product();
+        let outer_dims: usize = self.shape()[..dim].iter().product();
+        let mut data = Vec::with_capacity(outer_dims * indices.data().len() * stride);
+        for i in 0..outer_dims {
+            for &index in indices.data() {
+                let start = (i * dim_size + index) * stride;
+                data.extend_from_slice(&self.data()[start..start + stride]);
+            }
+        }
+
+        let mut shape = self.shape().to_vec();
+        shape[dim] = indices.data().len();
+        Tensor::<T>::from_vec(data, &shape)
+    }
+
+    /// Iterates over successive minibatches of `batch_size` rows along axis 0
+    ///
+    /// # Arguments
+    /// * `batch_size` - The number of rows per batch
+    /// * `drop_last` - Whether to discard a final batch with fewer than `batch_size` rows
+    ///
+    /// # Returns
+    /// An iterator of tensors with shape `[batch_size, ...]`. The iterator is empty
+    /// if `batch_size` is 0 or the tensor has rank 0.
+    pub fn batches(&self, batch_size: usize, drop_last: bool) -> impl Iterator<Item = ArcTensor<T>> + '_ {
+        let rows = if batch_size == 0 { 0 } else { self.shape().first().copied().unwrap_or(0) };
+        let row_size: usize = self.shape().iter().skip(1).product();
+        let num_batches = match drop_last {
+            true => rows / batch_size.max(1),
+            false => rows.div_ceil(batch_size.max(1)),
+        };
+
+        (0..num_batches).map(move |b| {
+            let start = b * batch_size;
+            let end = (start + batch_size).min(rows);
+            let mut shape = self.shape().to_vec();
+            shape[0] = end - start;
+            Tensor::<T>::from_vec(self.data()[start * row_size..end * row_size].to_vec(), &shape)
+                .expect("batch data always matches its shape")
+        })
+    }
+
+    /// Iterates over the rows of a 2-D tensor
+    ///
+    /// # Returns
+    /// * `Ok(Iterator)` of 1-D tensors, one per row
+    /// * `Err(MlError::TensorError)` with `InvalidOperation` if the tensor is not 2-D
+    pub fn rows(&self) -> MlResult<impl Iterator<Item = ArcTensor<T>> + '_> {
+        if self.shape().len() != 2 {
+            return Err(MlError::TensorError(TensorError::InvalidOperation {
+                op: "rows",
+                reason: format!("expected a 2-D tensor, got shape {:?}", self.shape()),
+            }));
+        }
+        self.axis_iter(0)
+    }
+
+    /// Iterates over the slices along a dimension, each with that dimension removed
+    ///
+    /// Iterating axis 0 of a `[batch, ...]` tensor yields one sample at a time.
+    ///
+    /// # Arguments
+    /// * `dim` - The dimension to iterate; negative values index from the end
+    ///
+    /// # Returns
+    /// * `Ok(Iterator)` of tensors with rank reduced by one
+    /// * `Err(MlError::TensorError)` with `InvalidAxis` if `dim` is out of range
+    pub fn axis_iter(&self, dim: i32) -> MlResult<impl Iterator<Item = ArcTensor<T>> + '_> {
+        let dim = normalize_dim(dim, self.ndim())?;
+        Ok((0..self.shape()[dim]).map(move |i| {
+            self.select(dim as i32, i).expect("index is within the dimension")
+        }))
+    }
+
+    /// Iterates over chunks of `size` slices along a dimension, keeping that dimension
+    ///
+    /// The last chunk is smaller if the dimension is not divisible by `size`.
+    ///
+    /// # Arguments
+    /// * `dim` - The dimension to split; negative values index from the end
+    /// * `size` - The number of slices per chunk
+    ///
+    /// # Returns
+    /// * `Ok(Iterator)` of tensors with the same rank as `self`
+    /// * `Err(MlError::TensorError)` with `InvalidAxis` if `dim` is out of range, or
+    ///   `InvalidOperation` if `size` is 0
+    pub fn axis_chunks_iter(&self, dim: i32, size: usize) -> MlResult<impl Iterator<Item = ArcTensor<T>> + '_> {
+        let dim = normalize_dim(dim, self.ndim())?;
+        if size == 0 {
+            return Err(MlError::TensorError(TensorError::InvalidOperation {
+                op: "axis_chunks_iter",
+                reason: "chunk size must be positive".to_string(),
+            }));
+        }
+
+        let dim_size = self.shape()[dim];
+        Ok((0..dim_size.div_ceil(size)).map(move |c| {
+            slice_along(self, dim, c * size, ((c + 1) * size).min(dim_size))
+        }))
+    }
+
+    /// Splits the tensor into pieces of the given sizes along a dimension
+    ///
+    /// Unlike [`axis_chunks_iter`](Self::axis_chunks_iter) the pieces may differ in
+    /// size, e.g. to separate fused query, key and value projections of unequal width.
+    ///
+    /// # Arguments
+    /// * `sizes` - The length of each piece along `dim`; they must sum to that dimension
+    /// * `dim` - The dimension to split; negative values index from the end
+    ///
+    /// # Returns
+    /// * `Ok(Vec<ArcTensor>)` with one tensor per entry of `sizes`, each keeping the rank of `self`
+    /// * `Err(MlError::TensorError)` with `InvalidAxis` if `dim` is out of range, or
+    ///   `InvalidShape` if `sizes` do not sum to the dimension length
+    pub fn split_with_sizes(&self, sizes: &[usize], dim: i32) -> MlResult<Vec<ArcTensor<T>>> {
+        let dim = normalize_dim(dim, self.ndim())?;
+        let total: usize = sizes.iter().sum();
+        if total != self.shape()[dim] {
+            let mut got = self.shape().to_vec();
+            got[dim] = total;
+            return Err(MlError::TensorError(TensorError::InvalidShape {
+                expected: self.shape().to_vec(),
+                got,
+            }));
+        }
+
+        let mut start = 0;
+        Ok(sizes.iter().map(|&size| {
+            let piece = slice_along(self, dim, start, start + size);
+            start += size;
+            piece
+        }).collect())
+    }
+}
+
+/// Copies the slices `start..end` of `dim` into a new tensor of the same rank
+fn slice_along<T: Debug + Clone + 'static>(tensor: &dyn TensorBase<T>, dim: usize, start: usize, end: usize) -> ArcTensor<T> {
+    let dim_size = tensor.shape()[dim];
+    let stride: usize = tensor.shape()[dim + 1..].iter().product();
+    let outer_dims: usize = tensor.shape()[..dim].iter().product();
+    let mut data = Vec::with_capacity(outer_dims * (end - start) * stride);
+    for i in 0..outer_dims {
+        data.extend_from_slice(&tensor.data()[(i * dim_size + start) * stride..(i * dim_size + end) * stride]);
+    }
+    let mut shape = tensor.shape().to_vec();
+    shape[dim] = end - start;
+    Tensor::<T>::from_vec(data, &shape).expect("slice data always matches its shape")
+}
+
+impl<T: Debug + Clone + std::ops::AddAssign + 'static> dyn TensorBase<T> + '_ {
+    /// Adds `src` into a copy of `self` at the positions given by `index` along `dim`
+    ///
+    /// For a 2-D tensor and `dim = 0`, `out[index[i][j]][j] += src[i][j]`. Sources mapping to
+    /// the same position are summed, unlike a scatter that overwrites. This is the
+    /// accumulation behind embedding bags and sparse gradients.
+    ///
+    /// # Arguments
+    /// * `dim` - The dimension to scatter along; negative values index from the end
+    /// * `index` - Target positions along `dim`, with the same shape as `src`
+    /// * `src` - The values to add; same rank as `self` and no larger in any dimension but `dim`
+    ///
+    /// # Returns
+    /// * `Ok(ArcTensor)` with the same shape as `self`
+    /// * `Err(MlError::TensorError)` with `InvalidAxis` if `dim` is out of range, `InvalidShape`
+    ///   if the shapes do not agree, or `InvalidOperation` if an index is out of bounds
+    pub fn scatter_add(&self, dim: i32, index: &dyn TensorBase<usize>, src: &dyn TensorBase<T>) -> MlResult<ArcTensor<T>> {
+        let dim = normalize_dim(dim, self.ndim())?;
+        let fits = src.ndim() == self.ndim()
+            && src.shape().iter().zip(self.shape()).enumerate().all(|(d, (&s, &t))| d == dim || s <= t);
+        if index.shape() != src.shape() || !fits {
+            return Err(MlError::TensorError(TensorError::InvalidShape {
+                expected: src.shape().to_vec(),
+                got: index.shape().to_vec(),
+            }));
+        }
+
+        let mut data = self.data().to_vec();
+        for ((mut coord, &position), value) in index.indexed_iter().zip(src.iter()) {
+            if position >= self.shape()[dim] {
+                return Err(MlError::TensorError(TensorError::InvalidOperation {
+                    op: "scatter_add",
+                    reason: format!("index {} is out of bounds for dimension {} with size {}", position, dim, self.shape()[dim]),
+                }));
+            }
+            coord[dim] = position;
+            let offset = coord.iter().zip(self.shape()).fold(0, |acc, (&i, &size)| acc * size + i);
+            data[offset] += value.clone();
+        }
+        Tensor::<T>::from_vec(data, self.shape())
+    }
+}
+
+/// Stacks tensors of the same shape along a new dimension
+///
+/// # Arguments
+/// * `tensors` - The tensors to stack, all with the same shape
+/// * `dim` - The position of the new dimension, in `0..=rank`; negative values index from the end
+///
+/// # Returns
+/// * `Ok(ArcTensor)` with shape `[..shape[..dim], tensors.len(), ..shape[dim..]]`
+/// * `Err(MlError::TensorError)` with `EmptyTensor` if `tensors` is empty, `InvalidShape`
+///   if the shapes differ, or `InvalidAxis` if `dim` is out of range
+pub fn stack<T: Debug + Clone + 'static>(tensors: &[&dyn TensorBase<T>], dim: i32) -> MlResult<ArcTensor<T>> {
+    let first = tensors.first().ok_or(MlError::TensorError(TensorError::EmptyTensor))?;
+    for tensor in &tensors[1..] {
+        first.chk_shape(*tensor)?;
+    }
+
+    let dim = normalize_dim(dim, first.ndim() + 1)?;
+
+    let inner: usize = first.shape()[dim..].iter().product();
+    let outer: usize = first.shape()[..dim].iter().product();
+    let mut data = Vec::with_capacity(outer * inner * tensors.len());
+    for o in 0..outer {
+        for tensor in tensors {
+            data.extend_from_slice(&tensor.data()[o * inner..(o + 1) * inner]);
+        }
+    }
+
+    let mut shape = first.shape().to_vec();
+    shape.insert(dim, tensors.len());
+    Tensor::<T>::from_vec(data, &shape)
+}
+
+/// Concatenates tensors along an existing dimension
+///
+/// # Arguments
+/// * `tensors` - The tensors to join, with equal shapes except along `dim`
+/// * `dim` - The dimension to join along; negative values index from the end
+///
+/// # Returns
+/// * `Ok(ArcTensor)` whose size along `dim` is the sum of the inputs' sizes
+/// * `Err(MlError::TensorError)` with `EmptyTensor` if `tensors` is empty, `InvalidShape`
+///   if the shapes differ outside `dim`, or `InvalidAxis` if `dim` is out of range
+pub fn concat<T: Debug + Clone + 'static>(tensors: &[&dyn TensorBase<T>], dim: i32) -> MlResult<ArcTensor<T>> {
+    let dim = check_concat_shapes(tensors, dim)?;
+    let first = tensors[0];
+    let outer: usize = first.shape()[..dim].iter().product();
+    let total: usize = tensors.iter().map(|t| t.shape()[dim]).sum();
+    let mut data = Vec::with_capacity(tensors.iter().map(|t| t.numel()).sum());
+    for o in 0..outer {
+        for tensor in tensors {
+            let inner = tensor.numel() / outer.max(1);
+            data.extend_from_slice(&tensor.data()[o * inner..(o + 1) * inner]);
+        }
+    }
+
+    let mut shape = first.shape().to_vec();
+    shape[dim] = total;
+    Tensor::<T>::from_vec(data, &shape)
+}
+
+/// Validates the inputs of [`concat`] and returns the normalized dimension
+pub(crate) fn check_concat_shapes<T: Debug + 'static>(tensors: &[&dyn TensorBase<T>], dim: i32) -> MlResult<usize> {
+    let first = tensors.first().ok_or(MlError::TensorError(TensorError::EmptyTensor))?;
+    let dim = normalize_dim(dim, first.ndim())?;
+    for tensor in &tensors[1..] {
+        let matches = tensor.ndim() == first.ndim()
+            && tensor.shape().iter().zip(first.shape()).enumerate().all(|(d, (a, b))| d == dim || a == b);
+        if !matches {
+            return Err(MlError::TensorError(TensorError::InvalidShape {
+                expected: first.shape().to_vec(),
+                got: tensor.shape().to_vec(),
+            }));
+        }
+    }
+    Ok(dim)
+}
+
+/// Builds a padding mask from sequence lengths
+///
+/// Row `b` of the mask holds 1.0 at positions `< lengths[b]` and 0.0 beyond, so
+/// padded steps of a batch of variable-length sequences can be masked out.
+///
+/// # Arguments
+/// * `lengths` - A 1-D tensor with the length of each sequence in the batch
+/// * `max_len` - The padded length of the sequences
+///
+/// # Returns
+/// * `Ok(ArcTensor)` with shape `[batch, max_len]`
+/// * `Err(MlError::TensorError)` with `InvalidShape` if `lengths` is not 1-D, or
+///   `InvalidOperation` if a length exceeds `max_len`
+pub fn sequence_mask(lengths: &dyn TensorBase<usize>, max_len: usize) -> MlResult<ArcTensor<f32>> {
+    if lengths.shape().len() != 1 {
+        return Err(MlError::TensorError(TensorError::InvalidShape {
+            expected: vec![lengths.data().len()],
+            got: lengths.shape().to_vec(),
+        }));
+    }
+    if let Some(&length) = lengths.data().iter().find(|&&length| length > max_len) {
+        return Err(MlError::TensorError(TensorError::InvalidOperation {
+            op: "sequence_mask",
+            reason: format!("length {} exceeds max_len {}", length, max_len),
+        }));
+    }
+
+    let data = lengths.data().iter()
+        .flat_map(|&length| (0..max_len).map(move |t| if t < length { 1.0 } else { 0.0 }))
+        .collect();
+    Tensor::<f32>::from_vec(data, &[lengths.data().len(), max_len])
+}
+
+/// Iterator over the elements of a tensor and their multi-dimensional indices
+///
+/// Created by [`TensorBase::indexed_iter`].
+pub struct IndexedIter<'a, T> {
+    data: std::slice::Iter<'a, T>,
+    shape: &'a [usize],
+    index: Vec<usize>,
+}
+
+impl<'a, T> IndexedIter<'a, T> {
+    pub(crate) fn new(data: &'a [T], shape: &'a [usize]) -> Self {
+        Self { data: data.iter(), shape, index: vec![0; shape.len()] }
+    }
+}
+
+impl<'a, T> Iterator for IndexedIter<'a, T> {
+    type Item = (Vec<usize>, &'a T);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let value = self.data.next()?;
+        let index = self.index.clone();
+        for (i, &dim) in self.index.iter_mut().zip(self.shape).rev() {
+            *i += 1;
+            if *i < dim {
+                break;
+            }
+            *i = 0;
+        }
+        Some((index, value))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.data.size_hint()
+    }
+}
+
+impl<T> ExactSizeIterator for IndexedIter<'_, T> {}
+
+/// Computes the flat offset of `indices`, panicking with the index and shape if any dimension is out of range
+fn checked_offset(indices: &[usize], shape: &[usize]) -> usize {
+    if indices.len() != shape.len() {
+        panic!("index {:?} has {} dimensions but the tensor with shape {:?} has {}", indices, indices.len(), shape, shape.len());
+    }
+    indices.iter().zip(shape).enumerate().fold(0, |acc, (d, (&i, &dim))| {
+        if i >= dim {
+            panic!("index {:?} is out of bounds for dimension {} with size {} of tensor with shape {:?}", indices, d, dim, shape);
+        }
+        acc * dim + i
+    })
+}
+
+/// Reads an element by multi-dimensional index, e.g. `tensor[[1, 2]]`
+///
+/// # Panics
+/// Panics if the number of indices differs from the rank or an index is out of range
+impl<T: Debug + 'static, const N: usize> std::ops::Index<[usize; N]> for Tensor<T> {
+    type Output = T;
+
+    fn index(&self, indices: [usize; N]) -> &T {
+        &self.data[checked_offset(&indices, &self.shape)]
+    }
+}
+
+/// Writes an element by multi-dimensional index, e.g. `tensor[[1, 2]] = 0.0`
+///
+/// # Panics
+/// Panics if the number of indices differs from the rank or an index is out of range
+impl<T: Debug + Clone + 'static, const N: usize> std::ops::IndexMut<[usize; N]> for Tensor<T> {
+    fn index_mut(&mut self, indices: [usize; N]) -> &mut T {
+        let offset = checked_offset(&indices, &self.shape);
+        &mut self.elements_mut()[offset]
+    }
+}
+
+/// Reads an element by multi-dimensional index, e.g. `tensor[[1, 2]]`
+///
+/// # Panics
+/// Panics if the number of indices differs from the rank or an index is out of range
+impl<T: Debug + 'static, const N: usize> std::ops::Index<[usize; N]> for ArcTensor<T> {
+    type Output = T;
+
+    fn index(&self, indices: [usize; N]) -> &T {
+        &self.data()[checked_offset(&indices, self.shape())]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{ops, MlError, MlResult};
+    use crate::tensor::*;
+
+    #[test]
+    fn test_index_operator() -> MlResult<()> {
+        let tensor = Tensor::<f32>::from_vec((0..24).map(|x| x as f32).collect(), &[2, 3, 4])?;
+        assert_eq!(tensor[[0, 0, 0]], 0.0);
+        assert_eq!(tensor[[0, 2, 1]], 9.0);
+        assert_eq!(tensor[[1, 0, 3]], 15.0);
+        assert_eq!(tensor[[1, 2, 3]], 23.0);
+
+        // get() no longer folds an out-of-range index into another element
+        assert_eq!(tensor.get(&[0, 3, 0]), None);
+        assert_eq!(tensor.get(&[1, 1, 1]), Some(&17.0));
+        Ok(())
+    }
+
+    #[test]
+    fn test_indexed_iter() -> MlResult<()> {
+        let tensor = Tensor::<f32>::from_vec((0..24).map(|x| x as f32 * 0.5).collect(), &[2, 3, 4])?;
+
+        let mut expected = Vec::new();
+        for i in 0..2 {
+            for j in 0..3 {
+                for k in 0..4 {
+                    expected.push((vec![i, j, k], tensor.get(&[i, j, k]).unwrap()));
+                }
+            }
+        }
+        let indexed: Vec<_> = tensor.indexed_iter().collect();
+        assert_eq!(indexed, expected);
+        assert_eq!(tensor.indexed_iter().len(), 24);
+        assert!(tensor.indexed_iter().all(|(idx, &v)| tensor.data()[tensor.index(&idx).unwrap()] == v));
+        assert!(tensor.iter().eq(tensor.data()));
+
+        let scalar = Tensor::<f32>::scalar(3.0);
+        assert_eq!(scalar.indexed_iter().collect::<Vec<_>>(), vec![(vec![], &3.0)]);
+        let empty = Tensor::<f32>::from_vec(vec![], &[2, 0])?;
+        assert_eq!(empty.indexed_iter().count(), 0);
+        Ok(())
+    }
+
+    #[test]
+    fn test_iter_mut() -> MlResult<()> {
+        let mut tensor = Tensor::<f32>::from_vec_owned(vec![1.0, -2.0, 3.0, -4.0], &[2, 2])?;
+        tensor.iter_mut().filter(|x| **x < 0.0).for_each(|x| *x = 0.0);
+        assert_eq!(tensor.data(), &[1.0, 0.0, 3.0, 0.0]);
+
+        let mut total = 0.0;
+        for x in &tensor {
+            total += x;
+        }
+        assert_eq!(total, 4.0);
+        assert_eq!(tensor.indexed_iter().nth(2), Some((vec![1, 0], &3.0)));
+        Ok(())
+    }
+
+    #[test]
+    fn test_index_mut() -> MlResult<()> {
+        let mut tensor = Tensor::<f32>::from_vec_owned(vec![0.0; 6], &[2, 3])?;
+        tensor[[1, 2]] = 5.0;
+        tensor[[0, 1]] += 2.0;
+
+        assert_eq!(tensor[[1, 2]], 5.0);
+        assert_eq!(tensor.data(), &[0.0, 2.0, 0.0, 0.0, 0.0, 5.0]);
+        Ok(())
+    }
+
+    #[test]
+    #[should_panic(expected = "index [0, 3] is out of bounds for dimension 1 with size 3 of tensor with shape [2, 3]")]
+    fn test_index_out_of_range() {
+        let tensor = Tensor::<f32>::from_vec(vec![0.0; 6], &[2, 3]).unwrap();
+        let _ = tensor[[0, 3]];
+    }
+
+    #[test]
+    #[should_panic(expected = "index [1] has 1 dimensions but the tensor with shape [2, 3] has 2")]
+    fn test_index_rank_mismatch() {
+        let mut tensor = Tensor::<f32>::from_vec_owned(vec![0.0; 6], &[2, 3]).unwrap();
+        tensor[[1]] = 1.0;
+    }
+
+    #[test]
+    fn test_gather_nd() -> MlResult<()> {
+        let tensor = Tensor::<f32>::from_vec((0..24).map(|x| x as f32).collect(), &[2, 3, 4])?;
+        let coords = Tensor::<usize>::from_vec(vec![0, 0, 0, 1, 2, 3, 1, 0, 2, 0, 0, 0], &[4, 3])?;
+        let result = tensor.gather_nd(&*coords)?;
+
+        assert_eq!(result.shape(), &[4]);
+        assert_eq!(result.data(), &[0.0, 23.0, 14.0, 0.0]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_gather_nd_invalid() -> MlResult<()> {
+        let tensor = Tensor::<f32>::from_vec(vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0], &[2, 3])?;
+        let wrong_rank = Tensor::<usize>::from_vec(vec![0, 0, 0], &[1, 3])?;
+        let out_of_bounds = Tensor::<usize>::from_vec(vec![0, 1, 2, 0], &[2, 2])?;
+        let coords = Tensor::<usize>::from_vec(vec![1, 3], &[1, 2])?;
+
+        assert!(tensor.gather_nd(&*wrong_rank).is_err());
+        assert!(tensor.gather_nd(&*out_of_bounds).is_err());
+        assert!(tensor.gather_nd(&*coords).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_select() -> MlResult<()> {
+        let tensor = Tensor::<f32>::from_vec((0..15).map(|x| x as f32).collect(), &[5, 3])?;
+
+        let row = tensor.select(0, 2)?;
+        assert_eq!(row.shape(), &[3]);
+        assert_eq!(row.data(), &[6.0, 7.0, 8.0]);
+
+        let column = tensor.select(-1, 1)?;
+        assert_eq!(column.shape(), &[5]);
+        assert_eq!(column.data(), &[1.0, 4.0, 7.0, 10.0, 13.0]);
+
+        let cube = Tensor::<usize>::from_vec((0..24).collect(), &[2, 3, 4])?;
+        let middle = cube.select(1, 2)?;
+        assert_eq!(middle.shape(), &[2, 4]);
+        assert_eq!(middle.data(), &[8, 9, 10, 11, 20, 21, 22, 23]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_select_invalid() -> MlResult<()> {
+        let tensor = Tensor::<f32>::from_vec(vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0], &[2, 3])?;
+
+        assert!(tensor.select(0, 2).is_err());
+        assert!(tensor.select(2, 0).is_err());
+        assert!(tensor.select(-3, 0).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_index_select() -> MlResult<()> {
+        let tensor = Tensor::<f32>::from_vec((0..12).map(|x| x as f32).collect(), &[3, 4])?;
+        let indices = Tensor::<usize>::from_vec(vec![2, 0, 2], &[3])?;
+
+        let rows = tensor.index_select(0, &*indices)?;
+        assert_eq!(rows.shape(), &[3, 4]);
+        assert_eq!(rows.data(), &[8.0, 9.0, 10.0, 11.0, 0.0, 1.0, 2.0, 3.0, 8.0, 9.0, 10.0, 11.0]);
+
+        let columns = tensor.index_select(-1, &*indices)?;
+        assert_eq!(columns.shape(), &[3, 3]);
+        assert_eq!(columns.data(), &[2.0, 0.0, 2.0, 6.0, 4.0, 6.0, 10.0, 8.0, 10.0]);
+
+        let none = Tensor::<usize>::from_vec(vec![], &[0])?;
+        assert_eq!(tensor.index_select(0, &*none)?.shape(), &[0, 4]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_index_select_invalid() -> MlResult<()> {
+        let tensor = Tensor::<f32>::from_vec((0..12).map(|x| x as f32).collect(), &[3, 4])?;
+        let out_of_range = Tensor::<usize>::from_vec(vec![0, 3], &[2])?;
+        assert!(matches!(
+            tensor.index_select(0, &*out_of_range).err().unwrap(),
+            MlError::TensorError(TensorError::InvalidAxis { axis: 0, .. })
+        ));
+        assert!(tensor.index_select(2, &*out_of_range).is_err());
+
+        let matrix = Tensor::<usize>::from_vec(vec![0, 1], &[1, 2])?;
+        assert!(tensor.index_select(0, &*matrix).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_batches() -> MlResult<()> {
+        let tensor = Tensor::<f32>::from_vec((0..10).map(|x| x as f32).collect(), &[5, 2])?;
+
+        let batches: Vec<_> = tensor.batches(2, false).collect();
+        assert_eq!(batches.len(), 3);
+        assert_eq!(batches[0].shape(), &[2, 2]);
+        assert_eq!(batches[1].data(), &[4.0, 5.0, 6.0, 7.0]);
+        assert_eq!(batches[2].shape(), &[1, 2]);
+        assert_eq!(batches[2].data(), &[8.0, 9.0]);
+
+        let batches: Vec<_> = tensor.batches(2, true).collect();
+        assert_eq!(batches.len(), 2);
+        assert_eq!(batches[1].shape(), &[2, 2]);
+
+        assert_eq!(tensor.batches(0, false).count(), 0);
+        assert_eq!(tensor.batches(8, true).count(), 0);
+        assert_eq!(tensor.batches(8, false).count(), 1);
+        Ok(())
+    }
+
+    #[test]
+    fn test_sequence_mask() -> MlResult<()> {
+        let lengths = Tensor::<usize>::from_vec(vec![2, 4, 0], &[3])?;
+        let mask = sequence_mask(&*lengths, 4)?;
+
+        assert_eq!(mask.shape(), &[3, 4]);
+        assert_eq!(mask.data(), &[
+            1.0, 1.0, 0.0, 0.0,
+            1.0, 1.0, 1.0, 1.0,
+            0.0, 0.0, 0.0, 0.0,
+        ]);
+
+        assert!(sequence_mask(&*lengths, 3).is_err());
+        let nested = Tensor::<usize>::from_vec(vec![1, 2], &[2, 1])?;
+        assert!(sequence_mask(&*nested, 4).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_axis_iter() -> MlResult<()> {
+        let tensor = Tensor::<f32>::from_vec((0..60).map(|x| x as f32).collect(), &[3, 4, 5])?;
+
+        let samples: Vec<_> = tensor.axis_iter(0)?.collect();
+        assert_eq!(samples.len(), 3);
+        assert_eq!(samples[1].shape(), &[4, 5]);
+        assert_eq!(samples[1].data(), &tensor.data()[20..40]);
+        let views: Vec<&dyn TensorBase<f32>> = samples.iter().map(|t| &**t).collect();
+        assert_eq!(stack(&views, 0)?.data(), tensor.data());
+
+        let columns: Vec<_> = tensor.axis_iter(1)?.collect();
+        assert_eq!(columns.len(), 4);
+        assert_eq!(columns[2].shape(), &[3, 5]);
+        assert_eq!(columns[2].get(&[1, 0]), tensor.get(&[1, 2, 0]));
+        let views: Vec<&dyn TensorBase<f32>> = columns.iter().map(|t| &**t).collect();
+        let restacked = stack(&views, 1)?;
+        assert_eq!(restacked.shape(), &[3, 4, 5]);
+        assert_eq!(restacked.data(), tensor.data());
+
+        // the items work with the existing ops
+        assert_eq!(ops!(samples[0], Add, samples[0])?.data()[4], 8.0);
+        assert!(tensor.axis_iter(3).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_axis_chunks_iter_and_rows() -> MlResult<()> {
+        let tensor = Tensor::<f32>::from_vec((0..60).map(|x| x as f32).collect(), &[3, 4, 5])?;
+
+        let chunks: Vec<_> = tensor.axis_chunks_iter(-1, 2)?.collect();
+        assert_eq!(chunks.len(), 3);
+        assert_eq!(chunks[0].shape(), &[3, 4, 2]);
+        assert_eq!(chunks[2].shape(), &[3, 4, 1]);
+        assert_eq!(chunks[2].data()[..2], [4.0, 9.0]);
+        assert!(tensor.axis_chunks_iter(0, 0).is_err());
+
+        let matrix = Tensor::<f32>::new(vec![vec![1.0, 2.0], vec![3.0, 4.0], vec![5.0, 6.0]])?;
+        let rows: Vec<_> = matrix.rows()?.map(|row| row.data().to_vec()).collect();
+        assert_eq!(rows, vec![vec![1.0, 2.0], vec![3.0, 4.0], vec![5.0, 6.0]]);
+        assert!(tensor.rows().is_err());
+
+        let row = Tensor::<f32>::from_vec(vec![1.0, 2.0], &[2])?;
+        let stacked = stack(&[&*row, &*row], -1)?;
+        assert_eq!(stacked.shape(), &[2, 2]);
+        assert_eq!(stacked.data(), &[1.0, 1.0, 2.0, 2.0]);
+        assert!(stack::<f32>(&[], 0).is_err());
+        assert!(stack(&[&*row, &*matrix], 0).is_err());
+        assert!(stack(&[&*row], 2).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_split_with_sizes() -> MlResult<()> {
+        // fused projection of width 2 + 1 + 1 for two tokens
+        let qkv = Tensor::<f32>::from_vec((0..8).map(|x| x as f32).collect(), &[2, 4])?;
+
+        let pieces = qkv.split_with_sizes(&[2, 1, 1], -1)?;
+        assert_eq!(pieces.len(), 3);
+        assert_eq!(pieces[0].shape(), &[2, 2]);
+        assert_eq!(pieces[0].data(), &[0.0, 1.0, 4.0, 5.0]);
+        assert_eq!(pieces[1].data(), &[2.0, 6.0]);
+        assert_eq!(pieces[2].data(), &[3.0, 7.0]);
+
+        let rows = qkv.split_with_sizes(&[0, 2], 0)?;
+        assert_eq!(rows[0].shape(), &[0, 4]);
+        assert_eq!(rows[1].data(), qkv.data());
+
+        assert!(matches!(
+            qkv.split_with_sizes(&[2, 1], 1),
+            Err(MlError::TensorError(TensorError::InvalidShape { .. }))
+        ));
+        assert!(matches!(
+            qkv.split_with_sizes(&[4], 2),
+            Err(MlError::TensorError(TensorError::InvalidAxis { .. }))
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn test_unsqueeze_expand() -> MlResult<()> {
+        let sample = Tensor::<f32>::from_vec(vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0], &[2, 3])?;
+
+        let batch = sample.unsqueeze_expand(0, 2)?;
+        assert_eq!(batch.shape(), &[2, 2, 3]);
+        assert_eq!(&batch.data()[..6], sample.data());
+        assert_eq!(&batch.data()[6..], sample.data());
+
+        let middle = sample.unsqueeze_expand(1, 2)?;
+        assert_eq!(middle.shape(), &[2, 2, 3]);
+        assert_eq!(middle.data(), &[1.0, 2.0, 3.0, 1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 4.0, 5.0, 6.0]);
+
+        let last = sample.unsqueeze_expand(-1, 2)?;
+        assert_eq!(last.shape(), &[2, 3, 2]);
+        assert_eq!(&last.data()[..4], &[1.0, 1.0, 2.0, 2.0]);
+
+        assert_eq!(sample.unsqueeze_expand(0, 0)?.shape(), &[0, 2, 3]);
+        assert!(matches!(
+            sample.unsqueeze_expand(3, 2),
+            Err(MlError::TensorError(TensorError::InvalidAxis { axis: 3, ndim: 3 }))
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn test_concat() -> MlResult<()> {
+        let a = Tensor::<f32>::from_vec(vec![1.0, 2.0, 3.0, 4.0], &[2, 2])?;
+        let b = Tensor::<f32>::from_vec(vec![5.0, 6.0, 7.0, 8.0, 9.0, 10.0], &[2, 3])?;
+
+        let columns = concat(&[&*a, &*b], 1)?;
+        assert_eq!(columns.shape(), &[2, 5]);
+        assert_eq!(columns.data(), &[1.0, 2.0, 5.0, 6.0, 7.0, 3.0, 4.0, 8.0, 9.0, 10.0]);
+        assert_eq!(concat(&[&*a, &*b], -1)?.data(), columns.data());
+
+        let rows = concat(&[&*a, &*a], 0)?;
+        assert_eq!(rows.shape(), &[4, 2]);
+        assert_eq!(rows.data(), &[1.0, 2.0, 3.0, 4.0, 1.0, 2.0, 3.0, 4.0]);
+
+        assert!(matches!(concat(&[&*a, &*b], 0), Err(MlError::TensorError(TensorError::InvalidShape { .. }))));
+        assert!(concat::<f32>(&[], 0).is_err());
+        assert!(concat(&[&*a, &*b], 2).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_masked_fill() -> MlResult<()> {
+        let tensor = Tensor::<f32>::from_vec(vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0], &[2, 3])?;
+        let mask = Tensor::<bool>::from_vec(vec![true, false, false, true, true, false], &[2, 3])?;
+        assert_eq!(tensor.masked_fill(&*mask, 0.0)?.data(), &[0.0, 2.0, 3.0, 0.0, 0.0, 6.0]);
+
+        let column_mask = Tensor::<bool>::from_vec(vec![false, true, false], &[3])?;
+        assert_eq!(tensor.masked_fill(&*column_mask, -1.0)?.data(), &[1.0, -1.0, 3.0, 4.0, -1.0, 6.0]);
+
+        let too_large = Tensor::<bool>::from_vec(vec![true; 12], &[2, 2, 3])?;
+        assert!(tensor.masked_fill(&*too_large, 0.0).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_item() -> MlResult<()> {
+        let scalar = Tensor::<f32>::scalar(2.5);
+        assert_eq!(scalar.shape(), &[] as &[usize]);
+        assert_eq!(scalar.item()?, 2.5);
+        assert_eq!(Tensor::<f32>::from_vec(vec![4.0], &[1, 1])?.item()?, 4.0);
+
+        let tensor = Tensor::<f32>::new(vec![vec![1.0, 7.0], vec![3.0, 2.0]])?;
+        let (max, _) = ops!(tensor, Matmax, None, false)?;
+        assert!(max.shape().is_empty());
+        assert_eq!(max.item()?, 7.0);
+
+        assert!(tensor.item().is_err());
+        assert!(Tensor::<f32>::from_vec(vec![], &[0])?.item().is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_item_shapes() -> MlResult<()> {
+        for shape in [&[][..], &[1], &[1, 1], &[1, 1, 1]] {
+            assert_eq!(Tensor::<f32>::from_vec(vec![-3.5], shape)?.item()?, -3.5);
+        }
+        assert_eq!(Tensor::<usize>::from_vec(vec![9], &[1])?.item()?, 9);
+
+        let tensor = Tensor::<f32>::from_vec(vec![1.0, 2.0], &[1, 2])?;
+        match tensor.item().err().unwrap() {
+            MlError::TensorError(TensorError::InvalidOperation { op, reason }) => {
+                assert_eq!(op, "item");
+                assert!(reason.contains("[1, 2]"), "{}", reason);
+            }
+            e => panic!("unexpected error {}", e),
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_to_scalar_array() -> MlResult<()> {
+        let metrics = Tensor::<f32>::from_vec(vec![0.25, 0.9], &[2])?;
+        let [loss, accuracy] = metrics.to_scalar_array()?;
+        assert_eq!((loss, accuracy), (0.25, 0.9));
+
+        let grid = Tensor::<f32>::from_vec(vec![1.0, 2.0, 3.0, 4.0], &[2, 2])?;
+        assert_eq!(grid.to_scalar_array::<4>()?, [1.0, 2.0, 3.0, 4.0]);
+        assert_eq!(Tensor::<f32>::scalar(5.0).to_scalar_array::<1>()?, [5.0]);
+        assert!(grid.to_scalar_array::<3>().is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_to_nested_vec() -> MlResult<()> {
+        let rows = vec![vec![1.0, 2.0, 3.0], vec![4.0, 5.0, 6.0]];
+        let tensor = Tensor::<f32>::new(rows.clone())?;
+        assert_eq!(tensor.to_vec(), tensor.data());
+        assert_eq!(tensor.to_vec2d()?, rows);
+
+        let transposed = tensor.permute(&[1, 0])?;
+        assert_eq!(transposed.to_vec2d()?, vec![vec![1.0, 4.0], vec![2.0, 5.0], vec![3.0, 6.0]]);
+
+        let cube = Tensor::<usize>::from_fn(&[2, 2, 3], |idx| idx[0] * 100 + idx[1] * 10 + idx[2])?;
+        let nested = cube.to_vec3d()?;
+        assert_eq!(nested[1][0], vec![100, 101, 102]);
+        assert_eq!(nested[0][1][2], 12);
+
+        assert!(tensor.to_vec3d().is_err());
+        assert!(cube.to_vec2d().is_err());
+        assert!(Tensor::<f32>::scalar(1.0).to_vec2d().is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_scatter_add() -> MlResult<()> {
+        // embedding bag: sum rows of src into 3 bags
+        let bags = Tensor::<f32>::from_vec(vec![0.0; 6], &[3, 2])?;
+        let src = Tensor::<f32>::from_vec(vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0], &[4, 2])?;
+        let index = Tensor::<usize>::from_vec(vec![0, 0, 2, 2, 0, 0, 1, 1], &[4, 2])?;
+        let summed = bags.scatter_add(0, &*index, &*src)?;
+        assert_eq!(summed.shape(), &[3, 2]);
+        assert_eq!(summed.data(), &[6.0, 8.0, 7.0, 8.0, 3.0, 4.0]);
+        assert_eq!(bags.data(), &[0.0; 6]);
+
+        let base = Tensor::<usize>::from_vec(vec![1; 6], &[2, 3])?;
+        let counts = Tensor::<usize>::from_vec(vec![1, 1], &[2, 1])?;
+        let columns = Tensor::<usize>::from_vec(vec![2, 2], &[2, 1])?;
+        assert_eq!(base.scatter_add(-1, &*columns, &*counts)?.data(), &[1, 1, 2, 1, 1, 2]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_scatter_add_invalid() -> MlResult<()> {
+        let base = Tensor::<f32>::from_vec(vec![0.0; 6], &[2, 3])?;
+        let src = Tensor::<f32>::from_vec(vec![1.0; 3], &[1, 3])?;
+
+        let out_of_bounds = Tensor::<usize>::from_vec(vec![0, 2, 1], &[1, 3])?;
+        assert!(matches!(
+            base.scatter_add(0, &*out_of_bounds, &*src),
+            Err(MlError::TensorError(TensorError::InvalidOperation { op: "scatter_add", .. }))
+        ));
+        let mismatched = Tensor::<usize>::from_vec(vec![0, 1], &[1, 2])?;
+        assert!(base.scatter_add(0, &*mismatched, &*src).is_err());
+        let too_wide = Tensor::<f32>::from_vec(vec![1.0; 4], &[1, 4])?;
+        let wide_index = Tensor::<usize>::from_vec(vec![0; 4], &[1, 4])?;
+        assert!(base.scatter_add(0, &*wide_index, &*too_wide).is_err());
+        assert!(base.scatter_add(2, &*out_of_bounds, &*src).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_tile_to() -> MlResult<()> {
+        let tensor = Tensor::<f32>::from_vec(vec![1.0, 2.0, 3.0], &[3])?;
+        let tiled = tensor.tile_to(7)?;
+        assert_eq!(tiled.shape(), &[7]);
+        assert_eq!(tiled.data(), &[1.0, 2.0, 3.0, 1.0, 2.0, 3.0, 1.0]);
+        assert_eq!(tensor.tile_to(2)?.data(), &[1.0, 2.0]);
+
+        assert!(tensor.tile_to(0).is_err());
+        assert!(Tensor::<f32>::from_vec(vec![], &[0])?.tile_to(3).is_err());
+        assert!(Tensor::<f32>::new(vec![vec![1.0, 2.0]])?.tile_to(4).is_err());
+        assert!(Tensor::<f32>::scalar(1.0).tile_to(4).is_err());
+        Ok(())
+    }
+}
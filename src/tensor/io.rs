@@ -0,0 +1,267 @@
+use std::fs::File;
+use std::io::{BufReader, BufWriter, Read, Write};
+
+use crate::{MlError, MlResult};
+use crate::tensor::{ArcTensor, FormatError, Tensor, TensorBase};
+
+const TENSOR_MAGIC: &[u8; 4] = b"MITT";
+const STATE_MAGIC: &[u8; 4] = b"MITS";
+const FORMAT_VERSION: u32 = 1;
+const DTYPE_F32: u8 = 0;
+
+impl dyn TensorBase<f32> + '_ {
+    /// Saves the tensor to a file
+    ///
+    /// # Arguments
+    /// * `path` - The file to write
+    pub fn save(&self, path: &str) -> MlResult<()> {
+        let mut writer = BufWriter::new(File::create(path).map_err(io_error)?);
+        self.save_to(&mut writer)?;
+        writer.flush().map_err(io_error)
+    }
+
+    /// Writes the tensor in the native binary format
+    ///
+    /// The header holds the magic bytes `MITT`, the format version as a little-endian
+    /// `u32`, a one-byte dtype tag and the rank and dimensions as little-endian `u64`,
+    /// followed by the data as little-endian `f32`.
+    ///
+    /// # Arguments
+    /// * `writer` - Any destination, such as a file, socket or `Vec<u8>`
+    pub fn save_to(&self, writer: &mut impl Write) -> MlResult<()> {
+        write_header(writer, TENSOR_MAGIC)?;
+        write_tensor(writer, self)
+    }
+}
+
+impl Tensor<f32> {
+    /// Loads a tensor written by `save`
+    ///
+    /// # Arguments
+    /// * `path` - The file to read
+    pub fn load(path: &str) -> MlResult<ArcTensor<f32>> {
+        Self::load_from(&mut BufReader::new(File::open(path).map_err(io_error)?))
+    }
+
+    /// Reads a tensor written by `save_to`
+    ///
+    /// # Arguments
+    /// * `reader` - Any source, such as a file, socket or byte slice
+    ///
+    /// # Returns
+    /// * `Ok(ArcTensor)` on success
+    /// * `Err(MlError::FormatError)` with `BadMagic`, `UnsupportedVersion`, `UnsupportedDtype`
+    ///   or `Truncated` if the input is not a complete tensor record
+    pub fn load_from(reader: &mut impl Read) -> MlResult<ArcTensor<f32>> {
+        read_header(reader, TENSOR_MAGIC)?;
+        read_tensor(reader)
+    }
+}
+
+/// Saves a list of named tensors to a single file
+///
+/// The file holds the magic bytes `MITS`, the format version and the tensor count,
+/// followed by each name and a tensor record in the same layout as `save_to`.
+///
+/// # Arguments
+/// * `tensors` - The `(name, tensor)` pairs to save
+/// * `path` - The file to write
+pub fn save_state(tensors: &[(&str, &dyn TensorBase<f32>)], path: &str) -> MlResult<()> {
+    let mut writer = BufWriter::new(File::create(path).map_err(io_error)?);
+    write_header(&mut writer, STATE_MAGIC)?;
+    write_u64(&mut writer, tensors.len() as u64)?;
+    for (name, tensor) in tensors {
+        write_u64(&mut writer, name.len() as u64)?;
+        writer.write_all(name.as_bytes()).map_err(io_error)?;
+        write_tensor(&mut writer, *tensor)?;
+    }
+    writer.flush().map_err(io_error)
+}
+
+/// Loads named tensors written by `save_state`, in the order they were saved
+///
+/// # Arguments
+/// * `path` - The file to read
+pub fn load_state(path: &str) -> MlResult<Vec<(String, ArcTensor<f32>)>> {
+    let mut reader = BufReader::new(File::open(path).map_err(io_error)?);
+    read_header(&mut reader, STATE_MAGIC)?;
+    let count = read_u64(&mut reader)?;
+
+    let mut tensors = Vec::new();
+    for _ in 0..count {
+        let len = read_u64(&mut reader)?;
+        let name = read_bytes(&mut reader, len)?;
+        let name = String::from_utf8(name).map_err(|e| MlError::StringError(format!("invalid tensor name: {}", e)))?;
+        tensors.push((name, read_tensor(&mut reader)?));
+    }
+    Ok(tensors)
+}
+
+fn write_header(writer: &mut impl Write, magic: &[u8; 4]) -> MlResult<()> {
+    writer.write_all(magic).map_err(io_error)?;
+    writer.write_all(&FORMAT_VERSION.to_le_bytes()).map_err(io_error)
+}
+
+fn read_header(reader: &mut impl Read, expected: &[u8; 4]) -> MlResult<()> {
+    let mut magic = [0u8; 4];
+    reader.read_exact(&mut magic).map_err(io_error)?;
+    if &magic != expected {
+        return Err(MlError::FormatError(FormatError::BadMagic { expected: *expected, got: magic }));
+    }
+
+    let mut version = [0u8; 4];
+    reader.read_exact(&mut version).map_err(io_error)?;
+    match u32::from_le_bytes(version) {
+        FORMAT_VERSION => Ok(()),
+        version => Err(MlError::FormatError(FormatError::UnsupportedVersion(version))),
+    }
+}
+
+fn write_tensor(writer: &mut impl Write, tensor: &dyn TensorBase<f32>) -> MlResult<()> {
+    writer.write_all(&[DTYPE_F32]).map_err(io_error)?;
+    write_u64(writer, tensor.shape().len() as u64)?;
+    for &dim in tensor.shape() {
+        write_u64(writer, dim as u64)?;
+    }
+    for &value in tensor.data() {
+        writer.write_all(&value.to_le_bytes()).map_err(io_error)?;
+    }
+    Ok(())
+}
+
+fn read_tensor(reader: &mut impl Read) -> MlResult<ArcTensor<f32>> {
+    let mut dtype = [0u8; 1];
+    reader.read_exact(&mut dtype).map_err(io_error)?;
+    if dtype[0] != DTYPE_F32 {
+        return Err(MlError::FormatError(FormatError::UnsupportedDtype(dtype[0])));
+    }
+
+    let ndim = read_u64(reader)?;
+    let shape = (0..ndim).map(|_| read_u64(reader).map(|d| d as usize)).collect::<MlResult<Vec<_>>>()?;
+    // a corrupted shape can claim more data than exists, which is reported as truncation
+    let len = shape.iter().try_fold(4u64, |acc, &d| acc.checked_mul(d as u64))
+        .ok_or(MlError::FormatError(FormatError::Truncated))?;
+    let bytes = read_bytes(reader, len)?;
+    let data = bytes.chunks_exact(4).map(|b| f32::from_le_bytes([b[0], b[1], b[2], b[3]])).collect();
+    Tensor::<f32>::from_vec(data, &shape)
+}
+
+/// Reads exactly `len` bytes without trusting `len` for the allocation size
+fn read_bytes(reader: &mut impl Read, len: u64) -> MlResult<Vec<u8>> {
+    let mut bytes = Vec::new();
+    reader.take(len).read_to_end(&mut bytes).map_err(io_error)?;
+    if (bytes.len() as u64) < len {
+        return Err(MlError::FormatError(FormatError::Truncated));
+    }
+    Ok(bytes)
+}
+
+fn write_u64(writer: &mut impl Write, value: u64) -> MlResult<()> {
+    writer.write_all(&value.to_le_bytes()).map_err(io_error)
+}
+
+fn read_u64(reader: &mut impl Read) -> MlResult<u64> {
+    let mut bytes = [0u8; 8];
+    reader.read_exact(&mut bytes).map_err(io_error)?;
+    Ok(u64::from_le_bytes(bytes))
+}
+
+fn io_error(e: std::io::Error) -> MlError {
+    match e.kind() {
+        std::io::ErrorKind::UnexpectedEof => MlError::FormatError(FormatError::Truncated),
+        _ => MlError::StringError(format!("I/O error: {}", e)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{MlError, MlResult};
+    use crate::tensor::*;
+    use crate::testing::temp_path;
+
+    #[test]
+    fn test_save_load_tensor() -> MlResult<()> {
+        let path = temp_path("tensor.bin");
+        let tensor = Tensor::<f32>::from_vec(vec![1.0, -2.5, 3.0, 4.0, 5.0, 6.0], &[2, 3])?;
+        tensor.save(&path)?;
+
+        let loaded = Tensor::<f32>::load(&path)?;
+        assert_eq!(loaded.shape(), tensor.shape());
+        assert_eq!(loaded.data(), tensor.data());
+        assert!(load_state(&path).is_err());
+        std::fs::remove_file(&path).unwrap();
+        Ok(())
+    }
+
+    #[test]
+    fn test_save_load_state() -> MlResult<()> {
+        let path = temp_path("state.bin");
+        let weight = Tensor::<f32>::from_vec(vec![1.0, 2.0, 3.0, 4.0], &[2, 2])?;
+        let bias = Tensor::<f32>::from_vec(vec![0.5], &[1])?;
+        let empty = Tensor::<f32>::from_vec(vec![], &[0, 3])?;
+        save_state(&[("layer.weight", &*weight), ("layer.bias", &*bias), ("empty", &*empty)], &path)?;
+
+        let loaded = load_state(&path)?;
+        assert_eq!(loaded.len(), 3);
+        assert_eq!(loaded[0].0, "layer.weight");
+        assert_eq!(loaded[0].1.data(), weight.data());
+        assert_eq!(loaded[1].0, "layer.bias");
+        assert_eq!(loaded[1].1.shape(), &[1]);
+        assert_eq!(loaded[2].1.shape(), &[0, 3]);
+        std::fs::remove_file(&path).unwrap();
+
+        assert!(load_state(&temp_path("missing.bin")).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_save_load_in_memory() -> MlResult<()> {
+        let tensor = Tensor::<f32>::from_vec(vec![1.0, -2.5, 3.0, 4.0, 5.0, 6.0], &[3, 1, 2])?;
+        let mut buffer = Vec::new();
+        tensor.save_to(&mut buffer)?;
+        assert_eq!(&buffer[..8], b"MITT\x01\0\0\0");
+
+        let loaded = Tensor::<f32>::load_from(&mut buffer.as_slice())?;
+        assert_eq!(loaded.shape(), &[3, 1, 2]);
+        assert_eq!(loaded.data(), tensor.data());
+
+        let scalar = Tensor::<f32>::scalar(7.0);
+        let mut buffer = Vec::new();
+        scalar.save_to(&mut buffer)?;
+        assert!(Tensor::<f32>::load_from(&mut buffer.as_slice())?.shape().is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn test_load_corrupt_input() -> MlResult<()> {
+        let tensor = Tensor::<f32>::from_vec(vec![1.0, 2.0, 3.0, 4.0], &[2, 2])?;
+        let mut buffer = Vec::new();
+        tensor.save_to(&mut buffer)?;
+        let load = |bytes: &[u8]| match Tensor::<f32>::load_from(&mut &bytes[..]) {
+            Err(MlError::FormatError(e)) => Some(e),
+            _ => None,
+        };
+
+        assert_eq!(load(&buffer[..buffer.len() - 1]), Some(FormatError::Truncated));
+        assert_eq!(load(&buffer[..6]), Some(FormatError::Truncated));
+
+        let mut wrong_length = buffer.clone();
+        wrong_length[17..25].copy_from_slice(&1000u64.to_le_bytes());
+        assert_eq!(load(&wrong_length), Some(FormatError::Truncated));
+        wrong_length[17..25].copy_from_slice(&u64::MAX.to_le_bytes());
+        assert_eq!(load(&wrong_length), Some(FormatError::Truncated));
+
+        let mut wrong_magic = buffer.clone();
+        wrong_magic[..4].copy_from_slice(b"MITS");
+        assert_eq!(load(&wrong_magic), Some(FormatError::BadMagic { expected: *b"MITT", got: *b"MITS" }));
+
+        let mut future_version = buffer.clone();
+        future_version[4] = 2;
+        assert_eq!(load(&future_version), Some(FormatError::UnsupportedVersion(2)));
+
+        let mut wrong_dtype = buffer;
+        wrong_dtype[8] = 9;
+        assert_eq!(load(&wrong_dtype), Some(FormatError::UnsupportedDtype(9)));
+        Ok(())
+    }
+}
@@ -0,0 +1,684 @@
+use std::fmt::Debug;
+
+use crate::{MlError, MlResult};
+use crate::tensor::{ArcTensor, Rng, Tensor, TensorBase, TensorError};
+
+/// Computes `bias + mat @ vec` in a single pass
+///
+/// # Arguments
+/// * `bias` - The bias vector with shape `[m]`
+/// * `mat` - The matrix with shape `[m, n]`
+/// * `vec` - The vector with shape `[n]`
+///
+/// # Returns
+/// A new tensor with shape `[m]` containing the affine transform of `vec`
+///
+/// # Errors
+/// * `MatrixMultiplicationError` if `mat` is not 2-D, `vec` is not 1-D or their inner dimensions differ
+/// * `InvalidShape` if `bias` is not `[m]`
+pub fn addmv(bias: &dyn TensorBase<f32>, mat: &dyn TensorBase<f32>, vec: &dyn TensorBase<f32>) -> MlResult<ArcTensor<f32>> {
+    if mat.shape().len() != 2 || vec.shape().len() != 1 || mat.shape()[1] != vec.shape()[0] {
+        return Err(MlError::TensorError(TensorError::MatrixMultiplicationError {
+            left_shape: mat.shape().to_vec(),
+            right_shape: vec.shape().to_vec(),
+        }));
+    }
+
+    let (m, n) = (mat.shape()[0], mat.shape()[1]);
+    if bias.shape() != [m] {
+        return Err(MlError::TensorError(TensorError::InvalidShape {
+            expected: vec![m],
+            got: bias.shape().to_vec(),
+        }));
+    }
+
+    let data = (0..m)
+        .map(|i| {
+            let row = &mat.data()[i * n..(i + 1) * n];
+            bias.data()[i] + row.iter().zip(vec.data()).map(|(&x, &y)| x * y).sum::<f32>()
+        })
+        .collect();
+
+    Tensor::<f32>::from_vec(data, &[m])
+}
+
+/// Computes `out = beta * out + a @ b` in place, like the accumulation of BLAS `gemm`
+///
+/// Both operands are either 2-D (`[m, k] @ [k, n]`) or share identical leading batch
+/// dimensions (`[.., m, k] @ [.., k, n]`). No result tensor is allocated. With `beta = 0`
+/// the previous contents of `out` are ignored, even if they are NaN.
+///
+/// # Arguments
+/// * `out` - The accumulator, whose shape must equal the shape of `a @ b`
+/// * `a` - The left operand
+/// * `b` - The right operand
+/// * `beta` - The scale applied to `out` before adding the product
+///
+/// # Errors
+/// * `MatrixMultiplicationError` if the operands are not at least 2-D, their inner dimensions
+///   differ or their batch dimensions differ
+/// * `InvalidShape` if `out` does not have the shape of the product
+/// * `InvalidOperation` if `out` is part of an autograd graph
+pub fn matmul_into(out: &mut Tensor<f32>, a: &dyn TensorBase<f32>, b: &dyn TensorBase<f32>, beta: f32) -> MlResult<()> {
+    out.check_in_place("matmul_into")?;
+    let (a_shape, b_shape) = (a.shape(), b.shape());
+    let ndim = a_shape.len();
+    if ndim < 2 || b_shape.len() != ndim || a_shape[..ndim - 2] != b_shape[..ndim - 2] || a_shape[ndim - 1] != b_shape[ndim - 2] {
+        return Err(MlError::TensorError(TensorError::MatrixMultiplicationError {
+            left_shape: a_shape.to_vec(),
+            right_shape: b_shape.to_vec(),
+        }));
+    }
+
+    let (m, k, n) = (a_shape[ndim - 2], a_shape[ndim - 1], b_shape[ndim - 1]);
+    let mut expected = a_shape[..ndim - 2].to_vec();
+    expected.extend([m, n]);
+    if out.shape() != expected {
+        return Err(MlError::TensorError(TensorError::InvalidShape {
+            expected,
+            got: out.shape().to_vec(),
+        }));
+    }
+
+    let out = out.elements_mut();
+    match beta {
+        0.0 => out.fill(0.0),
+        1.0 => {}
+        _ => out.iter_mut().for_each(|x| *x *= beta),
+    }
+    for batch in 0..out.len() / (m * n).max(1) {
+        let a = &a.data()[batch * m * k..(batch + 1) * m * k];
+        let b = &b.data()[batch * k * n..(batch + 1) * k * n];
+        let out = &mut out[batch * m * n..(batch + 1) * m * n];
+        for i in 0..m {
+            for p in 0..k {
+                let a_ip = a[i * k + p];
+                for (o, &b_pj) in out[i * n..(i + 1) * n].iter_mut().zip(&b[p * n..(p + 1) * n]) {
+                    *o += a_ip * b_pj;
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+/// The longest inner dimension for which [`matmul_i8`] cannot overflow `i32`
+///
+/// Each product of two `i8` values is at most `128 * 128 = 16384` in magnitude, so
+/// `131071` of them always fit in an `i32` accumulator.
+pub const MATMUL_I8_MAX_INNER_DIM: usize = i32::MAX as usize / (128 * 128);
+
+/// Multiplies two `i8` matrices, accumulating into `i32`
+///
+/// This is the kernel of quantized linear layers: weights and activations are
+/// stored as `i8` and the exact integer products are summed without rounding.
+///
+/// # Arguments
+/// * `a` - The left matrix with shape `[m, k]`
+/// * `b` - The right matrix with shape `[k, n]`
+///
+/// # Returns
+/// A new `i32` tensor with shape `[m, n]`
+///
+/// # Errors
+/// * `MatrixMultiplicationError` if either operand is not 2-D or the inner dimensions differ
+/// * `InvalidOperation` if `k` exceeds [`MATMUL_I8_MAX_INNER_DIM`], where the sum could overflow
+pub fn matmul_i8(a: &dyn TensorBase<i8>, b: &dyn TensorBase<i8>) -> MlResult<ArcTensor<i32>> {
+    if a.shape().len() != 2 || b.shape().len() != 2 || a.shape()[1] != b.shape()[0] {
+        return Err(MlError::TensorError(TensorError::MatrixMultiplicationError {
+            left_shape: a.shape().to_vec(),
+            right_shape: b.shape().to_vec(),
+        }));
+    }
+
+    let (m, k, n) = (a.shape()[0], a.shape()[1], b.shape()[1]);
+    if k > MATMUL_I8_MAX_INNER_DIM {
+        return Err(MlError::TensorError(TensorError::InvalidOperation {
+            op: "matmul_i8",
+            reason: format!("inner dimension {} exceeds {}, the i32 accumulator could overflow", k, MATMUL_I8_MAX_INNER_DIM),
+        }));
+    }
+
+    let mut data = vec![0i32; m * n];
+    for i in 0..m {
+        let out = &mut data[i * n..(i + 1) * n];
+        for (l, &x) in a.data()[i * k..(i + 1) * k].iter().enumerate() {
+            let row = &b.data()[l * n..(l + 1) * n];
+            out.iter_mut().zip(row).for_each(|(acc, &y)| *acc += x as i32 * y as i32);
+        }
+    }
+
+    Tensor::<i32>::from_vec(data, &[m, n])
+}
+
+impl dyn TensorBase<f32> + '_ {
+    /// Computes the determinant of each matrix in the trailing two dimensions
+    ///
+    /// A `[n, n]` tensor gives a 0-dimensional result and a `[..., n, n]` tensor gives
+    /// one determinant per matrix with shape `[...]`. Uses LU decomposition with partial
+    /// pivoting, accumulated in `f64`.
+    ///
+    /// # Returns
+    /// * `Ok(ArcTensor)` with the batch shape
+    /// * `Err(MlError::TensorError)` with `InvalidOperation` if the rank is less than 2 or
+    ///   the last two dimensions differ
+    pub fn det(&self) -> MlResult<ArcTensor<f32>> {
+        let (batch_shape, n) = square_batch(self.shape(), "det")?;
+        let data = (0..batch_shape.iter().product())
+            .map(|b| {
+                let mut lu: Vec<f64> = self.data()[b * n * n..(b + 1) * n * n].iter().map(|&x| x as f64).collect();
+                lu_det(&mut lu, n) as f32
+            })
+            .collect();
+        Tensor::<f32>::from_vec(data, batch_shape)
+    }
+
+    /// Computes the inverse of each matrix in the trailing two dimensions
+    ///
+    /// Uses Gauss-Jordan elimination with partial pivoting, accumulated in `f64`.
+    ///
+    /// # Returns
+    /// * `Ok(ArcTensor)` with the same `[..., n, n]` shape as `self`
+    /// * `Err(MlError::TensorError)` with `InvalidOperation` if the rank is less than 2, the
+    ///   last two dimensions differ, or a matrix is singular
+    pub fn inverse(&self) -> MlResult<ArcTensor<f32>> {
+        let (batch_shape, n) = square_batch(self.shape(), "inverse")?;
+        let mut data = Vec::with_capacity(self.data().len());
+        for b in 0..batch_shape.iter().product() {
+            let matrix: Vec<f64> = self.data()[b * n * n..(b + 1) * n * n].iter().map(|&x| x as f64).collect();
+            let inverse = gauss_jordan_inverse(matrix, n).ok_or_else(|| MlError::TensorError(TensorError::InvalidOperation {
+                op: "inverse",
+                reason: format!("matrix {} of the batch is singular", b),
+            }))?;
+            data.extend(inverse.into_iter().map(|x| x as f32));
+        }
+        Tensor::<f32>::from_vec(data, self.shape())
+    }
+
+    /// Computes the Frobenius norm, the square root of the sum of squares of all elements
+    ///
+    /// # Returns
+    /// * `Ok(f32)` with the norm, accumulated in `f64`; an empty tensor has norm 0
+    pub fn frobenius_norm(&self) -> MlResult<f32> {
+        Ok(self.data().iter().map(|&x| (x as f64) * (x as f64)).sum::<f64>().sqrt() as f32)
+    }
+
+    /// Estimates the spectral norm, the largest singular value, of a 2-D tensor
+    ///
+    /// Runs `iters` steps of power iteration on `AᵀA` from a fixed pseudo-random start
+    /// vector, so the result is deterministic. The estimate approaches the true norm from
+    /// below, more slowly when the two largest singular values are close.
+    ///
+    /// # Arguments
+    /// * `iters` - The number of power iterations, at least 1
+    ///
+    /// # Returns
+    /// * `Ok(f32)` with the estimate
+    /// * `Err(MlError::TensorError)` with `InvalidOperation` if the tensor is not 2-D or
+    ///   `iters` is 0
+    pub fn spectral_norm(&self, iters: usize) -> MlResult<f32> {
+        let &[rows, cols] = self.shape() else {
+            return Err(MlError::TensorError(TensorError::InvalidOperation {
+                op: "spectral_norm",
+                reason: format!("expected a 2-D tensor, got shape {:?}", self.shape()),
+            }));
+        };
+        if iters == 0 {
+            return Err(MlError::TensorError(TensorError::InvalidOperation {
+                op: "spectral_norm",
+                reason: "iters must be greater than 0".to_string(),
+            }));
+        }
+
+        let a: Vec<f64> = self.data().iter().map(|&x| x as f64).collect();
+        let mut rng = Rng::new(0);
+        let mut v: Vec<f64> = (0..cols).map(|_| rng.normal(0.0, 1.0) as f64).collect();
+        let mut sigma = 0.0;
+        for _ in 0..iters {
+            if normalize(&mut v) == 0.0 {
+                return Ok(0.0);
+            }
+            let mut u: Vec<f64> = (0..rows).map(|i| (0..cols).map(|j| a[i * cols + j] * v[j]).sum()).collect();
+            if normalize(&mut u) == 0.0 {
+                return Ok(0.0);
+            }
+            v = (0..cols).map(|j| (0..rows).map(|i| a[i * cols + j] * u[i]).sum()).collect();
+            sigma = v.iter().map(|x| x * x).sum::<f64>().sqrt();
+        }
+        Ok(sigma as f32)
+    }
+
+    /// Expands a `[n, d]` feature matrix into all monomials of the features up to `degree`
+    ///
+    /// Columns follow scikit-learn's `PolynomialFeatures`: the optional bias column of
+    /// ones, then the monomials grouped by degree, each group in lexicographic order of
+    /// the feature indices (`x0, x1, x0², x0·x1, x1²` for `d = 2`, `degree = 2`).
+    ///
+    /// # Arguments
+    /// * `degree` - The highest total degree, at least 1
+    /// * `include_bias` - Whether to prepend the degree-0 column of ones
+    ///
+    /// # Returns
+    /// * `Ok(ArcTensor)` with shape `[n, C(d + degree, degree)]`, one column fewer without
+    ///   the bias
+    /// * `Err(MlError::TensorError)` with `InvalidOperation` if the tensor is not 2-D or
+    ///   `degree` is 0
+    pub fn polynomial_features(&self, degree: usize, include_bias: bool) -> MlResult<ArcTensor<f32>> {
+        let &[rows, cols] = self.shape() else {
+            return Err(MlError::TensorError(TensorError::InvalidOperation {
+                op: "polynomial_features",
+                reason: format!("expected a 2-D tensor, got shape {:?}", self.shape()),
+            }));
+        };
+        if degree == 0 {
+            return Err(MlError::TensorError(TensorError::InvalidOperation {
+                op: "polynomial_features",
+                reason: "degree must be greater than 0".to_string(),
+            }));
+        }
+
+        let mut monomials: Vec<Vec<usize>> = if include_bias { vec![vec![]] } else { vec![] };
+        let mut previous: Vec<Vec<usize>> = vec![vec![]];
+        for _ in 0..degree {
+            previous = previous
+                .iter()
+                .flat_map(|term| {
+                    let first = term.last().copied().unwrap_or(0);
+                    (first..cols).map(move |j| term.iter().copied().chain([j]).collect())
+                })
+                .collect();
+            monomials.extend(previous.iter().cloned());
+        }
+        let width = multiset_count(cols + 1, degree) - usize::from(!include_bias);
+        debug_assert_eq!(monomials.len(), width);
+
+        let mut data = Vec::with_capacity(rows * width);
+        for i in 0..rows {
+            let row = &self.data()[i * cols..(i + 1) * cols];
+            data.extend(monomials.iter().map(|term| term.iter().map(|&j| row[j]).product::<f32>()));
+        }
+        Tensor::<f32>::from_vec(data, &[rows, width])
+    }
+}
+
+/// Counts the multisets of size `k` drawn from `n` items, `C(n + k - 1, k)`
+fn multiset_count(n: usize, k: usize) -> usize {
+    if n == 0 {
+        return usize::from(k == 0);
+    }
+    (1..=k).fold(1, |count, i| count * (n + i - 1) / i)
+}
+
+/// Scales `v` to unit length and returns its original length, leaving a zero vector as is
+fn normalize(v: &mut [f64]) -> f64 {
+    let norm = v.iter().map(|x| x * x).sum::<f64>().sqrt();
+    if norm > 0.0 {
+        v.iter_mut().for_each(|x| *x /= norm);
+    }
+    norm
+}
+
+/// Splits `[..., n, n]` into the batch shape and `n`
+fn square_batch<'s>(shape: &'s [usize], op: &'static str) -> MlResult<(&'s [usize], usize)> {
+    match shape {
+        [batch @ .., rows, cols] if rows == cols => Ok((batch, *rows)),
+        _ => Err(MlError::TensorError(TensorError::InvalidOperation {
+            op,
+            reason: format!("expected square matrices in the last two dimensions, got shape {:?}", shape),
+        })),
+    }
+}
+
+/// Computes the determinant of a row-major `n x n` matrix, overwriting it with its LU factors
+fn lu_det(lu: &mut [f64], n: usize) -> f64 {
+    let mut det = 1.0;
+    for col in 0..n {
+        let pivot = (col..n).max_by(|&a, &b| lu[a * n + col].abs().total_cmp(&lu[b * n + col].abs())).unwrap();
+        if lu[pivot * n + col] == 0.0 {
+            return 0.0;
+        }
+        if pivot != col {
+            (0..n).for_each(|k| lu.swap(col * n + k, pivot * n + k));
+            det = -det;
+        }
+        let p = lu[col * n + col];
+        det *= p;
+        for row in col + 1..n {
+            let factor = lu[row * n + col] / p;
+            for k in col..n {
+                lu[row * n + k] -= factor * lu[col * n + k];
+            }
+        }
+    }
+    det
+}
+
+/// Inverts a row-major `n x n` matrix, or returns `None` if it is singular
+fn gauss_jordan_inverse(mut matrix: Vec<f64>, n: usize) -> Option<Vec<f64>> {
+    let mut inverse = vec![0.0; n * n];
+    (0..n).for_each(|i| inverse[i * n + i] = 1.0);
+    for col in 0..n {
+        let pivot = (col..n).max_by(|&a, &b| matrix[a * n + col].abs().total_cmp(&matrix[b * n + col].abs()))?;
+        if matrix[pivot * n + col] == 0.0 {
+            return None;
+        }
+        for k in 0..n {
+            matrix.swap(col * n + k, pivot * n + k);
+            inverse.swap(col * n + k, pivot * n + k);
+        }
+        let p = matrix[col * n + col];
+        for k in 0..n {
+            matrix[col * n + k] /= p;
+            inverse[col * n + k] /= p;
+        }
+        for row in (0..n).filter(|&row| row != col) {
+            let factor = matrix[row * n + col];
+            for k in 0..n {
+                matrix[row * n + k] -= factor * matrix[col * n + k];
+                inverse[row * n + k] -= factor * inverse[col * n + k];
+            }
+        }
+    }
+    Some(inverse)
+}
+
+impl<T: Debug + Clone + 'static> dyn TensorBase<T> + '_ {
+    /// Transposes the last two dimensions, keeping any batch dimensions intact
+    ///
+    /// A `[B, T, D]` tensor becomes `[B, D, T]`.
+    ///
+    /// # Returns
+    /// * `Ok(ArcTensor)` with the same rank as the input
+    /// * `Err(MlError::TensorError)` with `InvalidOperation` if the rank is less than 2
+    #[allow(non_snake_case)]
+    pub fn mT(&self) -> MlResult<ArcTensor<T>> {
+        let ndim = self.shape().len();
+        if ndim < 2 {
+            return Err(MlError::TensorError(TensorError::InvalidOperation {
+                op: "mT",
+                reason: format!("expected a tensor with at least 2 dimensions, got shape {:?}", self.shape()),
+            }));
+        }
+
+        let (rows, cols) = (self.shape()[ndim - 2], self.shape()[ndim - 1]);
+        let mut data = Vec::with_capacity(self.data().len());
+        for matrix in self.data().chunks((rows * cols).max(1)) {
+            for j in 0..cols {
+                data.extend((0..rows).map(|i| matrix[i * cols + j].clone()));
+            }
+        }
+
+        let mut shape = self.shape().to_vec();
+        shape.swap(ndim - 2, ndim - 1);
+        Tensor::<T>::from_vec(data, &shape)
+    }
+
+    /// Reorders the dimensions of the tensor
+    ///
+    /// Dimension `i` of the result is dimension `dims[i]` of the input, so `[1, 2, 0]`
+    /// turns a `[C, H, W]` tensor into `[H, W, C]`.
+    ///
+    /// # Arguments
+    /// * `dims` - A permutation of `0..rank`
+    ///
+    /// # Returns
+    /// * `Ok(ArcTensor)` with the permuted shape
+    /// * `Err(MlError::TensorError)` with `InvalidOperation` if `dims` is not a permutation
+    pub fn permute(&self, dims: &[usize]) -> MlResult<ArcTensor<T>> {
+        let ndim = self.shape().len();
+        let mut seen = vec![false; ndim];
+        if dims.len() != ndim || !dims.iter().all(|&d| d < ndim && !std::mem::replace(&mut seen[d], true)) {
+            return Err(MlError::TensorError(TensorError::InvalidOperation {
+                op: "permute",
+                reason: format!("{:?} is not a permutation of the dimensions of shape {:?}", dims, self.shape()),
+            }));
+        }
+
+        let shape: Vec<usize> = dims.iter().map(|&d| self.shape()[d]).collect();
+        let mut strides = vec![1; ndim];
+        for i in (0..ndim.saturating_sub(1)).rev() {
+            strides[i] = strides[i + 1] * self.shape()[i + 1];
+        }
+        let data = (0..self.data().len()).map(|idx| {
+            let (mut remaining, mut offset) = (idx, 0);
+            for (&dim, &size) in dims.iter().zip(&shape).rev() {
+                offset += (remaining % size) * strides[dim];
+                remaining /= size;
+            }
+            self.data()[offset].clone()
+        }).collect();
+        Tensor::<T>::from_vec(data, &shape)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::ops::Deref;
+
+    use crate::{ops, MlError, MlResult};
+    use crate::tensor::*;
+
+    #[test]
+    fn test_addmv() -> MlResult<()> {
+        let bias = Tensor::<f32>::from_vec(vec![1.0, -1.0], &[2])?;
+        let mat = Tensor::<f32>::from_vec(vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0], &[2, 3])?;
+        let vec = Tensor::<f32>::from_vec(vec![7.0, 8.0, 9.0], &[3])?;
+        let result = addmv(&*bias, &*mat, &*vec)?;
+
+        assert_eq!(result.shape(), &[2]);
+        assert_eq!(result.data(), &[51.0, 121.0]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_addmv_invalid_shapes() -> MlResult<()> {
+        let bias = Tensor::<f32>::from_vec(vec![1.0, -1.0], &[2])?;
+        let mat = Tensor::<f32>::from_vec(vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0], &[2, 3])?;
+        let short = Tensor::<f32>::from_vec(vec![7.0, 8.0], &[2])?;
+        let vec = Tensor::<f32>::from_vec(vec![7.0, 8.0, 9.0], &[3])?;
+
+        assert!(matches!(
+            addmv(&*bias, &*mat, &*short),
+            Err(MlError::TensorError(TensorError::MatrixMultiplicationError { .. }))
+        ));
+        assert!(matches!(
+            addmv(&*vec, &*mat, &*vec),
+            Err(MlError::TensorError(TensorError::InvalidShape { .. }))
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn test_matmul_into() -> MlResult<()> {
+        let a = Tensor::<f32>::from_vec(vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0], &[2, 3])?;
+        let b = Tensor::<f32>::from_vec(vec![7.0, 8.0, 9.0, 10.0, 11.0, 12.0], &[3, 2])?;
+        let product = ops!(a, Matmul, b)?;
+
+        let mut out = Tensor::<f32>::from_vec_owned(vec![f32::NAN; 4], &[2, 2])?;
+        matmul_into(&mut out, &*a, &*b, 0.0)?;
+        assert_eq!(out.data(), product.data());
+
+        // accumulating twice doubles the product, then beta scales the running sum
+        matmul_into(&mut out, &*a, &*b, 1.0)?;
+        assert_eq!(out.data(), product.data().iter().map(|x| 2.0 * x).collect::<Vec<_>>());
+        matmul_into(&mut out, &*a, &*b, 0.5)?;
+        assert_eq!(out.data(), product.data().iter().map(|x| 2.0 * x).collect::<Vec<_>>());
+
+        let batched_a = Tensor::<f32>::from_vec((0..12).map(|x| x as f32).collect(), &[2, 2, 3])?;
+        let batched_b = Tensor::<f32>::from_vec((0..12).map(|x| x as f32).collect(), &[2, 3, 2])?;
+        let mut out = Tensor::<f32>::from_vec_owned(vec![0.0; 8], &[2, 2, 2])?;
+        matmul_into(&mut out, &*batched_a, &*batched_b, 0.0)?;
+        assert_eq!(out.data(), ops!(batched_a, Matmul, batched_b)?.data());
+        Ok(())
+    }
+
+    #[test]
+    fn test_matmul_into_invalid_shapes() -> MlResult<()> {
+        let a = Tensor::<f32>::from_vec(vec![1.0; 6], &[2, 3])?;
+        let b = Tensor::<f32>::from_vec(vec![1.0; 6], &[3, 2])?;
+        let mut wrong_out = Tensor::<f32>::from_vec_owned(vec![0.0; 6], &[2, 3])?;
+        assert!(matches!(
+            matmul_into(&mut wrong_out, &*a, &*b, 1.0),
+            Err(MlError::TensorError(TensorError::InvalidShape { .. }))
+        ));
+        assert_eq!(wrong_out.data(), &[0.0; 6]);
+
+        let mut out = Tensor::<f32>::from_vec_owned(vec![0.0; 4], &[2, 2])?;
+        assert!(matches!(
+            matmul_into(&mut out, &*a, &*a, 1.0),
+            Err(MlError::TensorError(TensorError::MatrixMultiplicationError { .. }))
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn test_mt() -> MlResult<()> {
+        let matrix = Tensor::<f32>::from_vec(vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0], &[2, 3])?;
+        let transposed = matrix.mT()?;
+        assert_eq!(transposed.shape(), &[3, 2]);
+        assert_eq!(transposed.data(), &[1.0, 4.0, 2.0, 5.0, 3.0, 6.0]);
+
+        let batched = Tensor::<usize>::from_vec((0..12).collect(), &[2, 3, 2])?;
+        let transposed = batched.mT()?;
+        assert_eq!(transposed.shape(), &[2, 2, 3]);
+        assert_eq!(transposed.data(), &[0, 2, 4, 1, 3, 5, 6, 8, 10, 7, 9, 11]);
+        assert_eq!(transposed.mT()?.data(), batched.data());
+
+        let vector = Tensor::<f32>::from_vec(vec![1.0, 2.0], &[2])?;
+        assert!(vector.mT().is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_permute() -> MlResult<()> {
+        let tensor = Tensor::<usize>::from_vec((0..24).collect(), &[2, 3, 4])?;
+        let permuted = tensor.permute(&[2, 0, 1])?;
+        assert_eq!(permuted.shape(), &[4, 2, 3]);
+        for (i, j, k) in (0..2).flat_map(|i| (0..3).flat_map(move |j| (0..4).map(move |k| (i, j, k)))) {
+            assert_eq!(permuted.data()[permuted.index(&[k, i, j]).unwrap()], tensor.data()[tensor.index(&[i, j, k]).unwrap()]);
+        }
+        assert_eq!(permuted.permute(&[1, 2, 0])?.data(), tensor.data());
+
+        let matrix = Tensor::<f32>::from_vec(vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0], &[2, 3])?;
+        assert_eq!(matrix.permute(&[1, 0])?.data(), matrix.mT()?.data());
+
+        assert!(tensor.permute(&[0, 1]).is_err());
+        assert!(tensor.permute(&[0, 1, 1]).is_err());
+        assert!(tensor.permute(&[0, 1, 3]).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_det() -> MlResult<()> {
+        let matrix = Tensor::<f32>::new(vec![vec![0.0, 2.0, 1.0], vec![1.0, 1.0, 0.0], vec![3.0, 0.0, 2.0]])?;
+        let det = matrix.det()?;
+        assert_eq!(det.shape(), &[] as &[usize]);
+        assert!((det.data()[0] + 7.0).abs() < 1e-5);
+
+        // one covariance per point: diag(1, 2), a singular matrix and a rotation
+        let batch = Tensor::<f32>::from_vec(vec![1.0, 0.0, 0.0, 2.0, 1.0, 2.0, 2.0, 4.0, 0.0, -1.0, 1.0, 0.0], &[3, 2, 2])?;
+        let dets = batch.det()?;
+        assert_eq!(dets.shape(), &[3]);
+        assert_eq!(dets.data(), &[2.0, 0.0, 1.0]);
+        assert_eq!(batch.view(&[3, 1, 2, 2])?.det()?.shape(), &[3, 1]);
+
+        assert!(Tensor::<f32>::from_vec(vec![0.0; 6], &[2, 3])?.det().is_err());
+        assert!(Tensor::<f32>::from_vec(vec![0.0; 4], &[4])?.det().is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_inverse() -> MlResult<()> {
+        let batch = Tensor::<f32>::from_vec(vec![4.0, 7.0, 2.0, 6.0, 0.0, 1.0, 1.0, 0.0], &[2, 2, 2])?;
+        let inverse = batch.inverse()?;
+        assert_eq!(inverse.shape(), &[2, 2, 2]);
+        let expected = [0.6, -0.7, -0.2, 0.4, 0.0, 1.0, 1.0, 0.0];
+        for (a, e) in inverse.data().iter().zip(expected) {
+            assert!((a - e).abs() < 1e-6, "{:?}", inverse.data());
+        }
+
+        // the product with the original is the identity for every matrix in the batch
+        let product = ops!(batch, Matmul, inverse)?;
+        for (a, e) in product.data().iter().zip([1.0, 0.0, 0.0, 1.0, 1.0, 0.0, 0.0, 1.0]) {
+            assert!((a - e).abs() < 1e-5);
+        }
+
+        let singular = Tensor::<f32>::from_vec(vec![1.0, 0.0, 0.0, 1.0, 1.0, 2.0, 2.0, 4.0], &[2, 2, 2])?;
+        assert!(singular.inverse().is_err());
+        assert!(Tensor::<f32>::from_vec(vec![0.0; 6], &[3, 2])?.inverse().is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_matrix_norms() -> MlResult<()> {
+        let matrix = Tensor::<f32>::from_vec(vec![3.0, 0.0, 4.0, 5.0], &[2, 2])?;
+        assert!((matrix.frobenius_norm()? - 50f32.sqrt()).abs() < 1e-6);
+        assert_eq!(Tensor::<f32>::from_vec(vec![], &[0, 3])?.frobenius_norm()?, 0.0);
+
+        // singular values of [[3, 0], [4, 5]] are 3 * sqrt(5) and sqrt(5)
+        assert!((matrix.spectral_norm(50)? - 3.0 * 5f32.sqrt()).abs() < 1e-4);
+
+        let diagonal = Tensor::<f32>::from_vec(vec![2.0, 0.0, 0.0, 0.0, -7.0, 0.0], &[2, 3])?;
+        assert!((diagonal.spectral_norm(20)? - 7.0).abs() < 1e-4);
+        let row = Tensor::<f32>::from_vec(vec![1.0, -1.0], &[1, 2])?;
+        assert!((row.spectral_norm(1)? - 2f32.sqrt()).abs() < 1e-6);
+        assert_eq!(Tensor::<f32>::from_vec(vec![0.0; 4], &[2, 2])?.spectral_norm(5)?, 0.0);
+
+        assert!(Tensor::<f32>::from_vec(vec![1.0; 8], &[2, 2, 2])?.spectral_norm(5).is_err());
+        assert!(matrix.spectral_norm(0).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_polynomial_features() -> MlResult<()> {
+        let x = Tensor::<f32>::from_vec(vec![2.0, 3.0, -1.0, 0.5], &[2, 2])?;
+
+        let expanded = x.polynomial_features(2, true)?;
+        assert_eq!(expanded.shape(), &[2, 6]);
+        // 1, a, b, a², ab, b²
+        assert_eq!(&expanded.data()[..6], &[1.0, 2.0, 3.0, 4.0, 6.0, 9.0]);
+        assert_eq!(&expanded.data()[6..], &[1.0, -1.0, 0.5, 1.0, -0.5, 0.25]);
+
+        let cubic = Tensor::<f32>::from_vec(vec![1.0, 2.0, 3.0], &[1, 3])?.polynomial_features(3, false)?;
+        // C(3 + 3, 3) - 1 columns, ending with c³
+        assert_eq!(cubic.shape(), &[1, 19]);
+        assert_eq!(cubic.data()[18], 27.0);
+        assert_eq!(cubic.data()[9], 1.0);
+
+        assert_eq!(x.polynomial_features(1, false)?.data(), x.data());
+        assert!(x.polynomial_features(0, true).is_err());
+        assert!(Tensor::<f32>::from_vec(vec![1.0, 2.0], &[2])?.polynomial_features(2, true).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_matmul_i8() -> MlResult<()> {
+        let a = Tensor::<i8>::from_vec(vec![1, -2, 3, 4, 5, -6], &[2, 3])?;
+        let b = Tensor::<i8>::from_vec(vec![7, 8, 9, 10, -11, 12], &[3, 2])?;
+        let result = matmul_i8(&*a, &*b)?;
+
+        assert_eq!(result.shape(), &[2, 2]);
+        assert_eq!(result.data(), &[-44, 24, 139, 10]);
+
+        // the extremes accumulate without wrapping
+        let k = 1000;
+        let a = Tensor::<i8>::from_vec(vec![-128; k], &[1, k])?;
+        let b = Tensor::<i8>::from_vec(vec![-128; k], &[k, 1])?;
+        assert_eq!(matmul_i8(&*a, &*b)?.data(), &[16384 * k as i32]);
+
+        assert!(matmul_i8(&*a, &*a).is_err());
+        let vector = Tensor::<i8>::from_vec(vec![1, 2], &[2])?;
+        assert!(matmul_i8(&*vector, &*vector).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_matmul_i8_inner_dim_limit() -> MlResult<()> {
+        let k = MATMUL_I8_MAX_INNER_DIM + 1;
+        let a = Tensor::<i8>::from_vec(vec![0; k], &[1, k])?;
+        let b = Tensor::<i8>::from_vec(vec![0; k], &[k, 1])?;
+        assert!(matmul_i8(&*a, &*b).is_err());
+        assert!(MATMUL_I8_MAX_INNER_DIM as i64 * 16384 <= i32::MAX as i64);
+        Ok(())
+    }
+}
@@ -4,11 +4,61 @@ use std::{
 };
 use std::ops::Deref;
 
-use crate::{backend::Backend, MlResult};
+use crate::{backend::Backend, MlError, MlResult};
 
 mod ops;
 mod broadcast;
 mod creation;
+mod linalg;
+mod dispatch;
+mod reduce;
+mod indexing;
+mod random;
+mod activation;
+mod io;
+mod display;
+mod csv;
+#[cfg(feature = "enable_backpropagation")]
+mod autograd;
+mod npy;
+mod npz;
+mod view;
+mod safetensors;
+mod quantize;
+mod builder;
+mod checkpoint;
+#[cfg(feature = "serde")]
+mod serialization;
+#[cfg(feature = "ndarray")]
+mod array;
+#[cfg(feature = "approx")]
+mod approx;
+#[cfg(feature = "image")]
+mod image;
+#[cfg(feature = "arrow")]
+mod arrow;
+
+pub use linalg::{addmv, matmul_i8, matmul_into, MATMUL_I8_MAX_INNER_DIM};
+pub use dispatch::{apply_binary, apply_unary, BinaryOpKind, UnaryOpKind};
+pub use random::Rng;
+pub use builder::TensorBuilder;
+pub use creation::CollectTensor;
+pub use checkpoint::checkpoint;
+pub use activation::{activation_by_name, Activation, ActivationFn, TopPInput};
+pub use indexing::{concat, sequence_mask, stack, IndexedIter};
+pub use io::{load_state, save_state};
+pub use csv::CsvOptions;
+pub use display::{print_options, set_print_options, PrintOptions};
+pub use npz::{load_npz, save_npz};
+pub use safetensors::{load_safetensors, save_safetensors};
+#[cfg(feature = "image")]
+pub use image::ImageLayout;
+#[cfg(feature = "arrow")]
+pub use arrow::NullPolicy;
+#[cfg(feature = "enable_backpropagation")]
+pub use autograd::{CheckpointNode, GradFn, OpNode};
+#[cfg(feature = "enable_backpropagation")]
+use autograd::with_grad_fn;
 
 /// 다양한 텐서 연산을 위한 편리한 매크로를 제공합니다.
 ///
@@ -18,16 +68,27 @@ mod creation;
 /// # supported operator
 /// 이 매크로는 다음과 같은 연산들을 지원합니다:
 /// - 이항 연산: `Matmul`, `Add`, `Sub`, `Mul`, `Div`
-/// - 단항 연산: `Exp`, `Neg`, `Sqrt`, `Abs`, `Square`, `Log`
-/// - 특수 연산: `Topk`, `Matmax`, `Pow`
+/// - 단항 연산: `Exp`, `Neg`, `Sqrt`, `Abs`, `Square`, `Log`, `Relu6`, `Hardtanh`
+/// - 특수 연산: `Topk`, `Matmax`, `Var`, `Pow`
+///
+/// `Matmax` takes an optional fifth argument controlling NaN propagation, e.g.
+/// `ops!(t, Matmax, Some(1), false, false)` skips NaNs. Likewise `Var` takes an
+/// optional fifth argument selecting Welford's algorithm, e.g. `ops!(t, Var, None, false, true)`.
+/// `Hardtanh` clamps to `[-1, 1]` unless bounds are given, e.g. `ops!(t, Hardtanh, 0.0, 1.0)`.
+///
+/// # Nested expressions
+/// 이항 연산과 단항 연산은 중첩해서 쓸 수도 있습니다, e.g.
+/// `ops!(Sqrt(Add(Square(Sub(a, b)), eps)))`. Each tensor argument is evaluated exactly
+/// once, and the first failing op returns its error from the whole expression.
 ///
 /// # Examples
 ///
 /// ```rust
-/// use MIT::{ops, tensor::{Tensor, TensorBase}};
+/// use MIT::prelude::*;
 ///
-/// let mut tensor1 = Tensor::<f32>::new(vec![vec![1.0, 2.0, 3.0]]);
-/// let tensor2 = Tensor::<f32>::new(vec![vec![3.0, 2.0, 1.0]]);
+/// # fn main() -> MlResult<()> {
+/// let tensor1 = Tensor::<f32>::new(vec![vec![1.0, 2.0, 3.0]])?;
+/// let tensor2 = Tensor::<f32>::new(vec![vec![3.0, 2.0, 1.0]])?;
 ///
 /// // 기본 산술 연산
 /// let result = ops!(tensor1, Add, tensor2)?;
@@ -38,8 +99,14 @@ mod creation;
 /// let result = ops!(tensor1, Log)?;
 ///
 /// // 특수 연산
-/// let result = ops!(tensor1, Topk, 5, true)?; // 상위 5개 요소, 정렬됨
+/// let (values, indices) = ops!(tensor1, Topk, 2, true)?; // 상위 2개 요소, 정렬됨
 /// let result = ops!(tensor1, Pow, 2.0)?; // 텐서의 제곱
+/// let result = ops!(tensor1, Pow, tensor2)?; // 요소별 지수 (브로드캐스팅)
+///
+/// // 중첩 연산
+/// let result = ops!(Sqrt(Add(Square(Sub(tensor1, tensor2)), tensor1)))?;
+/// # Ok(())
+/// # }
 /// ```
 ///
 /// # Parameters
@@ -51,9 +118,9 @@ mod creation;
 /// # Return
 /// 지정된 연산의 순전파(forward) 결과를 반환합니다.
 ///
-/// # Panic
-/// 연산 초기화가 실패할 경우 패닉이 발생합니다 (`unwrap()`으로 감싸져 있음).
-/// 이러한 에러를 직접 처리해야 하는 경우, 기본 메서드를 직접 사용하는 것을 고려하세요.
+/// # Errors
+/// 연산 초기화(`new`)와 순전파(`forward`)의 에러는 패닉 없이 `Err`로 반환되므로 `?`로 전파할 수 있습니다.
+/// 형상 검증은 각 연산의 `new`에서 이루어집니다.
 ///
 /// # Implementation Details
 /// - 모든 연산은 원본 텐서의 형상(shape)을 유지합니다.
@@ -65,68 +132,204 @@ mod creation;
 /// 고성능 연산이 필요한 경우 in-place 연산을 지원하는 별도 메서드 구현을 권장합니다.
 #[macro_export]
 macro_rules! ops {
+    (@eval Add ( $($args:tt)+ )) => { $crate::ops!(@binary Add [] $($args)+) };
+    (@eval Sub ( $($args:tt)+ )) => { $crate::ops!(@binary Sub [] $($args)+) };
+    (@eval Mul ( $($args:tt)+ )) => { $crate::ops!(@binary Mul [] $($args)+) };
+    (@eval Div ( $($args:tt)+ )) => { $crate::ops!(@binary Div [] $($args)+) };
+    (@eval Matmul ( $($args:tt)+ )) => { $crate::ops!(@binary Matmul [] $($args)+) };
+    (@eval Exp ( $($arg:tt)+ )) => { $crate::ops!(@unary Exp $($arg)+) };
+    (@eval Neg ( $($arg:tt)+ )) => { $crate::ops!(@unary Neg $($arg)+) };
+    (@eval Sqrt ( $($arg:tt)+ )) => { $crate::ops!(@unary Sqrt $($arg)+) };
+    (@eval Abs ( $($arg:tt)+ )) => { $crate::ops!(@unary Abs $($arg)+) };
+    (@eval Square ( $($arg:tt)+ )) => { $crate::ops!(@unary Square $($arg)+) };
+    (@eval Log ( $($arg:tt)+ )) => { $crate::ops!(@unary Log $($arg)+) };
+    (@eval Relu6 ( $($arg:tt)+ )) => { $crate::ops!(@unary Relu6 $($arg)+) };
+    (@eval Hardtanh ( $($arg:tt)+ )) => { $crate::ops!(@unary Hardtanh $($arg)+) };
+    (@eval $leaf:expr) => { $leaf };
+
+    (@unary $op:ident $($arg:tt)+) => {{
+        use ::std::ops::Deref as _;
+        use $crate::tensor::Function as _;
+        let operand = &$crate::ops!(@eval $($arg)+);
+        let mut op = $crate::tensor::$op::new(operand.deref(), None)?;
+        op.forward()?
+    }};
+
+    // collects the tokens of the first operand up to the top-level comma
+    (@binary $op:ident [$($first:tt)+] , $($second:tt)+) => {{
+        use ::std::ops::Deref as _;
+        use $crate::tensor::Function as _;
+        let first = &$crate::ops!(@eval $($first)+);
+        let second = &$crate::ops!(@eval $($second)+);
+        let mut op = $crate::tensor::$op::new(first.deref(), Some(second.deref()))?;
+        op.forward()?
+    }};
+
+    (@binary $op:ident [$($first:tt)*] $next:tt $($rest:tt)*) => {
+        $crate::ops!(@binary $op [$($first)* $next] $($rest)*)
+    };
+
+    ($op:ident ( $($args:tt)+ )) => {
+        (|| -> $crate::MlResult<$crate::tensor::ArcTensor<f32>> {
+            Ok($crate::ops!(@eval $op ( $($args)+ )))
+        })()
+    };
+
     ($tensor:expr, Matmul, $second_tensor:expr) => {
-        Matmul::new($tensor.deref(), Some($second_tensor.deref())).unwrap().forward()
+        match Matmul::new($tensor.deref(), Some($second_tensor.deref())) {
+            Ok(mut op) => op.forward(),
+            Err(e) => Err(e),
+        }
     };
 
-    ($tensor:expr, Topk, $k:expr, $sorted:expr) => {{
-        let mut op = Topk::new($tensor.deref(), None).unwrap();
-        op.topk = Some(($k, $sorted));
-        op.forward()
-    }};
+    ($tensor:expr, Topk, $k:expr, $sorted:expr) => {
+        match Topk::with_k($tensor.deref(), $k, $sorted) {
+            Ok(mut op) => op.forward(),
+            Err(e) => Err(e),
+        }
+    };
 
-    ($tensor:expr, Matmax, $dim:expr, $keepdim:expr) => {{
-        let mut op = Matmax::new($tensor.deref(), None).unwrap();
-        op.matmax = Some(($dim, $keepdim));
-        op.forward()
-    }};
+    ($tensor:expr, Matmax, $dim:expr, $keepdim:expr) => {
+        match Matmax::with_dim($tensor.deref(), $dim, $keepdim) {
+            Ok(mut op) => op.forward(),
+            Err(e) => Err(e),
+        }
+    };
+
+    ($tensor:expr, Matmax, $dim:expr, $keepdim:expr, $nan_propagate:expr) => {
+        match Matmax::with_dim($tensor.deref(), $dim, $keepdim) {
+            Ok(mut op) => {
+                op.nan_propagate = $nan_propagate;
+                op.forward()
+            }
+            Err(e) => Err(e),
+        }
+    };
+
+    ($tensor:expr, Var, $dim:expr, $keepdim:expr) => {
+        match Var::new($tensor.deref(), None) {
+            Ok(mut op) => {
+                op.var = Some(($dim, $keepdim));
+                op.forward()
+            }
+            Err(e) => Err(e),
+        }
+    };
+
+    ($tensor:expr, Var, $dim:expr, $keepdim:expr, $stable:expr) => {
+        match Var::new($tensor.deref(), None) {
+            Ok(mut op) => {
+                op.var = Some(($dim, $keepdim));
+                op.stable = $stable;
+                op.forward()
+            }
+            Err(e) => Err(e),
+        }
+    };
 
     ($tensor:expr, Add, $second_tensor:expr) => {
-        Add::new($tensor.deref(), Some($second_tensor.deref())).unwrap().forward()
+        match Add::new($tensor.deref(), Some($second_tensor.deref())) {
+            Ok(mut op) => op.forward(),
+            Err(e) => Err(e),
+        }
     };
 
     ($tensor:expr, Sub, $second_tensor:expr) => {
-        Sub::new($tensor.deref(), Some($second_tensor.deref())).unwrap().forward()
+        match Sub::new($tensor.deref(), Some($second_tensor.deref())) {
+            Ok(mut op) => op.forward(),
+            Err(e) => Err(e),
+        }
     };
 
     ($tensor:expr, Mul, $second_tensor:expr) => {
-        Mul::new($tensor.deref(), Some($second_tensor.deref())).unwrap().forward()
+        match Mul::new($tensor.deref(), Some($second_tensor.deref())) {
+            Ok(mut op) => op.forward(),
+            Err(e) => Err(e),
+        }
     };
 
     ($tensor:expr, Div, $second_tensor:expr) => {
-        Div::new($tensor.deref(), Some($second_tensor.deref())).unwrap().forward()
+        match Div::new($tensor.deref(), Some($second_tensor.deref())) {
+            Ok(mut op) => op.forward(),
+            Err(e) => Err(e),
+        }
     };
 
     ($tensor:expr, Exp) => {
-        Exp::new($tensor.deref(), None).unwrap().forward()
+        match Exp::new($tensor.deref(), None) {
+            Ok(mut op) => op.forward(),
+            Err(e) => Err(e),
+        }
     };
 
     ($tensor:expr, Neg) => {
-        Neg::new($tensor.deref(), None).unwrap().forward()
+        match Neg::new($tensor.deref(), None) {
+            Ok(mut op) => op.forward(),
+            Err(e) => Err(e),
+        }
     };
 
     ($tensor:expr, Sqrt) => {
-        Sqrt::new($tensor.deref(), None).unwrap().forward()
+        match Sqrt::new($tensor.deref(), None) {
+            Ok(mut op) => op.forward(),
+            Err(e) => Err(e),
+        }
     };
 
     ($tensor:expr, Abs) => {
-        Abs::new($tensor.deref(), None).unwrap().forward()
+        match Abs::new($tensor.deref(), None) {
+            Ok(mut op) => op.forward(),
+            Err(e) => Err(e),
+        }
     };
 
     ($tensor:expr, Square) => {
-        Square::new($tensor.deref(), None).unwrap().forward()
+        match Square::new($tensor.deref(), None) {
+            Ok(mut op) => op.forward(),
+            Err(e) => Err(e),
+        }
     };
 
     ($tensor:expr, Log) => {
-        Log::new($tensor.deref(), None).unwrap().forward()
+        match Log::new($tensor.deref(), None) {
+            Ok(mut op) => op.forward(),
+            Err(e) => Err(e),
+        }
     };
 
-    ($tensor:expr, Pow, $exponent:expr) => {{
-        let mut op = Pow::new($tensor.deref(), None).unwrap();
-        op.power = Some($exponent);
-        op.forward()
+    ($tensor:expr, Relu6) => {
+        match Relu6::new($tensor.deref(), None) {
+            Ok(mut op) => op.forward(),
+            Err(e) => Err(e),
+        }
+    };
 
-    }};
+    ($tensor:expr, Hardtanh) => {
+        match Hardtanh::new($tensor.deref(), None) {
+            Ok(mut op) => op.forward(),
+            Err(e) => Err(e),
+        }
+    };
+
+    ($tensor:expr, Hardtanh, $min:expr, $max:expr) => {
+        match Hardtanh::new($tensor.deref(), None) {
+            Ok(mut op) => {
+                op.bounds = ($min, $max);
+                op.forward()
+            }
+            Err(e) => Err(e),
+        }
+    };
+
+    ($tensor:expr, Pow, $exponent:expr) => {
+        match Pow::new($tensor.deref(), None) {
+            Ok(mut op) => {
+                $crate::tensor::PowExponent::apply(&$exponent, &mut op);
+                op.forward()
+            }
+            Err(e) => Err(e),
+        }
+    };
 }
 
 
@@ -138,36 +341,41 @@ macro_rules! ops {
 /// - `Sub`: 텐서의 각 요소에서 스칼라 값을 뺌
 /// - `Mul`: 텐서의 각 요소에 스칼라 값을 곱함
 /// - `Div`: 텐서의 각 요소를 스칼라 값으로 나눔
+/// - `Pow`: 텐서의 각 요소를 스칼라 값으로 거듭제곱함
+/// - `Max`: 텐서의 각 요소와 스칼라 값 중 큰 값을 취함
+/// - `Min`: 텐서의 각 요소와 스칼라 값 중 작은 값을 취함
 ///
 /// ## 역방향 연산 (스칼라 op 텐서)
-/// - `buS`: 스칼라 값에서 텐서의 각 요소를 뺌
-/// - `viD`: 스칼라 값을 텐서의 각 요소로 나눔
+/// - `RSub`: 스칼라 값에서 텐서의 각 요소를 뺌
+/// - `RDiv`: 스칼라 값을 텐서의 각 요소로 나눔
+/// - `buS`, `viD`: `RSub`, `RDiv`의 이전 이름으로, 사용 시 deprecated 경고가 발생합니다
 ///
 /// # Examples
 ///
 /// ```rust
-/// use MIT::{scalar_ops, tensor::{Tensor, TensorBase}};
+/// use MIT::prelude::*;
 ///
-/// let tensor = Tensor::<f32>::new(vec![vec![1.0, 2.0, 3.0]]);
+/// # fn main() -> MlResult<()> {
+/// let tensor = Tensor::<f32>::new(vec![vec![1.0, 2.0, 3.0]])?;
 ///
 /// // 정방향 연산 예시
-/// let result = scalar_ops!(tensor, Add, 2.0); // 모든 요소에 2.0을 더함
-/// let result = scalar_ops!(tensor, Mul, 3.0); // 모든 요소에 3.0을 곱함
+/// let result = scalar_ops!(tensor, Add, 2.0)?; // 모든 요소에 2.0을 더함
+/// let result = scalar_ops!(tensor, Mul, 3.0)?; // 모든 요소에 3.0을 곱함
+/// let result = scalar_ops!(tensor, Max, 2.0)?; // [2.0, 2.0, 3.0]
 ///
 /// // 역방향 연산 예시
-/// let result = scalar_ops!(5.0, buS, tensor); // 5.0에서 각 요소를 뺌
-/// let result = scalar_ops!(1.0, viD, tensor); // 1.0을 각 요소로 나눔
+/// let result = scalar_ops!(5.0, RSub, tensor)?; // 5.0에서 각 요소를 뺌
+/// let result = scalar_ops!(1.0, RDiv, tensor)?; // 1.0을 각 요소로 나눔
+/// # Ok(())
+/// # }
 /// ```
 ///
 /// # Return
-/// 지정된 연산의 순전파(forward) 결과를 반환합니다.
-///
-/// # Panic
-/// 연산 초기화가 실패할 경우 패닉이 발생합니다 (`unwrap()`으로 감싸져 있음).
-/// 이러한 에러를 직접 처리해야 하는 경우, 기본 메서드를 직접 사용하는 것을 고려하세요.
+/// 지정된 연산의 결과를 `MlResult<ArcTensor<T>>`로 반환합니다.
 ///
 /// # Implementation Details
 /// - 모든 연산은 원본 텐서의 형상(shape)을 유지합니다.
+/// - 텐서 표현식은 한 번만 평가되므로, 부수 효과가 있는 표현식도 안전하게 넘길 수 있습니다.
 /// - 새로운 텐서를 생성하여 결과를 반환하므로, 원본 텐서는 변경되지 않습니다.
 /// - Iterator와 map을 사용하여 각 요소에 대한 연산을 수행합니다.
 ///
@@ -189,30 +397,73 @@ macro_rules! ops {
 #[macro_export]
 macro_rules! scalar_ops {
     ($tensor:expr, Add, $scalar:expr) => {
-        Tensor::from_vec($tensor.data().iter().map(|&x| x + $scalar).collect(), &$tensor.shape())
+        $crate::scalar_ops!(@map $tensor, |x, s| x + s, $scalar)
     };
 
     ($tensor:expr, Sub, $scalar:expr) => {
-        Tensor::from_vec($tensor.data().iter().map(|&x| x - $scalar).collect(), &$tensor.shape())
+        $crate::scalar_ops!(@map $tensor, |x, s| x - s, $scalar)
     };
 
     ($tensor:expr, Mul, $scalar:expr) => {
-        Tensor::from_vec($tensor.data().iter().map(|&x| x * $scalar).collect(), &$tensor.shape())
+        $crate::scalar_ops!(@map $tensor, |x, s| x * s, $scalar)
     };
 
     ($tensor:expr, Div, $scalar:expr) => {
-        Tensor::from_vec($tensor.data().iter().map(|&x| x / $scalar).collect(), &$tensor.shape())
+        $crate::scalar_ops!(@map $tensor, |x, s| x / s, $scalar)
     };
 
-    ($scalar:expr, buS, $tensor:expr) => {
-        Tensor::from_vec($tensor.data().iter().map(|&x| $scalar - x).collect(), &$tensor.shape())
+    ($tensor:expr, Pow, $scalar:expr) => {
+        $crate::scalar_ops!(@map $tensor, |x, s| x.powf(s), $scalar)
     };
 
-    ($scalar:expr, viD, $tensor:expr) => {
-        Tensor::from_vec($tensor.data().iter().map(|&x| $scalar / x).collect(), &$tensor.shape())
+    ($tensor:expr, Max, $scalar:expr) => {
+        $crate::scalar_ops!(@map $tensor, |x, s| x.max(s), $scalar)
     };
+
+    ($tensor:expr, Min, $scalar:expr) => {
+        $crate::scalar_ops!(@map $tensor, |x, s| x.min(s), $scalar)
+    };
+
+    ($scalar:expr, RSub, $tensor:expr) => {
+        $crate::scalar_ops!(@map $tensor, |x, s| s - x, $scalar)
+    };
+
+    ($scalar:expr, RDiv, $tensor:expr) => {
+        $crate::scalar_ops!(@map $tensor, |x, s| s / x, $scalar)
+    };
+
+    ($scalar:expr, buS, $tensor:expr) => {{
+        $crate::tensor::scalar_ops_bus();
+        $crate::scalar_ops!($scalar, RSub, $tensor)
+    }};
+
+    ($scalar:expr, viD, $tensor:expr) => {{
+        $crate::tensor::scalar_ops_vid();
+        $crate::scalar_ops!($scalar, RDiv, $tensor)
+    }};
+
+    (@map $tensor:expr, |$x:ident, $s:ident| $body:expr, $scalar:expr) => {{
+        #[allow(unused_imports)]
+        use $crate::tensor::TensorBase as _;
+        let tensor = &$tensor;
+        let $s = $scalar;
+        <$crate::tensor::Tensor<_> as $crate::tensor::TensorBase<_>>::from_vec(
+            tensor.data().iter().map(|&$x| $body).collect(),
+            tensor.shape(),
+        )
+    }};
 }
 
+/// Marks the `buS` arm of [`scalar_ops!`] as deprecated
+#[doc(hidden)]
+#[deprecated(note = "use `RSub` instead of `buS` in `scalar_ops!`")]
+pub fn scalar_ops_bus() {}
+
+/// Marks the `viD` arm of [`scalar_ops!`] as deprecated
+#[doc(hidden)]
+#[deprecated(note = "use `RDiv` instead of `viD` in `scalar_ops!`")]
+pub fn scalar_ops_vid() {}
+
 #[derive(Debug, Clone)]
 pub enum TensorError {
     InvalidShape {
@@ -229,17 +480,58 @@ pub enum TensorError {
         reason: String,
     },
     InvalidAxis {
-        axis: usize,
-        shape: Vec<usize>,
+        axis: i32,
+        ndim: usize,
     },
     MatrixMultiplicationError {
         left_shape: Vec<usize>,
         right_shape: Vec<usize>,
     },
     EmptyTensor,
+    /// A row of nested input whose length differs from the first row
+    RaggedInput {
+        row: usize,
+        expected: usize,
+        got: usize,
+    },
+    /// A failure inside an op, with the op name and the shapes of all operands
+    OpFailed {
+        op: &'static str,
+        shapes: Vec<Vec<usize>>,
+        detail: Box<TensorError>,
+    },
+}
+
+impl TensorError {
+    /// Short description of the failure, without the shapes carried by `OpFailed`
+    fn summary(&self) -> String {
+        match self {
+            TensorError::InvalidShape { .. } => "shapes do not match".to_string(),
+            TensorError::InvalidOperation { reason, .. } => reason.clone(),
+            TensorError::InvalidAxis { axis, ndim } => format!("invalid axis {} for {} dimensions", axis, ndim),
+            TensorError::MatrixMultiplicationError { left_shape, right_shape } => {
+                let inner = left_shape.last();
+                let other = match right_shape.len() {
+                    0 => None,
+                    1 => right_shape.first(),
+                    n => right_shape.get(n - 2),
+                };
+                if inner == other { "batch dimensions differ" } else { "inner dimensions differ" }.to_string()
+            }
+            TensorError::EmptyTensor => "empty tensor".to_string(),
+            other => other.to_string(),
+        }
+    }
 }
 
-impl std::error::Error for TensorError {}
+impl std::error::Error for TensorError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            TensorError::OpFailed { detail, .. } => Some(detail.as_ref()),
+            _ => None,
+        }
+    }
+}
 
 impl Display for TensorError {
     fn fmt(&self, f: &mut Formatter<'_>) -> Result {
@@ -253,8 +545,8 @@ impl Display for TensorError {
             TensorError::InvalidOperation { op, reason } => {
                 write!(f, "Invalid operation '{}': {}", op, reason)
             }
-            TensorError::InvalidAxis { axis, shape } => {
-                write!(f, "Invalid axis {} for tensor with shape {:?}", axis, shape)
+            TensorError::InvalidAxis { axis, ndim } => {
+                write!(f, "Invalid axis {} for tensor with {} dimensions", axis, ndim)
             }
             TensorError::MatrixMultiplicationError {
                 left_shape,
@@ -265,30 +557,199 @@ impl Display for TensorError {
             TensorError::EmptyTensor => {
                 write!(f, "Empty tensor")
             }
+            TensorError::RaggedInput { row, expected, got } => {
+                write!(f, "Ragged input: row {} has {} elements, expected {}", row, got, expected)
+            }
+            TensorError::OpFailed { op, shapes, detail } => {
+                write!(f, "{}: {} (", op, detail.summary())?;
+                match shapes.as_slice() {
+                    [input] => write!(f, "input {:?}", input)?,
+                    [left, right] => write!(f, "left {:?}, right {:?}", left, right)?,
+                    shapes => write!(f, "operands {:?}", shapes)?,
+                }
+                write!(f, ")")
+            }
         }
     }
 }
 
+/// Errors from reading the native tensor file format
+#[derive(Debug, Clone, PartialEq)]
+pub enum FormatError {
+    BadMagic {
+        expected: [u8; 4],
+        got: [u8; 4],
+    },
+    UnsupportedVersion(u32),
+    UnsupportedDtype(u8),
+    Truncated,
+    /// A shape whose size in bytes does not fit in `usize`
+    SizeOverflow {
+        shape: Vec<usize>,
+    },
+}
+
+impl std::error::Error for FormatError {}
+
+impl Display for FormatError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        match self {
+            FormatError::BadMagic { expected, got } => {
+                write!(f, "Invalid file header: expected {:?}, got {:?}", expected, got)
+            }
+            FormatError::UnsupportedVersion(version) => {
+                write!(f, "Unsupported format version {}", version)
+            }
+            FormatError::UnsupportedDtype(tag) => {
+                write!(f, "Unsupported dtype tag {}", tag)
+            }
+            FormatError::Truncated => {
+                write!(f, "Unexpected end of input")
+            }
+            FormatError::SizeOverflow { shape } => {
+                write!(f, "Size in bytes of shape {:?} overflows", shape)
+            }
+        }
+    }
+}
+
+/// Converts a possibly negative dimension into an index into a shape of rank `ndim`
+///
+/// `-1` is the last dimension and `-ndim` the first. Every op taking a dimension goes
+/// through this, so they all accept and reject the same values.
+///
+/// # Returns
+/// * `Ok(usize)` with the normalized dimension, in `0..ndim`
+/// * `Err(MlError::TensorError)` with `InvalidAxis` holding the original `dim` if it is
+///   outside `-ndim..ndim`
+pub fn normalize_dim(dim: i32, ndim: usize) -> MlResult<usize> {
+    let normalized = if dim < 0 { dim as i64 + ndim as i64 } else { dim as i64 };
+    if !(0..ndim as i64).contains(&normalized) {
+        return Err(MlError::TensorError(TensorError::InvalidAxis { axis: dim, ndim }));
+    }
+    Ok(normalized as usize)
+}
+
 pub struct Tensor<Type: Debug + 'static>
 {
-    data: Vec<Type>,
+    /// Shared with the graph nodes that recorded this tensor as an input; writes copy it first
+    data: Arc<Vec<Type>>,
     shape: Vec<usize>,
     requires_grad: bool,
 
     #[cfg(feature = "enable_backpropagation")]
-    grad: Option<Box<dyn TensorBase<Type>>>,
+    grad: Option<Box<dyn TensorBase<Type> + Send + Sync>>,
     #[cfg(feature = "enable_backpropagation")]
-    grad_fn: Option<Box<dyn Function<'static, Type, Forwarded=(), Gradiant=()>>>
+    grad_fn: Option<Arc<dyn GradFn<Type>>>
 }
 
 pub struct ArcTensor<T>(pub Arc<dyn TensorBase<T>>);
 
-impl ArcTensor<f32> {
-    pub fn new(tensor: Tensor<f32>) -> Self {
+/// Shares the underlying tensor, including its gradient state, by cloning the `Arc`
+///
+/// Use [`ArcTensor::deep_clone`] for an independent copy of the data.
+impl<T> Clone for ArcTensor<T> {
+    fn clone(&self) -> Self {
+        ArcTensor(Arc::clone(&self.0))
+    }
+}
+
+/// Wraps [`Tensor::default`], an empty 1-D tensor
+impl<T: Debug + 'static> Default for ArcTensor<T> {
+    fn default() -> Self {
+        ArcTensor::new(Tensor::default())
+    }
+}
+
+impl<T: Debug + Clone + 'static> ArcTensor<T> {
+    /// Copies the data into new storage with the same shape and `requires_grad` flag
+    ///
+    /// Like [`Tensor`]'s `Clone`, the copy starts without a gradient or `grad_fn`.
+    pub fn deep_clone(&self) -> ArcTensor<T> {
+        let mut tensor = Tensor::<T>::from_vec_owned(self.data().to_vec(), self.shape())
+            .expect("the data of a tensor always matches its shape");
+        tensor.requires_grad = self.requires_grad();
+        ArcTensor::new(tensor)
+    }
+}
+
+impl<T: Debug + 'static> ArcTensor<T> {
+    pub fn new(tensor: Tensor<T>) -> Self {
         ArcTensor(
             Arc::new(tensor)
         )
     }
+
+    /// Returns a tensor whose data is laid out contiguously in row-major order
+    ///
+    /// Tensors are currently always stored contiguously, so this is a cheap `Arc` clone
+    /// that shares the underlying data. Ops that require row-major data can call it
+    /// defensively without paying for a copy.
+    ///
+    /// # Returns
+    /// A tensor sharing the same storage as `self`
+    pub fn contiguous(&self) -> ArcTensor<T> {
+        ArcTensor(Arc::clone(&self.0))
+    }
+
+    /// Returns the same elements with a different shape, never reordering data
+    ///
+    /// Unlike a copying reshape, this refuses layouts that would need the elements
+    /// rearranged, so callers know no copy happens. The result shares storage with
+    /// `self`, as with `reinterpret`.
+    ///
+    /// # Arguments
+    /// * `new_shape` - The shape of the view; must hold the same number of elements
+    ///
+    /// # Returns
+    /// * `Ok(ArcTensor)` with shape `new_shape`
+    /// * `Err(MlError::TensorError)` with `InvalidShape` if the element count differs, or
+    ///   `InvalidOperation` if the layout is not contiguous
+    pub fn view(&self, new_shape: &[usize]) -> MlResult<ArcTensor<T>>
+    where
+        T: Send + Sync,
+    {
+        if !self.is_contiguous() {
+            return Err(MlError::TensorError(TensorError::InvalidOperation {
+                op: "view",
+                reason: format!("tensor with shape {:?} is not contiguous, call contiguous() first", self.shape()),
+            }));
+        }
+        self.reinterpret(new_shape)
+    }
+
+    /// Returns a tensor with a new shape that shares the data of `self` without copying
+    ///
+    /// The caller asserts that the elements are already in the row-major order of
+    /// `new_shape`. Both tensors read the same storage, so any in-place mutation of it
+    /// would be visible through both; keeping the shape returns `self` itself. With
+    /// `enable_backpropagation`, a new shape records a `reshape` node when `self` requires
+    /// a gradient.
+    ///
+    /// # Arguments
+    /// * `new_shape` - The new shape; must hold the same number of elements
+    ///
+    /// # Returns
+    /// * `Ok(ArcTensor)` with shape `new_shape`
+    /// * `Err(MlError::TensorError)` with `InvalidShape` if the element count differs
+    pub fn reinterpret(&self, new_shape: &[usize]) -> MlResult<ArcTensor<T>>
+    where
+        T: Send + Sync,
+    {
+        if new_shape.iter().product::<usize>() != self.data().len() {
+            return Err(MlError::TensorError(TensorError::InvalidShape {
+                expected: self.shape().to_vec(),
+                got: new_shape.to_vec(),
+            }));
+        }
+        if new_shape == self.shape() {
+            return Ok(self.contiguous());
+        }
+        let tensor = ArcTensor(Arc::new(view::ReshapedView::new(Arc::clone(&self.0), new_shape)));
+        #[cfg(feature = "enable_backpropagation")]
+        let tensor = with_grad_fn(tensor, "reshape", &[&*self.0]);
+        Ok(tensor)
+    }
 }
 
 impl<T> Deref for ArcTensor<T> {
@@ -299,31 +760,166 @@ impl<T> Deref for ArcTensor<T> {
     }
 }
 
-impl PartialEq for Tensor<f32> {
-    fn eq(&self, other: &Self) -> bool {
+macro_rules! impl_exact_eq {
+    ($($elem:ty),*) => {$(
+        impl PartialEq for Tensor<$elem> {
+            fn eq(&self, other: &Self) -> bool {
+                self.data == other.data && self.shape == other.shape
+            }
+        }
 
-        self.data == other.data && self.shape == other.shape
-    }
+        /// Compares the shapes and elements of the underlying tensors
+        impl PartialEq for ArcTensor<$elem> {
+            fn eq(&self, other: &Self) -> bool {
+                self.shape() == other.shape() && self.data() == other.data()
+            }
+        }
+
+        impl Eq for Tensor<$elem> {}
+    )*};
+}
+
+impl_exact_eq!(bool, i8, i16, i32, i64, isize, u8, u16, u32, u64, usize);
+
+/// FNV-1a over 64-bit words, used by `fingerprint`
+struct Fnv1a(u64);
+
+impl Fnv1a {
+    const PRIME: u64 = 0x100000001b3;
 }
 
-impl Eq for Tensor<f32> {
-    // Todo: 구현 필요
+impl Default for Fnv1a {
+    fn default() -> Self {
+        Fnv1a(0xcbf29ce484222325)
+    }
 }
 
-impl PartialOrd for Tensor<f32> {
-    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
-        self.data.partial_cmp(&other.data)
+impl std::hash::Hasher for Fnv1a {
+    fn finish(&self) -> u64 {
+        self.0
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for &byte in bytes {
+            self.0 = (self.0 ^ byte as u64).wrapping_mul(Self::PRIME);
+        }
+    }
+
+    fn write_u64(&mut self, word: u64) {
+        self.0 = (self.0 ^ word).wrapping_mul(Self::PRIME);
     }
 }
 
-impl Ord for Tensor<f32> {
-    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
-        self.partial_cmp(other).unwrap_or(std::cmp::Ordering::Equal)
+/// Feeds the shape and the element bit patterns to `state`
+fn hash_contents<H: std::hash::Hasher>(shape: &[usize], bits: impl Iterator<Item = u64>, state: &mut H) {
+    state.write_u64(shape.len() as u64);
+    for &d in shape {
+        state.write_u64(d as u64);
+    }
+    for word in bits {
+        state.write_u64(word);
     }
 }
 
+macro_rules! impl_float_cmp {
+    ($($elem:ty),*) => {$(
+        impl Tensor<$elem> {
+            /// Maps an element to its representative for comparison and hashing
+            ///
+            /// `-0.0` becomes `0.0` and every NaN becomes the positive quiet NaN, so
+            /// `total_cmp` on the result treats each group as one value and places NaN
+            /// above `+inf`.
+            fn canonical(v: $elem) -> $elem {
+                if v == 0.0 {
+                    0.0
+                } else if v.is_nan() {
+                    <$elem>::NAN
+                } else {
+                    v
+                }
+            }
+
+            /// Bit patterns of the canonical elements
+            fn bit_patterns(data: &[$elem]) -> impl Iterator<Item = u64> + '_ {
+                data.iter().map(|&v| Self::canonical(v).to_bits() as u64)
+            }
+
+            /// Compares two slices element-wise in the canonical total order
+            fn total_cmp_data(left: &[$elem], right: &[$elem]) -> std::cmp::Ordering {
+                left.iter()
+                    .zip(right)
+                    .map(|(&a, &b)| Self::canonical(a).total_cmp(&Self::canonical(b)))
+                    .find(|ordering| ordering.is_ne())
+                    .unwrap_or_else(|| left.len().cmp(&right.len()))
+            }
+        }
+
+        /// Tensors are equal when their shapes match and every pair of elements is equal,
+        /// where `-0.0` equals `0.0` and NaN equals NaN regardless of payload
+        ///
+        /// This differs from IEEE comparison of the elements but makes equality
+        /// reflexive, so `Eq`, `Ord` and `Hash` can all agree with it.
+        impl PartialEq for Tensor<$elem> {
+            fn eq(&self, other: &Self) -> bool {
+                self.shape == other.shape && Self::total_cmp_data(&self.data, &other.data).is_eq()
+            }
+        }
+
+        /// Compares the underlying tensors with the same NaN and signed-zero rules as `Tensor`
+        impl PartialEq for ArcTensor<$elem> {
+            fn eq(&self, other: &Self) -> bool {
+                self.shape() == other.shape() && Tensor::<$elem>::total_cmp_data(self.data(), other.data()).is_eq()
+            }
+        }
+
+        impl Eq for Tensor<$elem> {}
+
+        impl PartialOrd for Tensor<$elem> {
+            fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+                Some(self.cmp(other))
+            }
+        }
+
+        /// Orders tensors by shape first, then lexicographically by data
+        ///
+        /// Shapes compare as slices, so `[1, 4]` sorts before `[2, 2]` whatever the data.
+        /// Elements compare with `total_cmp` after folding `-0.0` into `0.0` and all NaNs
+        /// into one value greater than `+inf`. Two tensors compare `Equal` exactly when
+        /// they are equal under `PartialEq`.
+        impl Ord for Tensor<$elem> {
+            fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+                self.shape.cmp(&other.shape).then_with(|| Self::total_cmp_data(&self.data, &other.data))
+            }
+        }
+
+        /// Hashes the shape and the bit patterns of the canonical elements
+        ///
+        /// Signed zeros and NaN payloads are folded the same way as in `PartialEq`, so
+        /// equal tensors hash equally and a tensor containing NaN can be used as a key.
+        impl std::hash::Hash for Tensor<$elem> {
+            fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+                hash_contents(&self.shape, Self::bit_patterns(&self.data), state);
+            }
+        }
+
+        impl dyn TensorBase<$elem> + '_ {
+            /// Returns a 64-bit hash of the shape and elements
+            ///
+            /// This is a single FNV-1a pass, cheaper than serializing the tensor to build
+            /// a cache key. Tensors that are equal hash equally.
+            pub fn fingerprint(&self) -> u64 {
+                let mut state = Fnv1a::default();
+                hash_contents(self.shape(), Tensor::<$elem>::bit_patterns(self.data()), &mut state);
+                std::hash::Hasher::finish(&state)
+            }
+        }
+    )*};
+}
+
+impl_float_cmp!(f32, f64);
+
 pub trait TensorBase<Type: Debug + 'static> {
-    fn new(data: Vec<Vec<Type>>)                            -> ArcTensor<Type> where Self: Sized;
+    fn new(data: Vec<Vec<Type>>)                            -> MlResult<ArcTensor<Type>> where Self: Sized;
     fn from_vec(data: Vec<Type>, shape: &[usize])           -> MlResult<ArcTensor<Type>> where Self: Sized;
     fn shape(&self)                                         -> &[usize];
     fn data(&self)                                          -> &[Type];
@@ -333,22 +929,79 @@ pub trait TensorBase<Type: Debug + 'static> {
     /// Enables gradient computation for the tensor
     fn requires_grad(&self) -> bool;
 
-    // #[cfg(feature = "enable_backpropagation")]
-    //// Sets the gradient function for the tensor
-    // fn set_grad_fn(&self, grad_fn: Box<dyn Function<'static, Type, Forwarded=(), Gradiant=()>>);
+    /// Returns the number of dimensions
+    fn ndim(&self) -> usize {
+        self.shape().len()
+    }
 
-   //  #[cfg(feature = "enable_backpropagation")]
-   //  //// Returns the gradient of the tensor
-   // fn grad(&self) -> Option<&dyn TensorBase<Type>>;
-}
+    /// Returns the total number of elements
+    fn numel(&self) -> usize {
+        self.shape().iter().product()
+    }
 
-impl Debug for &dyn TensorBase<f32> {
-    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
-        write!(
-            f, "TensorBase<f32> Debug - data: {:?}, shape: {:?} requires_grad: {:?} ",
-               self.data(), self.shape(), self.requires_grad()
-        )
+    /// Returns the size of a dimension
+    ///
+    /// # Arguments
+    /// * `dim` - The dimension; negative values index from the end
+    ///
+    /// # Returns
+    /// * `Ok(usize)` with the size of `dim`
+    /// * `Err(MlError::TensorError)` with `InvalidAxis` if `dim` is out of range
+    fn size(&self, dim: i32) -> MlResult<usize> {
+        Ok(self.shape()[normalize_dim(dim, self.ndim())?])
+    }
+
+    /// Returns whether the tensor has no elements
+    fn is_empty(&self) -> bool {
+        self.numel() == 0
     }
+
+    /// Returns whether the data is laid out contiguously in row-major order
+    ///
+    /// Always true for now; strided tensors will report false.
+    fn is_contiguous(&self) -> bool {
+        true
+    }
+
+    /// Returns the number of elements to skip in `data` to advance one step along each dimension
+    ///
+    /// The strides are row-major, so a tensor of shape `[2, 3, 4]` has strides `[12, 4, 1]`.
+    fn strides(&self) -> Vec<usize> {
+        let mut strides = vec![1; self.shape().len()];
+        for i in (0..strides.len().saturating_sub(1)).rev() {
+            strides[i] = strides[i + 1] * self.shape()[i + 1];
+        }
+        strides
+    }
+
+    /// Iterates over the elements in row-major order
+    fn iter(&self) -> std::slice::Iter<'_, Type> {
+        self.data().iter()
+    }
+
+    /// Iterates over the elements in row-major order together with their multi-dimensional index
+    ///
+    /// Each index `idx` satisfies `get(&idx) == Some(value)`.
+    fn indexed_iter(&self) -> IndexedIter<'_, Type> {
+        IndexedIter::new(self.data(), self.shape())
+    }
+
+    #[cfg(feature = "enable_backpropagation")]
+    /// Sets the graph node recording how the tensor was computed
+    fn set_grad_fn(&mut self, grad_fn: Box<dyn GradFn<Type>>);
+
+    #[cfg(feature = "enable_backpropagation")]
+    /// Returns the graph node recording how the tensor was computed, if any
+    fn grad_fn(&self) -> Option<Arc<dyn GradFn<Type>>>;
+
+    #[cfg(feature = "enable_backpropagation")]
+    /// Returns the gradient accumulated for the tensor, if any
+    fn grad(&self) -> Option<&dyn TensorBase<Type>>;
+
+    #[cfg(feature = "enable_backpropagation")]
+    /// Returns a shared handle to the element buffer, so graph nodes can record the
+    /// tensor as an input without copying it
+    fn shared_data(&self) -> Arc<Vec<Type>>;
 }
 
 pub trait Function<'t, T: Debug + Clone> {
@@ -370,16 +1023,6 @@ pub trait Function<'t, T: Debug + Clone> {
 // }
 
 
-// pub trait BroadcastLayer {
-//     fn can_broadcast(&self, other: &Self) -> bool;
-//     fn broadcast_shape(&self, other: &Self) -> Vec<usize>;
-//     fn broadcasting<F>(self, other: Self, op: F) -> Option<Self>
-//     where
-//         F: Fn(f32, f32) -> f32,
-//         Self: Sized;
-//     fn calculate_broadcast_indices(&self, other: &Self, idx: usize, shape: &[usize]) -> Option<(usize, usize)>;
-// }
-
 /// Structure representing an exponential operation.
 pub struct Exp<'t, T>    { tensor: &'t dyn TensorBase<T>, backend: Arc<dyn Backend>,
     #[cfg(feature = "enable_backpropagation")]
@@ -416,11 +1059,80 @@ pub struct Log<'t, T>     { tensor: &'t dyn TensorBase<T>, backend: Arc<dyn Back
     output: Option<Arc<dyn TensorBase<T>>>
 }
 
+/// Structure representing a ReLU6 operation, clamping each element to `[0, 6]`.
+pub struct Relu6<'t, T>   { tensor: &'t dyn TensorBase<T>, backend: Arc<dyn Backend>,
+    #[cfg(feature = "enable_backpropagation")]
+    output: Option<Arc<dyn TensorBase<T>>>
+}
+
+/// Structure representing a hardtanh operation, clamping each element to `[min, max]`.
+///
+/// `bounds` holds `(min, max)` and defaults to `(-1.0, 1.0)`.
+pub struct Hardtanh<'t, T> { tensor: &'t dyn TensorBase<T>, backend: Arc<dyn Backend>,
+    #[cfg(feature = "enable_backpropagation")]
+    output: Option<Arc<dyn TensorBase<T>>>,
+    pub bounds: (f32, f32),
+}
+
 /// Structure representing a power operation.
+///
+/// The exponent is either the scalar `power` or the tensor `exponent`, which is
+/// broadcast against the base.
 pub struct Pow<'t, T>     { tensor: &'t dyn TensorBase<T>, backend: Arc<dyn Backend>,
     #[cfg(feature = "enable_backpropagation")]
     output: Option<Arc<dyn TensorBase<T>>>,
     pub power: Option<f32>,
+    pub exponent: Option<&'t dyn TensorBase<T>>,
+}
+
+/// An exponent accepted by `ops!(tensor, Pow, exponent)`, either a scalar or a tensor
+pub trait PowExponent<'t> {
+    /// Stores the exponent on the operation
+    fn apply(&'t self, op: &mut Pow<'t, f32>);
+}
+
+impl<'t> PowExponent<'t> for f32 {
+    fn apply(&'t self, op: &mut Pow<'t, f32>) {
+        op.power = Some(*self);
+    }
+}
+
+impl<'t> PowExponent<'t> for ArcTensor<f32> {
+    fn apply(&'t self, op: &mut Pow<'t, f32>) {
+        op.exponent = Some(self.deref());
+    }
+}
+
+/// A tensor operand of the method-style ops, such as `a.add(&b)`
+///
+/// Lets the methods accept `&ArcTensor`, `&Tensor` and `&dyn TensorBase` alike.
+pub trait AsTensor<T: Debug + 'static> {
+    /// Returns the tensor as a trait object
+    fn as_tensor(&self) -> &dyn TensorBase<T>;
+}
+
+impl<T: Debug + 'static> AsTensor<T> for ArcTensor<T> {
+    fn as_tensor(&self) -> &dyn TensorBase<T> {
+        self.deref()
+    }
+}
+
+impl<T: Debug + 'static> AsTensor<T> for Tensor<T> {
+    fn as_tensor(&self) -> &dyn TensorBase<T> {
+        self
+    }
+}
+
+impl<T: Debug + 'static> AsTensor<T> for dyn TensorBase<T> + '_ {
+    fn as_tensor(&self) -> &dyn TensorBase<T> {
+        self
+    }
+}
+
+impl<T: Debug + 'static, A: AsTensor<T> + ?Sized> AsTensor<T> for &A {
+    fn as_tensor(&self) -> &dyn TensorBase<T> {
+        (**self).as_tensor()
+    }
 }
 
 /// Structure representing a Top-k operation.
@@ -431,12 +1143,35 @@ pub struct Topk<'t, T>    { tensor: &'t dyn TensorBase<T>, backend: Arc<dyn Back
 } // k: usize, sorted: bool
 
 /// Structure representing a matrix max operation along a dimension.
+///
+/// `nan_propagate` defaults to true, following IEEE semantics: a NaN in a slice makes its
+/// maximum NaN, with the index of the first NaN. When false, NaNs are skipped like NumPy's
+/// `nanmax`, and only a slice of nothing but NaNs yields NaN.
 pub struct Matmax<'t, T>  { tensor: &'t dyn TensorBase<T>, backend: Arc<dyn Backend>,
     #[cfg(feature = "enable_backpropagation")]
     output: Option<(Arc<dyn TensorBase<T>>, Arc<dyn TensorBase<T>>)>,
-    pub matmax: Option<(Option<i32>, bool)>
+    pub matmax: Option<(Option<i32>, bool)>,
+    pub nan_propagate: bool,
+} // dim: (Option<i32>, keepdim: bool
+
+/// Structure representing a population variance operation along a dimension.
+///
+/// By default the mean is taken first and the squared deviations summed after it, in
+/// `f32`. With `stable` set, Welford's single-pass update is used instead, accumulating
+/// in `f64`, which stays accurate for large inputs far from zero.
+pub struct Var<'t, T>  { tensor: &'t dyn TensorBase<T>, backend: Arc<dyn Backend>,
+    #[cfg(feature = "enable_backpropagation")]
+    output: Option<Arc<dyn TensorBase<T>>>,
+    pub var: Option<(Option<i32>, bool)>,
+    pub stable: bool,
 } // dim: (Option<i32>, keepdim: bool
 
+/// Structure representing a concatenation along an existing dimension.
+///
+/// Unlike the ops implementing [`Function`] it takes any number of inputs, so it
+/// has its own `new`, `forward` and `backward` with the same roles.
+pub struct Concat<'t, T>  { tensors: Vec<&'t dyn TensorBase<T>>, pub dim: i32 }
+
 /// Structure representing an addition operation.
 pub struct Add<'t, T>     {
     first_tensor: &'t dyn TensorBase<T>,
@@ -485,7 +1220,7 @@ pub struct Matmul<'t, T> {
 #[cfg(test)]
 mod tests {
     use std::ops::Deref;
-    use crate::MlResult;
+    use crate::{MlError, MlResult};
     use crate::tensor::*;
 
     pub fn assert_tensor_eq(tensor: &ArcTensor<f32>, expected_tensor: &ArcTensor<f32>) -> MlResult<()> {
@@ -496,52 +1231,516 @@ mod tests {
 
     #[test]
     fn tensor() -> MlResult<()> {
-        let t1 = Tensor::new(vec![vec![1.0, 2.0]]);
+        let t1 = Tensor::new(vec![vec![1.0, 2.0]])?;
         assert_eq!(t1.data(), vec![1.0, 2.0]);
         assert_eq!(t1.shape(), vec![1, 2]);
         Ok(())
     }
 
+    #[test]
+    fn test_new_rejects_ragged_rows() -> MlResult<()> {
+        let ragged = Tensor::<f32>::new(vec![vec![1.0, 2.0], vec![3.0, 4.0], vec![5.0]]);
+        assert!(matches!(
+            ragged,
+            Err(MlError::TensorError(TensorError::RaggedInput { row: 2, expected: 2, got: 1 }))
+        ));
+        assert!(matches!(
+            Tensor::<f32>::new(vec![]),
+            Err(MlError::TensorError(TensorError::EmptyTensor))
+        ));
+        assert!(matches!(
+            Tensor::<f32>::new(vec![vec![], vec![1.0]]),
+            Err(MlError::TensorError(TensorError::RaggedInput { row: 1, expected: 0, got: 1 }))
+        ));
+
+        let empty_rows = Tensor::<f32>::new(vec![vec![], vec![], vec![]])?;
+        assert_eq!(empty_rows.shape(), &[3, 0]);
+        assert!(empty_rows.data().is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn test_contiguous() -> MlResult<()> {
+        let t1 = Tensor::<f32>::from_vec(vec![1.0, 2.0, 3.0, 4.0], &[2, 2])?;
+        let t2 = t1.contiguous();
+        assert!(std::sync::Arc::ptr_eq(&t1.0, &t2.0));
+        assert_tensor_eq(&t1, &t2)
+    }
+
+    #[test]
+    fn test_reinterpret_shares_data() -> MlResult<()> {
+        let tensor = Tensor::<f32>::from_vec((0..6).map(|x| x as f32).collect(), &[2, 3])?;
+        let flat = tensor.reinterpret(&[6])?;
+        assert_eq!(flat.shape(), &[6]);
+        assert_eq!(flat.data().as_ptr(), tensor.data().as_ptr());
+        assert_eq!(flat.get(&[4]), Some(&4.0));
+
+        let nested = flat.reinterpret(&[3, 1, 2])?;
+        assert_eq!(nested.data().as_ptr(), tensor.data().as_ptr());
+        assert_eq!(nested.get(&[2, 0, 1]), Some(&5.0));
+        assert_eq!(ops!(nested, Add, nested)?.data(), &[0.0, 2.0, 4.0, 6.0, 8.0, 10.0]);
+
+        assert!(tensor.reinterpret(&[4]).is_err());
+        Ok(())
+    }
+
+    #[cfg(feature = "enable_backpropagation")]
+    #[test]
+    fn test_reinterpret_records_grad_fn() -> MlResult<()> {
+        let tensor = Tensor::<f32>::from_vec(vec![0.0, 1.0, 2.0, 3.0], &[2, 2])?;
+        let flat = tensor.exp()?.reinterpret(&[4])?;
+        let node = flat.grad_fn().unwrap();
+        assert_eq!(node.name(), "reshape");
+        assert_eq!(node.inputs()[0].shape(), &[2, 2]);
+        assert_eq!(node.inputs()[0].grad_fn().unwrap().name(), "exp");
+        assert_eq!(tensor.exp()?.view(&[4, 1])?.grad_fn().unwrap().name(), "reshape");
+        Ok(())
+    }
+
+    #[test]
+    fn test_from_fn() -> MlResult<()> {
+        let pattern = Tensor::<f32>::from_fn(&[2, 3, 4], |idx| (idx[0] * 100 + idx[1] * 10 + idx[2]) as f32)?;
+        assert_eq!(pattern.shape(), &[2, 3, 4]);
+        for (idx, &value) in pattern.indexed_iter() {
+            assert_eq!(value, (idx[0] * 100 + idx[1] * 10 + idx[2]) as f32);
+        }
+
+        // a distance matrix between positions 0..3
+        let distances = Tensor::<usize>::from_fn(&[3, 3], |idx| idx[0].abs_diff(idx[1]))?;
+        assert_eq!(distances.data(), &[0, 1, 2, 1, 0, 1, 2, 1, 0]);
+
+        assert_eq!(Tensor::<f32>::from_fn(&[], |idx| idx.len() as f32)?.data(), &[0.0]);
+        assert!(matches!(
+            Tensor::<f32>::from_fn(&[2, 0], |_| 1.0).err().unwrap(),
+            MlError::TensorError(TensorError::EmptyTensor)
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn test_shape_helpers() -> MlResult<()> {
+        let tensor = Tensor::<f32>::from_vec(vec![0.0; 24], &[2, 3, 4])?;
+        assert_eq!(tensor.ndim(), 3);
+        assert_eq!(tensor.numel(), 24);
+        assert!(!tensor.is_empty());
+        assert_eq!(tensor.size(0)?, 2);
+        assert_eq!(tensor.size(-1)?, 4);
+        assert_eq!(tensor.size(-3)?, 2);
+        assert!(matches!(tensor.size(3), Err(MlError::TensorError(TensorError::InvalidAxis { .. }))));
+        assert!(tensor.size(-4).is_err());
+
+        let scalar = Tensor::<f32>::scalar(1.0);
+        assert_eq!((scalar.ndim(), scalar.numel()), (0, 1));
+        assert!(scalar.size(0).is_err());
+
+        let empty = Tensor::<f32>::from_vec(vec![], &[2, 0])?;
+        assert!(empty.is_empty());
+        assert_eq!((empty.numel(), empty.size(1)?), (0, 0));
+        Ok(())
+    }
+
+    #[test]
+    fn test_normalize_dim() -> MlResult<()> {
+        assert_eq!(normalize_dim(-3, 3)?, 0);
+        assert_eq!(normalize_dim(-1, 3)?, 2);
+        assert_eq!(normalize_dim(0, 3)?, 0);
+        assert_eq!(normalize_dim(2, 3)?, 2);
+        for dim in [-4, 3, i32::MIN, i32::MAX] {
+            match normalize_dim(dim, 3) {
+                Err(MlError::TensorError(TensorError::InvalidAxis { axis, ndim })) => assert_eq!((axis, ndim), (dim, 3)),
+                other => panic!("expected an invalid axis error for {}, got {:?}", dim, other),
+            }
+        }
+        assert!(normalize_dim(0, 0).is_err());
+        assert_eq!(
+            normalize_dim(-4, 3).unwrap_err().to_string(),
+            "Tensor error: Invalid axis -4 for tensor with 3 dimensions"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_dim_taking_ops_agree() -> MlResult<()> {
+        let tensor = Tensor::<f32>::from_vec((0..24).map(|x| x as f32).collect(), &[2, 3, 4])?;
+        // (dim, normalized dimension or None when out of range)
+        let cases = [(-3, Some(0)), (-1, Some(2)), (0, Some(0)), (2, Some(2)), (-4, None), (3, None)];
+
+        for (dim, expected) in cases {
+            let size = tensor.size(dim).ok();
+            let softmax = tensor.softmax(dim).ok().map(|t| t.shape().to_vec());
+            let select = tensor.select(dim, 0).ok().map(|t| t.shape().to_vec());
+            let max = ops!(tensor, Matmax, Some(dim), true).ok().map(|(t, _)| t.shape().to_vec());
+
+            match expected {
+                Some(d) => {
+                    let mut reduced = tensor.shape().to_vec();
+                    reduced.remove(d);
+                    let mut kept = tensor.shape().to_vec();
+                    kept[d] = 1;
+                    assert_eq!(size, Some(tensor.shape()[d]), "size({})", dim);
+                    assert_eq!(softmax.as_deref(), Some(tensor.shape()), "softmax({})", dim);
+                    assert_eq!(select, Some(reduced), "select({})", dim);
+                    assert_eq!(max, Some(kept), "Matmax({})", dim);
+                }
+                None => assert!(size.is_none() && softmax.is_none() && select.is_none() && max.is_none(), "dim {}", dim),
+            }
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_clone() -> MlResult<()> {
+        let original = Tensor::<f32>::from_vec_owned(vec![1.0, 2.0, 3.0], &[3])?;
+        let mut copy = original.clone();
+        copy.data_mut()[0] = 10.0;
+        assert_eq!(original.data(), &[1.0, 2.0, 3.0]);
+        assert_eq!(copy.data(), &[10.0, 2.0, 3.0]);
+        assert_eq!(copy.shape(), original.shape());
+        assert_eq!(copy.requires_grad(), original.requires_grad());
+
+        #[cfg(feature = "enable_backpropagation")]
+        {
+            let mut with_grad = original.clone();
+            with_grad.set_grad(&original)?;
+            assert!(with_grad.grad().is_some());
+            assert!(with_grad.clone().grad().is_none());
+        }
+
+        let shared = ArcTensor::new(original);
+        let alias = shared.clone();
+        assert!(Arc::ptr_eq(&shared.0, &alias.0));
+
+        let deep = shared.deep_clone();
+        assert!(!Arc::ptr_eq(&shared.0, &deep.0));
+        assert_ne!(shared.data().as_ptr(), deep.data().as_ptr());
+        assert_eq!(deep.data(), shared.data());
+        assert_eq!(deep.shape(), &[3]);
+
+        let empty = Tensor::<f32>::default();
+        assert_eq!(empty.shape(), &[0]);
+        assert!(empty.is_empty());
+        assert_eq!(ArcTensor::<usize>::default().shape(), &[0]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_fill_zero_copy_from() -> MlResult<()> {
+        let mut buffer = Tensor::<f32>::from_vec_owned(vec![1.0; 6], &[2, 3])?;
+        buffer.fill_(2.5)?;
+        assert_eq!(buffer.data(), &[2.5; 6]);
+        buffer.zero_()?;
+        assert_eq!(buffer.data(), &[0.0; 6]);
+
+        let source = Tensor::<f32>::from_vec((0..6).map(|x| x as f32).collect(), &[3, 2])?;
+        let transposed = source.permute(&[1, 0])?;
+        buffer.copy_from(&*transposed)?;
+        assert_eq!(buffer.data(), &[0.0, 2.0, 4.0, 1.0, 3.0, 5.0]);
+        buffer.copy_from(&*source.view(&[2, 3])?)?;
+        assert_eq!(buffer.data(), source.data());
+
+        assert!(matches!(
+            buffer.copy_from(&*source),
+            Err(MlError::TensorError(TensorError::InvalidShape { .. }))
+        ));
+
+        let mut labels = Tensor::<usize>::from_vec_owned(vec![3, 4], &[2])?;
+        labels.zero_()?;
+        assert_eq!(labels.data(), &[0, 0]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_into_iter_and_collect_tensor() -> MlResult<()> {
+        let tensor = Tensor::<f32>::from_vec_owned(vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0], &[2, 3])?;
+        let expected: Vec<f32> = tensor.data().iter().map(|x| x * 10.0).collect();
+        let ptr = tensor.data().as_ptr();
+
+        // the buffer is moved through the pipeline, not copied
+        let scaled = tensor.into_iter().map(|x| x * 10.0).collect_tensor(&[3, 2])?;
+        assert_eq!(scaled.data().as_ptr(), ptr);
+        assert_eq!(scaled.shape(), &[3, 2]);
+        assert_eq!(scaled.data(), expected.as_slice());
+
+        let flat: Tensor<usize> = (0..4).collect();
+        assert_eq!(flat.shape(), &[4]);
+        assert_eq!(flat.into_iter().collect::<Vec<_>>(), vec![0, 1, 2, 3]);
+
+        assert!(matches!(
+            (0..5).map(|x| x as f32).collect_tensor(&[2, 2]),
+            Err(MlError::TensorError(TensorError::InvalidDataLength { expected: 4, got: 5 }))
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn test_get_mut_and_set() -> MlResult<()> {
+        let mut tensor = Tensor::<f32>::from_vec_owned(vec![0.0; 6], &[2, 3])?;
+        tensor.set(&[1, 2], 7.0)?;
+        *tensor.get_mut(&[0, 1]).unwrap() = 3.0;
+        assert_eq!(tensor.data(), &[0.0, 3.0, 0.0, 0.0, 0.0, 7.0]);
+
+        // [0, 3] has the flat offset of [1, 0] but is out of range for dimension 1
+        assert!(tensor.get_mut(&[0, 3]).is_none());
+        match tensor.set(&[0, 3], 1.0) {
+            Err(MlError::TensorError(TensorError::InvalidOperation { op: "set", reason })) => {
+                assert_eq!(reason, "index 3 is out of range for dimension 1 with size 3")
+            }
+            other => panic!("unexpected result {:?}", other),
+        }
+        assert!(tensor.set(&[1], 1.0).is_err());
+        assert!(tensor.get_mut(&[1, 0, 0]).is_none());
+        assert_eq!(tensor.data(), &[0.0, 3.0, 0.0, 0.0, 0.0, 7.0]);
+        Ok(())
+    }
+
+    #[cfg(feature = "enable_backpropagation")]
+    #[test]
+    fn test_in_place_refuses_graph_tensors() -> MlResult<()> {
+        let mut tensor = Tensor::<f32>::from_vec_owned(vec![1.0, 2.0], &[2])?;
+        let other = Tensor::<f32>::from_vec(vec![1.0, 1.0], &[2])?;
+        tensor.set_grad_fn(Box::new(OpNode::new("test", &[&*other])));
+
+        assert!(tensor.fill_(0.0).is_err());
+        assert!(tensor.zero_().is_err());
+        assert!(tensor.copy_from(&*other).is_err());
+        assert!(tensor.add_(&*other).is_err());
+        assert!(tensor.set(&[0], 5.0).is_err());
+        assert!(tensor.get_mut(&[0]).is_none());
+        assert!(tensor.ema_update(&*other, 0.5).is_err());
+        let a = Tensor::<f32>::from_vec(vec![1.0, 2.0], &[2, 1])?;
+        let b = Tensor::<f32>::from_vec(vec![1.0], &[1, 1])?;
+        let mut product = Tensor::<f32>::from_vec_owned(vec![0.0, 0.0], &[2, 1])?;
+        product.set_grad_fn(Box::new(OpNode::new("test", &[&*other])));
+        assert!(matmul_into(&mut product, &*a, &*b, 0.0).is_err());
+        assert_eq!(tensor.data(), &[1.0, 2.0]);
+        Ok(())
+    }
+
+    #[cfg(feature = "enable_backpropagation")]
+    #[test]
+    #[should_panic(expected = "autograd graph")]
+    fn test_scalar_assign_refuses_graph_tensors() {
+        let mut tensor = Tensor::<f32>::from_vec_owned(vec![1.0, 2.0], &[2]).unwrap();
+        let other = Tensor::<f32>::from_vec(vec![1.0, 1.0], &[2]).unwrap();
+        tensor.set_grad_fn(Box::new(OpNode::new("test", &[&*other])));
+        tensor += 1.0;
+    }
+
+    #[cfg(feature = "enable_backpropagation")]
+    #[test]
+    #[should_panic(expected = "autograd graph")]
+    fn test_data_mut_refuses_graph_tensors() {
+        let mut tensor = Tensor::<f32>::from_vec_owned(vec![1.0, 2.0], &[2]).unwrap();
+        let other = Tensor::<f32>::from_vec(vec![1.0, 1.0], &[2]).unwrap();
+        tensor.set_grad_fn(Box::new(OpNode::new("test", &[&*other])));
+        tensor.data_mut()[0] = 5.0;
+    }
+
+    #[cfg(feature = "enable_backpropagation")]
+    #[test]
+    #[should_panic(expected = "autograd graph")]
+    fn test_iter_mut_refuses_graph_tensors() {
+        let mut tensor = Tensor::<f32>::from_vec_owned(vec![1.0, 2.0], &[2]).unwrap();
+        let other = Tensor::<f32>::from_vec(vec![1.0, 1.0], &[2]).unwrap();
+        tensor.set_grad_fn(Box::new(OpNode::new("test", &[&*other])));
+        tensor.iter_mut().for_each(|x| *x = 0.0);
+    }
+
+    #[test]
+    fn test_strides() -> MlResult<()> {
+        let tensor = Tensor::<f32>::from_vec(vec![0.0; 24], &[2, 3, 4])?;
+        assert_eq!(tensor.strides(), vec![12, 4, 1]);
+        assert!(tensor.is_contiguous());
+
+        assert_eq!(Tensor::<f32>::from_vec(vec![0.0; 5], &[5])?.strides(), vec![1]);
+        assert_eq!(Tensor::<f32>::scalar(1.0).strides(), Vec::<usize>::new());
+        assert_eq!(Tensor::<f32>::from_vec(vec![], &[2, 0, 3])?.strides(), vec![0, 3, 1]);
+
+        // the strides of a view follow its own shape
+        let view = tensor.view(&[4, 6])?;
+        assert_eq!(view.strides(), vec![6, 1]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_view() -> MlResult<()> {
+        let tensor = Tensor::<f32>::from_vec((0..12).map(|x| x as f32).collect(), &[3, 4])?;
+        assert!(tensor.is_contiguous());
+
+        let view = tensor.view(&[2, 3, 2])?;
+        assert_eq!(view.shape(), &[2, 3, 2]);
+        assert_eq!(view.data().as_ptr(), tensor.data().as_ptr());
+
+        let same = tensor.view(&[3, 4])?;
+        assert!(std::sync::Arc::ptr_eq(&tensor.0, &same.0));
+
+        match tensor.view(&[5, 2]).err().unwrap() {
+            MlError::TensorError(TensorError::InvalidShape { expected, got }) => {
+                assert_eq!(expected, vec![3, 4]);
+                assert_eq!(got, vec![5, 2]);
+            }
+            e => panic!("unexpected error {}", e),
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_hash_as_map_key() -> MlResult<()> {
+        let mut cache = std::collections::HashMap::new();
+        cache.insert(Tensor::<f32>::from_vec_owned(vec![1.0, 2.0, 3.0, 4.0], &[2, 2])?, "square");
+        cache.insert(Tensor::<f32>::from_vec_owned(vec![1.0, 2.0, 3.0, 4.0], &[4])?, "flat");
+
+        assert_eq!(cache.len(), 2);
+        assert_eq!(cache[&Tensor::<f32>::from_vec_owned(vec![1.0, 2.0, 3.0, 4.0], &[2, 2])?], "square");
+        assert!(!cache.contains_key(&Tensor::<f32>::from_vec_owned(vec![1.0, 2.0, 3.0, 5.0], &[4])?));
+
+        // equal tensors must hash equally, including signed zeros
+        let positive = Tensor::<f32>::from_vec_owned(vec![0.0], &[1])?;
+        let negative = Tensor::<f32>::from_vec_owned(vec![-0.0], &[1])?;
+        assert!(positive == negative);
+        let set: std::collections::HashSet<_> = [positive, negative].into_iter().collect();
+        assert_eq!(set.len(), 1);
+        Ok(())
+    }
+
+    #[test]
+    fn test_nan_and_signed_zero_comparison() -> MlResult<()> {
+        use std::cmp::Ordering;
+
+        let nan = Tensor::<f32>::from_vec_owned(vec![f32::NAN], &[1])?;
+        let other_nan = Tensor::<f32>::from_vec_owned(vec![-f32::NAN], &[1])?;
+        let one = Tensor::<f32>::from_vec_owned(vec![1.0], &[1])?;
+        let inf = Tensor::<f32>::from_vec_owned(vec![f32::INFINITY], &[1])?;
+
+        // NaN equals NaN whatever the sign or payload, and sorts above +inf
+        assert!(nan == nan);
+        assert!(nan == other_nan);
+        assert_eq!(nan.cmp(&other_nan), Ordering::Equal);
+        let set: std::collections::HashSet<_> = [nan.clone(), other_nan.clone()].into_iter().collect();
+        assert_eq!(set.len(), 1);
+        assert!(nan != one);
+        assert_eq!(nan.cmp(&one), Ordering::Greater);
+        assert_eq!(inf.partial_cmp(&nan), Some(Ordering::Less));
+
+        let positive = Tensor::<f64>::from_vec_owned(vec![0.0, 2.0], &[2])?;
+        let negative = Tensor::<f64>::from_vec_owned(vec![-0.0, 2.0], &[2])?;
+        assert!(positive == negative);
+        assert_eq!(positive.cmp(&negative), Ordering::Equal);
+
+        let mut tensors = vec![nan.clone(), one.clone(), inf.clone(), other_nan.clone(), Tensor::<f32>::from_vec_owned(vec![-1.0], &[1])?];
+        tensors.sort();
+        let sorted: Vec<f32> = tensors.iter().map(|t| t.data[0]).collect();
+        assert_eq!(&sorted[..3], &[-1.0, 1.0, f32::INFINITY]);
+        assert!(sorted[3..].iter().all(|v| v.is_nan()));
+
+        let mut reversed = tensors.clone();
+        reversed.reverse();
+        reversed.sort();
+        assert_eq!(reversed, tensors);
+        Ok(())
+    }
+
+    #[test]
+    fn test_ordering_compares_shape_first() -> MlResult<()> {
+        use std::cmp::Ordering;
+        use std::collections::HashSet;
+
+        let row = Tensor::<f32>::from_vec_owned(vec![1.0, 2.0, 3.0, 4.0], &[1, 4])?;
+        let square = Tensor::<f32>::from_vec_owned(vec![1.0, 2.0, 3.0, 4.0], &[2, 2])?;
+
+        assert!(row != square);
+        assert_eq!(row.cmp(&square), Ordering::Less);
+        assert_eq!(square.partial_cmp(&row), Some(Ordering::Greater));
+        let set: HashSet<_> = [row.clone(), square.clone()].into_iter().collect();
+        assert_eq!(set.len(), 2);
+
+        // shape decides before data
+        let small_square = Tensor::<f32>::from_vec_owned(vec![0.0; 4], &[2, 2])?;
+        assert_eq!(row.cmp(&small_square), Ordering::Less);
+
+        let mut tensors = vec![square.clone(), row.clone(), square.clone()];
+        tensors.sort();
+        tensors.dedup();
+        assert_eq!(tensors, vec![row.clone(), square.clone()]);
+        assert_eq!(row.cmp(&row.clone()), Ordering::Equal);
+        Ok(())
+    }
+
+    #[test]
+    fn test_fingerprint() -> MlResult<()> {
+        let a = Tensor::<f64>::from_vec(vec![1.5, -2.0, 0.25], &[3])?;
+        let b = Tensor::<f64>::from_vec(vec![1.5, -2.0, 0.25], &[3])?;
+        assert_eq!(a.fingerprint(), b.fingerprint());
+
+        // flipping the lowest mantissa bit of one element changes the fingerprint
+        let flipped = f64::from_bits(0.25f64.to_bits() ^ 1);
+        let c = Tensor::<f64>::from_vec(vec![1.5, -2.0, flipped], &[3])?;
+        assert_ne!(a.fingerprint(), c.fingerprint());
+        assert_ne!(a.fingerprint(), a.view(&[3, 1])?.fingerprint());
+
+        let zeros = Tensor::<f32>::from_vec(vec![0.0, -0.0], &[2])?;
+        let positive = Tensor::<f32>::from_vec(vec![0.0, 0.0], &[2])?;
+        assert_eq!(zeros.fingerprint(), positive.fingerprint());
+
+        let mut cache = std::collections::HashMap::new();
+        cache.insert(Tensor::<f64>::from_vec_owned(vec![1.5, -2.0, 0.25], &[3])?, a.fingerprint());
+        assert_eq!(cache[&Tensor::<f64>::from_vec_owned(vec![1.5, -2.0, 0.25], &[3])?], b.fingerprint());
+        assert!(!cache.contains_key(&Tensor::<f64>::from_vec_owned(vec![1.5, -2.0, flipped], &[3])?));
+        Ok(())
+    }
+
+    #[cfg(feature = "enable_backpropagation")]
+    #[test]
+    fn test_grad_through_trait_object() -> MlResult<()> {
+        let mut tensor = Tensor::<f32>::from_vec_owned(vec![1.0, 2.0], &[2])?;
+        assert!((&tensor as &dyn TensorBase<f32>).grad().is_none());
+
+        tensor.set_grad(&*Tensor::<f32>::from_vec(vec![0.5, -0.5], &[2])?)?;
+        let tensor: &dyn TensorBase<f32> = &tensor;
+        assert_eq!(tensor.grad().map(|g| g.data()), Some(&[0.5, -0.5][..]));
+        Ok(())
+    }
+
     #[test]
     fn test_add() -> MlResult<()> {
-        let first = Tensor::<f32>::new(vec![vec![1.0, 2.0]]);
-        let second = Tensor::<f32>::new(vec![vec![3.0, 4.0]]);
+        let first = Tensor::<f32>::new(vec![vec![1.0, 2.0]])?;
+        let second = Tensor::<f32>::new(vec![vec![3.0, 4.0]])?;
         let m_add = ops!(first, Add, second)?;
         let s_add = first + second;
-        let et = Tensor::<f32>::new(vec![vec![4.0, 6.0]]);
+        let et = Tensor::<f32>::new(vec![vec![4.0, 6.0]])?;
 
         assert_tensor_eq(&m_add, &et)?;
         assert_tensor_eq(&s_add, &et)
     }
     #[test]
     fn test_sub() -> MlResult<()> {
-        let first = Tensor::<f32>::new(vec![vec![1.0, 2.0]]);
-        let second = Tensor::<f32>::new(vec![vec![3.0, 4.0]]);
+        let first = Tensor::<f32>::new(vec![vec![1.0, 2.0]])?;
+        let second = Tensor::<f32>::new(vec![vec![3.0, 4.0]])?;
         let m_sub = ops!(first, Sub, second)?;
         let s_sub = first - second;
-        let et = Tensor::<f32>::new(vec![vec![-2.0, -2.0]]);
+        let et = Tensor::<f32>::new(vec![vec![-2.0, -2.0]])?;
 
         assert_tensor_eq(&m_sub, &et)?;
         assert_tensor_eq(&s_sub, &et)
     }
     #[test]
     fn test_mul_symbol() -> MlResult<()> {
-        let first = Tensor::<f32>::new(vec![vec![1.0, 2.0]]);
-        let second = Tensor::<f32>::new(vec![vec![3.0, 4.0]]);
+        let first = Tensor::<f32>::new(vec![vec![1.0, 2.0]])?;
+        let second = Tensor::<f32>::new(vec![vec![3.0, 4.0]])?;
         let m_mul = ops!(first, Mul, second)?;
         let s_mul = first * second;
-        let et = Tensor::<f32>::new(vec![vec![3.0, 8.0]]);
+        let et = Tensor::<f32>::new(vec![vec![3.0, 8.0]])?;
 
         assert_tensor_eq(&m_mul, &et)?;
         assert_tensor_eq(&s_mul, &et)
     }
     #[test]
     fn test_div_symbol() -> MlResult<()> {
-        let first = Tensor::<f32>::new(vec![vec![1.0, 2.0]]);
-        let second = Tensor::<f32>::new(vec![vec![2.0, 4.0]]);
+        let first = Tensor::<f32>::new(vec![vec![1.0, 2.0]])?;
+        let second = Tensor::<f32>::new(vec![vec![2.0, 4.0]])?;
         let m_div = ops!(first, Div, second)?;
         let s_div = first / second;
-        let et = Tensor::<f32>::new(vec![vec![0.5, 0.5]]);
+        let et = Tensor::<f32>::new(vec![vec![0.5, 0.5]])?;
 
         assert_tensor_eq(&m_div, &et)?;
         assert_tensor_eq(&s_div, &et)
@@ -549,65 +1748,65 @@ mod tests {
 
     #[test]
     fn test_macro_matmul() {
-        let first = Tensor::new(vec![vec![1.0, 2.0]]);
-        let second = Tensor::new(vec![vec![3.0], vec![4.0]]);
+        let first = Tensor::new(vec![vec![1.0, 2.0]]).unwrap();
+        let second = Tensor::new(vec![vec![3.0], vec![4.0]]).unwrap();
         let result = ops!(first, Matmul, second).unwrap();
         assert_eq!(result.data(), vec![11.0]);
     }
 
     #[test]
     fn test_macro_exp() {
-        let tensor = Tensor::new(vec![vec![1.0, 2.0]]);
+        let tensor = Tensor::new(vec![vec![1.0, 2.0]]).unwrap();
         let result = ops!(tensor, Exp).unwrap();
         assert_eq!(result.data(), vec![std::f32::consts::E, 7.389056]);
     }
 
     #[test]
     fn test_macro_neg() {
-        let tensor = Tensor::new(vec![vec![1.0, -2.0]]);
+        let tensor = Tensor::new(vec![vec![1.0, -2.0]]).unwrap();
         let result = ops!(tensor, Neg).unwrap();
         assert_eq!(result.data(), vec![-1.0, 2.0]);
     }
 
     #[test]
     fn test_macro_sqrt() {
-        let tensor = Tensor::new(vec![vec![1.0, 4.0]]);
+        let tensor = Tensor::new(vec![vec![1.0, 4.0]]).unwrap();
         let result = ops!(tensor, Sqrt).unwrap();
         assert_eq!(result.data(), vec![1.0, 2.0]);
     }
 
     #[test]
     fn test_macro_abs() {
-        let tensor = Tensor::new(vec![vec![1.0, -2.0]]);
+        let tensor = Tensor::new(vec![vec![1.0, -2.0]]).unwrap();
         let result = ops!(tensor, Abs).unwrap();
         assert_eq!(result.data(), vec![1.0, 2.0]);
     }
 
     #[test]
     fn test_macro_square() {
-        let tensor = Tensor::new(vec![vec![2.0, 3.0]]);
+        let tensor = Tensor::new(vec![vec![2.0, 3.0]]).unwrap();
         let result = ops!(tensor, Square).unwrap();
         assert_eq!(result.data(), vec![4.0, 9.0]);
     }
 
     #[test]
     fn test_macro_log() {
-        let tensor = Tensor::new(vec![vec![1.0, std::f32::consts::E]]);
+        let tensor = Tensor::new(vec![vec![1.0, std::f32::consts::E]]).unwrap();
         let result = ops!(tensor, Log).unwrap();
         assert_eq!(result.data(), vec![0.0, 0.99999994]);
     }
 
     #[test]
     fn test_macro_pow() {
-        let tensor = Tensor::new(vec![vec![2.0, 3.0]]);
+        let tensor = Tensor::new(vec![vec![2.0, 3.0]]).unwrap();
         let result = ops!(tensor, Pow, 2.0).unwrap();
         assert_eq!(result.data(), vec![4.0, 9.0]);
     }
 
     #[test]
     fn tensor_ops_add_scalar() -> MlResult<()> {
-        let first = Tensor::<f32>::new(vec![vec![1.0, 2.0]]);
-        let et = Tensor::<f32>::new(vec![vec![3.0, 4.0]]);
+        let first = Tensor::<f32>::new(vec![vec![1.0, 2.0]])?;
+        let et = Tensor::<f32>::new(vec![vec![3.0, 4.0]])?;
         let result = scalar_ops!(first, Add, 2.0)?;
         // 텐서와 스칼라의 차원이 맞지 않아, 오류 발생.
         // 스칼라 연산 메서드를 따로 구현하야하나?
@@ -615,42 +1814,74 @@ mod tests {
     }
     #[test]
     fn tensor_ops_sub_scalar() -> MlResult<()> {
-        let first = Tensor::<f32>::new(vec![vec![1.0, 2.0]]);
-        let et = Tensor::<f32>::new(vec![vec![-1.0, 0.0]]);
+        let first = Tensor::<f32>::new(vec![vec![1.0, 2.0]])?;
+        let et = Tensor::<f32>::new(vec![vec![-1.0, 0.0]])?;
         let result = scalar_ops!(first, Sub, 2.0)?;
 
         assert_tensor_eq(&result, &et)
     }
     #[test]
     fn tensor_ops_mul_scalar() -> MlResult<()> {
-        let first = Tensor::<f32>::new(vec![vec![1.0, 2.0]]);
-        let et = Tensor::<f32>::new(vec![vec![2.0, 4.0]]);
+        let first = Tensor::<f32>::new(vec![vec![1.0, 2.0]])?;
+        let et = Tensor::<f32>::new(vec![vec![2.0, 4.0]])?;
         let result = scalar_ops!(first, Mul , 2.0)?;
 
         assert_tensor_eq(&result, &et)
     }
     #[test]
     fn tensor_ops_div_scalar() -> MlResult<()> {
-        let first = Tensor::<f32>::new(vec![vec![1.0, 2.0]]);
-        let et = Tensor::<f32>::new(vec![vec![0.5, 1.0]]);
+        let first = Tensor::<f32>::new(vec![vec![1.0, 2.0]])?;
+        let et = Tensor::<f32>::new(vec![vec![0.5, 1.0]])?;
         let result = scalar_ops!(first, Div , 2.0)?;
 
         assert_tensor_eq(&result, &et)
     }
 
     #[test]
+    fn tensor_ops_scalar_reverse_and_extrema() -> MlResult<()> {
+        let tensor = Tensor::<f32>::new(vec![vec![1.0, 2.0, 4.0]])?;
+
+        assert_eq!(scalar_ops!(2.0, RSub, tensor)?.data(), &[1.0, 0.0, -2.0]);
+        assert_eq!(scalar_ops!(2.0, RDiv, tensor)?.data(), &[2.0, 1.0, 0.5]);
+        assert_eq!(scalar_ops!(tensor, Pow, 2.0)?.data(), &[1.0, 4.0, 16.0]);
+        assert_eq!(scalar_ops!(tensor, Max, 2.0)?.data(), &[2.0, 2.0, 4.0]);
+        let result = scalar_ops!(tensor, Min, 2.0)?;
+        assert_eq!(result.data(), &[1.0, 2.0, 2.0]);
+        assert_eq!(result.shape(), &[1, 3]);
+        Ok(())
+    }
+
+    #[test]
+    fn tensor_ops_scalar_evaluates_tensor_once() -> MlResult<()> {
+        let mut calls = 0;
+        let mut make = || {
+            calls += 1;
+            Tensor::<f32>::from_vec(vec![1.0, 2.0], &[2])
+        };
+
+        let result = scalar_ops!(make()?, Add, 1.0)?;
+        assert_eq!(result.data(), &[2.0, 3.0]);
+        let result = scalar_ops!(3.0, RSub, make()?)?;
+        assert_eq!(result.data(), &[2.0, 1.0]);
+        assert_eq!(calls, 2);
+        Ok(())
+    }
+
+    #[test]
+    #[allow(deprecated)]
     fn tensor_ops_scalar_sub() -> MlResult<()> {
-        let first = Tensor::<f32>::new(vec![vec![1.0, 2.0]]);
-        let et = Tensor::<f32>::new(vec![vec![1.0, 0.0]]);
+        let first = Tensor::<f32>::new(vec![vec![1.0, 2.0]])?;
+        let et = Tensor::<f32>::new(vec![vec![1.0, 0.0]])?;
         let result = scalar_ops!(2.0, buS , first)?;
 
         assert_tensor_eq(&result, &et)
 
     }
     #[test]
+    #[allow(deprecated)]
     fn tensor_ops_scalar_div() -> MlResult<()> {
-        let first = Tensor::<f32>::new(vec![vec![1.0, 2.0]]);
-        let et = Tensor::<f32>::new(vec![vec![2.0, 1.0]]);
+        let first = Tensor::<f32>::new(vec![vec![1.0, 2.0]])?;
+        let et = Tensor::<f32>::new(vec![vec![2.0, 1.0]])?;
         let result = scalar_ops!(2.0, viD , first)?;
 
         assert_tensor_eq(&result, &et)
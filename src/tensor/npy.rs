@@ -0,0 +1,221 @@
+use crate::{MlError, MlResult};
+use crate::tensor::{ArcTensor, FormatError, Tensor, TensorBase};
+
+const NPY_MAGIC: &[u8; 6] = b"\x93NUMPY";
+const NPY_ALIGN: usize = 64;
+
+impl Tensor<f32> {
+    /// Loads a tensor from a NumPy `.npy` file
+    ///
+    /// Little-endian `f4` and `f8` arrays are supported; `f8` values are converted
+    /// to `f32`. Fortran-order arrays are transposed into row-major order.
+    ///
+    /// # Arguments
+    /// * `path` - The file to read
+    pub fn from_npy_file(path: &str) -> MlResult<ArcTensor<f32>> {
+        let bytes = std::fs::read(path).map_err(|e| MlError::StringError(format!("I/O error: {}", e)))?;
+        Self::from_npy_bytes(&bytes)
+    }
+
+    /// Parses a tensor from the contents of a `.npy` file
+    ///
+    /// # Returns
+    /// * `Ok(ArcTensor)` on success
+    /// * `Err(MlError::StringError)` if the header is malformed or the dtype is unsupported
+    /// * `Err(MlError::FormatError)` with `SizeOverflow` if the byte size of the shape overflows
+    pub fn from_npy_bytes(bytes: &[u8]) -> MlResult<ArcTensor<f32>> {
+        if bytes.len() < 10 || &bytes[..6] != NPY_MAGIC {
+            return Err(npy_error("missing .npy magic string".to_string()));
+        }
+
+        let (header_len, header_start) = match bytes[6] {
+            1 => (u16::from_le_bytes([bytes[8], bytes[9]]) as usize, 10),
+            2 | 3 if bytes.len() >= 12 => (u32::from_le_bytes([bytes[8], bytes[9], bytes[10], bytes[11]]) as usize, 12),
+            major => return Err(npy_error(format!("unsupported .npy version {}.{}", major, bytes[7]))),
+        };
+        let body_start = header_start + header_len;
+        let header = bytes.get(header_start..body_start)
+            .and_then(|h| std::str::from_utf8(h).ok())
+            .ok_or_else(|| npy_error("truncated or invalid header".to_string()))?;
+
+        let descr = header_value(header, "descr")?;
+        let descr = descr.trim_matches(|c| c == '\'' || c == '"');
+        let fortran_order = match header_value(header, "fortran_order")? {
+            "True" => true,
+            "False" => false,
+            other => return Err(npy_error(format!("invalid fortran_order value {}", other))),
+        };
+        let shape = parse_shape(header_value(header, "shape")?)?;
+
+        let body = &bytes[body_start..];
+        let data: Vec<f32> = match descr {
+            "<f4" => read_values(body, &shape, 4)?.map(|b| f32::from_le_bytes([b[0], b[1], b[2], b[3]])).collect(),
+            "<f8" => read_values(body, &shape, 8)?
+                .map(|b| f64::from_le_bytes([b[0], b[1], b[2], b[3], b[4], b[5], b[6], b[7]]) as f32)
+                .collect(),
+            other => return Err(npy_error(format!("unsupported dtype '{}', expected '<f4' or '<f8'", other))),
+        };
+
+        let data = match fortran_order {
+            true => fortran_to_row_major(&data, &shape),
+            false => data,
+        };
+        Tensor::<f32>::from_vec(data, &shape)
+    }
+}
+
+impl dyn TensorBase<f32> + '_ {
+    /// Saves the tensor as a NumPy `.npy` file (version 1.0, `<f4`, C order)
+    ///
+    /// # Arguments
+    /// * `path` - The file to write
+    pub fn save_npy(&self, path: &str) -> MlResult<()> {
+        std::fs::write(path, self.to_npy_bytes()).map_err(|e| MlError::StringError(format!("I/O error: {}", e)))
+    }
+
+    /// Encodes the tensor in the `.npy` format
+    pub fn to_npy_bytes(&self) -> Vec<u8> {
+        let shape = match self.shape() {
+            [] => "()".to_string(),
+            [dim] => format!("({},)", dim),
+            dims => format!("({})", dims.iter().map(|d| d.to_string()).collect::<Vec<_>>().join(", ")),
+        };
+        let mut header = format!("{{'descr': '<f4', 'fortran_order': False, 'shape': {}, }}", shape);
+        let padding = (NPY_ALIGN - (10 + header.len() + 1) % NPY_ALIGN) % NPY_ALIGN;
+        header.push_str(&" ".repeat(padding));
+        header.push('\n');
+
+        let mut bytes = Vec::with_capacity(10 + header.len() + self.data().len() * 4);
+        bytes.extend_from_slice(NPY_MAGIC);
+        bytes.extend_from_slice(&[1, 0]);
+        bytes.extend_from_slice(&(header.len() as u16).to_le_bytes());
+        bytes.extend_from_slice(header.as_bytes());
+        for value in self.data() {
+            bytes.extend_from_slice(&value.to_le_bytes());
+        }
+        bytes
+    }
+}
+
+/// Returns the raw text of a value in the header dictionary
+fn header_value<'a>(header: &'a str, key: &str) -> MlResult<&'a str> {
+    let start = header.find(&format!("'{}':", key))
+        .ok_or_else(|| npy_error(format!("header is missing '{}'", key)))? + key.len() + 3;
+    let rest = header[start..].trim_start();
+    let end = match rest.chars().next() {
+        Some('(') => rest.find(')').map(|i| i + 1),
+        _ => rest.find([',', '}']),
+    }.ok_or_else(|| npy_error(format!("malformed value for '{}'", key)))?;
+    Ok(rest[..end].trim())
+}
+
+fn parse_shape(text: &str) -> MlResult<Vec<usize>> {
+    text.trim_start_matches('(').trim_end_matches(')')
+        .split(',')
+        .map(str::trim)
+        .filter(|dim| !dim.is_empty())
+        .map(|dim| dim.parse().map_err(|_| npy_error(format!("invalid shape {}", text))))
+        .collect()
+}
+
+/// Splits the body into one chunk per element of `shape`
+///
+/// The dims come from the file header, so the byte length is computed with checked
+/// arithmetic rather than trusted to fit in `usize`.
+fn read_values<'a>(body: &'a [u8], shape: &[usize], size: usize) -> MlResult<std::slice::ChunksExact<'a, u8>> {
+    let len = shape.iter().try_fold(size, |acc, &d| acc.checked_mul(d))
+        .ok_or_else(|| MlError::FormatError(FormatError::SizeOverflow { shape: shape.to_vec() }))?;
+    if body.len() < len {
+        return Err(npy_error(format!("expected {} bytes of data, got {}", len, body.len())));
+    }
+    Ok(body[..len].chunks_exact(size))
+}
+
+fn fortran_to_row_major(data: &[f32], shape: &[usize]) -> Vec<f32> {
+    (0..data.len()).map(|idx| {
+        let (mut remaining, mut offset, mut stride) = (idx, 0, 1);
+        let mut position = vec![0; shape.len()];
+        for (i, &dim) in shape.iter().enumerate().rev() {
+            position[i] = remaining % dim;
+            remaining /= dim;
+        }
+        for (&pos, &dim) in position.iter().zip(shape) {
+            offset += pos * stride;
+            stride *= dim;
+        }
+        data[offset]
+    }).collect()
+}
+
+fn npy_error(reason: String) -> MlError {
+    MlError::StringError(format!("invalid .npy data: {}", reason))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{MlError, MlResult};
+    use crate::tensor::*;
+
+    fn fixture(name: &str) -> String {
+        format!("{}/testdata/{}", env!("CARGO_MANIFEST_DIR"), name)
+    }
+
+    #[test]
+    fn test_npy_round_trip() -> MlResult<()> {
+        for shape in [vec![], vec![0], vec![5], vec![2, 3], vec![2, 3, 4]] {
+            let len = shape.iter().product::<usize>();
+            let tensor = Tensor::<f32>::from_vec((0..len).map(|x| x as f32 * 0.5 - 1.0).collect(), &shape)?;
+            let restored = Tensor::<f32>::from_npy_bytes(&tensor.to_npy_bytes())?;
+            assert_eq!(restored.shape(), &shape[..]);
+            assert_eq!(restored.data(), tensor.data());
+        }
+
+        let path = std::env::temp_dir().join(format!("mit_{}_tensor.npy", std::process::id())).to_string_lossy().into_owned();
+        let tensor = Tensor::<f32>::from_vec(vec![1.0, 2.0, 3.0, 4.0], &[2, 2])?;
+        tensor.save_npy(&path)?;
+        assert_eq!(Tensor::<f32>::from_npy_file(&path)?.data(), tensor.data());
+        std::fs::remove_file(&path).unwrap();
+        Ok(())
+    }
+
+    #[test]
+    fn test_npy_fixtures() -> MlResult<()> {
+        let tensor = Tensor::<f32>::from_npy_file(&fixture("f32_2x3.npy"))?;
+        assert_eq!(tensor.shape(), &[2, 3]);
+        assert_eq!(tensor.data(), &[0.0, 1.0, 2.0, 3.0, 4.0, 5.0]);
+        assert_eq!(tensor.to_npy_bytes(), std::fs::read(fixture("f32_2x3.npy")).unwrap());
+
+        let tensor = Tensor::<f32>::from_npy_file(&fixture("f64_4.npy"))?;
+        assert_eq!(tensor.shape(), &[4]);
+        assert_eq!(tensor.data(), &[0.5, -1.5, 2.25, 1e10]);
+
+        let tensor = Tensor::<f32>::from_npy_file(&fixture("f32_2x3_fortran.npy"))?;
+        assert_eq!(tensor.shape(), &[2, 3]);
+        assert_eq!(tensor.data(), &[0.0, 1.0, 2.0, 3.0, 4.0, 5.0]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_npy_invalid() {
+        let message = Tensor::<f32>::from_npy_file(&fixture("i64_3.npy")).err().unwrap().to_string();
+        assert!(message.contains("'<i8'"));
+
+        assert!(Tensor::<f32>::from_npy_bytes(b"not an npy file").is_err());
+        let truncated = std::fs::read(fixture("f32_2x3.npy")).unwrap();
+        assert!(Tensor::<f32>::from_npy_bytes(&truncated[..truncated.len() - 4]).is_err());
+    }
+
+    #[test]
+    fn test_npy_shape_overflow() {
+        // the element count wraps to 0 in unchecked arithmetic, so an empty body would pass
+        let header = "{'descr': '<f4', 'fortran_order': False, 'shape': (4294967296, 4294967296, 2), }\n";
+        let mut bytes = b"\x93NUMPY\x01\x00".to_vec();
+        bytes.extend_from_slice(&(header.len() as u16).to_le_bytes());
+        bytes.extend_from_slice(header.as_bytes());
+
+        assert!(matches!(
+            Tensor::<f32>::from_npy_bytes(&bytes),
+            Err(MlError::FormatError(FormatError::SizeOverflow { .. }))
+        ));
+    }
+}
@@ -0,0 +1,221 @@
+use std::collections::HashMap;
+
+use crate::{MlError, MlResult};
+use crate::tensor::{ArcTensor, Tensor, TensorBase};
+
+const LOCAL_HEADER_SIG: u32 = 0x0403_4b50;
+const CENTRAL_HEADER_SIG: u32 = 0x0201_4b50;
+const END_OF_CENTRAL_DIR_SIG: u32 = 0x0605_4b50;
+const ZIP64_EXTRA_ID: u16 = 0x0001;
+
+/// Saves named tensors as a NumPy `.npz` archive
+///
+/// Each tensor is stored uncompressed as `<name>.npy`, so the archive can be
+/// read with `numpy.load`.
+///
+/// # Arguments
+/// * `path` - The file to write
+/// * `tensors` - The `(name, tensor)` pairs to save
+///
+/// # Returns
+/// * `Ok(())` on success
+/// * `Err(MlError::StringError)` if a name is used twice or the file cannot be written
+pub fn save_npz(path: &str, tensors: &[(&str, &dyn TensorBase<f32>)]) -> MlResult<()> {
+    let mut archive = Vec::new();
+    let mut central_directory = Vec::new();
+
+    for (i, (name, tensor)) in tensors.iter().enumerate() {
+        if tensors[..i].iter().any(|(other, _)| other == name) {
+            return Err(npz_error(format!("duplicate tensor name '{}'", name)));
+        }
+
+        let file_name = format!("{}.npy", name);
+        let data = tensor.to_npy_bytes();
+        let crc = crc32(&data);
+        let offset = archive.len() as u32;
+
+        push_u32(&mut archive, LOCAL_HEADER_SIG);
+        push_entry_fields(&mut archive, crc, data.len() as u32, file_name.len() as u16);
+        archive.extend_from_slice(file_name.as_bytes());
+        archive.extend_from_slice(&data);
+
+        push_u32(&mut central_directory, CENTRAL_HEADER_SIG);
+        push_u16(&mut central_directory, 20); // version made by
+        push_entry_fields(&mut central_directory, crc, data.len() as u32, file_name.len() as u16);
+        push_u16(&mut central_directory, 0); // comment length
+        push_u16(&mut central_directory, 0); // disk number
+        push_u16(&mut central_directory, 0); // internal attributes
+        push_u32(&mut central_directory, 0); // external attributes
+        push_u32(&mut central_directory, offset);
+        central_directory.extend_from_slice(file_name.as_bytes());
+    }
+
+    let central_offset = archive.len() as u32;
+    archive.extend_from_slice(&central_directory);
+    push_u32(&mut archive, END_OF_CENTRAL_DIR_SIG);
+    push_u16(&mut archive, 0);
+    push_u16(&mut archive, 0);
+    push_u16(&mut archive, tensors.len() as u16);
+    push_u16(&mut archive, tensors.len() as u16);
+    push_u32(&mut archive, central_directory.len() as u32);
+    push_u32(&mut archive, central_offset);
+    push_u16(&mut archive, 0);
+
+    std::fs::write(path, archive).map_err(|e| MlError::StringError(format!("I/O error: {}", e)))
+}
+
+/// Loads all arrays from a NumPy `.npz` archive
+///
+/// Only uncompressed (stored) entries are supported, which is what `numpy.savez` writes.
+///
+/// # Arguments
+/// * `path` - The file to read
+///
+/// # Returns
+/// A map from array name (without the `.npy` suffix) to tensor
+pub fn load_npz(path: &str) -> MlResult<HashMap<String, ArcTensor<f32>>> {
+    let bytes = std::fs::read(path).map_err(|e| MlError::StringError(format!("I/O error: {}", e)))?;
+    let eocd = (0..bytes.len().saturating_sub(21)).rev()
+        .find(|&i| read_u32(&bytes, i) == Some(END_OF_CENTRAL_DIR_SIG))
+        .ok_or_else(|| npz_error("missing end of central directory".to_string()))?;
+    let count = read_u16(&bytes, eocd + 10).unwrap_or(0) as usize;
+    let mut pos = read_u32(&bytes, eocd + 16).unwrap_or(0) as usize;
+
+    let mut tensors = HashMap::new();
+    for _ in 0..count {
+        if read_u32(&bytes, pos) != Some(CENTRAL_HEADER_SIG) {
+            return Err(npz_error("corrupt central directory".to_string()));
+        }
+        let field = |offset: usize| read_u16(&bytes, pos + offset).unwrap_or(0) as usize;
+        let (method, name_len, extra_len, comment_len) = (field(10), field(28), field(30), field(32));
+        let crc = read_u32(&bytes, pos + 16).unwrap_or(0);
+        let mut size = read_u32(&bytes, pos + 20).unwrap_or(0) as u64;
+        let mut offset = read_u32(&bytes, pos + 42).unwrap_or(0) as u64;
+        let name = bytes.get(pos + 46..pos + 46 + name_len)
+            .and_then(|name| std::str::from_utf8(name).ok())
+            .ok_or_else(|| npz_error("invalid entry name".to_string()))?;
+        let extra = bytes.get(pos + 46 + name_len..pos + 46 + name_len + extra_len).unwrap_or(&[]);
+        read_zip64_extra(extra, &mut size, &mut offset);
+        pos += 46 + name_len + extra_len + comment_len;
+
+        if method != 0 {
+            return Err(npz_error(format!("entry '{}' is compressed, only stored entries are supported", name)));
+        }
+        let offset = offset as usize;
+        if read_u32(&bytes, offset) != Some(LOCAL_HEADER_SIG) {
+            return Err(npz_error(format!("corrupt local header for '{}'", name)));
+        }
+        let data_start = offset + 30 + read_u16(&bytes, offset + 26).unwrap_or(0) as usize + read_u16(&bytes, offset + 28).unwrap_or(0) as usize;
+        let data = bytes.get(data_start..data_start + size as usize)
+            .ok_or_else(|| npz_error(format!("truncated data for '{}'", name)))?;
+        if crc32(data) != crc {
+            return Err(npz_error(format!("checksum mismatch for '{}'", name)));
+        }
+
+        let key = name.strip_suffix(".npy").unwrap_or(name).to_string();
+        tensors.insert(key, Tensor::<f32>::from_npy_bytes(data)?);
+    }
+    Ok(tensors)
+}
+
+/// Writes the fields shared by local and central headers, from "version needed" to "extra length"
+fn push_entry_fields(buf: &mut Vec<u8>, crc: u32, size: u32, name_len: u16) {
+    push_u16(buf, 20); // version needed
+    push_u16(buf, 0); // flags
+    push_u16(buf, 0); // stored
+    push_u16(buf, 0); // modification time
+    push_u16(buf, 0x21); // modification date, 1980-01-01
+    push_u32(buf, crc);
+    push_u32(buf, size);
+    push_u32(buf, size);
+    push_u16(buf, name_len);
+    push_u16(buf, 0); // extra length
+}
+
+/// Replaces saturated 32-bit fields with the values from a zip64 extra field
+fn read_zip64_extra(extra: &[u8], size: &mut u64, offset: &mut u64) {
+    let mut pos = 0;
+    while let (Some(id), Some(len)) = (read_u16(extra, pos), read_u16(extra, pos + 2)) {
+        if id == ZIP64_EXTRA_ID {
+            // the uncompressed size comes first, then the compressed size, then the offset
+            let mut field = pos + 4;
+            if *size == 0xFFFF_FFFF {
+                *size = read_u64(extra, field + 8).unwrap_or(*size);
+                field += 16;
+            }
+            if *offset == 0xFFFF_FFFF {
+                *offset = read_u64(extra, field).unwrap_or(*offset);
+            }
+        }
+        pos += 4 + len as usize;
+    }
+}
+
+fn crc32(data: &[u8]) -> u32 {
+    !data.iter().fold(!0u32, |crc, &byte| {
+        (0..8).fold(crc ^ byte as u32, |crc, _| (crc >> 1) ^ (0xEDB8_8320 & (crc & 1).wrapping_neg()))
+    })
+}
+
+fn push_u16(buf: &mut Vec<u8>, value: u16) {
+    buf.extend_from_slice(&value.to_le_bytes());
+}
+
+fn push_u32(buf: &mut Vec<u8>, value: u32) {
+    buf.extend_from_slice(&value.to_le_bytes());
+}
+
+fn read_u16(bytes: &[u8], pos: usize) -> Option<u16> {
+    Some(u16::from_le_bytes(bytes.get(pos..pos + 2)?.try_into().ok()?))
+}
+
+fn read_u32(bytes: &[u8], pos: usize) -> Option<u32> {
+    Some(u32::from_le_bytes(bytes.get(pos..pos + 4)?.try_into().ok()?))
+}
+
+fn read_u64(bytes: &[u8], pos: usize) -> Option<u64> {
+    Some(u64::from_le_bytes(bytes.get(pos..pos + 8)?.try_into().ok()?))
+}
+
+fn npz_error(reason: String) -> MlError {
+    MlError::StringError(format!("invalid .npz archive: {}", reason))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::MlResult;
+    use crate::tensor::*;
+    use crate::testing::temp_path;
+
+    #[test]
+    fn test_npz_round_trip() -> MlResult<()> {
+        let path = temp_path("weights.npz");
+        let weight = Tensor::<f32>::from_vec(vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0], &[2, 3])?;
+        let bias = Tensor::<f32>::from_vec(vec![-0.5, 0.5], &[2])?;
+        let scale = Tensor::<f32>::from_vec(vec![3.0], &[])?;
+        save_npz(&path, &[("weight", &*weight), ("bias", &*bias), ("scale", &*scale)])?;
+
+        let loaded = load_npz(&path)?;
+        assert_eq!(loaded.len(), 3);
+        assert_eq!(loaded["weight"].shape(), &[2, 3]);
+        assert_eq!(loaded["weight"].data(), weight.data());
+        assert_eq!(loaded["bias"].data(), bias.data());
+        assert_eq!(loaded["scale"].shape().len(), 0);
+        assert!(!loaded.contains_key("missing"));
+        std::fs::remove_file(&path).unwrap();
+
+        assert!(save_npz(&path, &[("bias", &*bias), ("bias", &*weight)]).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_load_numpy_savez_fixture() -> MlResult<()> {
+        let loaded = load_npz(&format!("{}/testdata/savez.npz", env!("CARGO_MANIFEST_DIR")))?;
+
+        assert_eq!(loaded.len(), 2);
+        assert_eq!(loaded["a"].shape(), &[2, 3]);
+        assert_eq!(loaded["a"].data(), &[0.0, 1.0, 2.0, 3.0, 4.0, 5.0]);
+        assert_eq!(loaded["b"].data(), &[0.5, -1.5, 2.25, 1e10]);
+        Ok(())
+    }
+}
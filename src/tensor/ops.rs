@@ -2,8 +2,129 @@ use std::ops::Deref;
 use std::sync::Arc;
 use crate::{backend, MlError, MlResult};
 use crate::backend::{Backend, Device};
-use crate::tensor::{Abs, Add, Div, Exp, Log, Matmax, Matmul, Mul, Neg, Pow, Sub, Sqrt, Square, Topk, Tensor, TensorError, ArcTensor};
-use crate::tensor::{TensorBase, Function};
+use crate::tensor::{Abs, Add, Concat, Div, Exp, Hardtanh, Log, Matmax, Matmul, Mul, Neg, Pow, Relu6, Sub, Sqrt, Square, Topk, Var, Tensor, TensorError, ArcTensor};
+use crate::tensor::{concat, normalize_dim, AsTensor, TensorBase, Function};
+use crate::tensor::indexing::check_concat_shapes;
+use crate::tensor::broadcast::{broadcast_index, broadcast_op, broadcast_shape, can_broadcast};
+#[cfg(feature = "enable_backpropagation")]
+use crate::tensor::autograd::with_grad_fn;
+
+/// Returns the second operand of a binary op, which must be present
+fn second_operand<'t>(op: &'static str, second: Option<&'t dyn TensorBase<f32>>) -> MlResult<&'t dyn TensorBase<f32>> {
+    second.ok_or_else(|| MlError::TensorError(TensorError::InvalidOperation {
+        op,
+        reason: "a second tensor is required".to_string(),
+    }))
+}
+
+/// Wraps a tensor error from `op` with the shapes of its operands, so the message names
+/// both the failing op and what it was given
+fn op_context<'a>(op: &'static str, operands: &'a [&dyn TensorBase<f32>]) -> impl FnOnce(MlError) -> MlError + 'a {
+    move |error| match error {
+        MlError::TensorError(detail @ TensorError::OpFailed { .. }) => MlError::TensorError(detail),
+        MlError::TensorError(detail) => MlError::TensorError(TensorError::OpFailed {
+            op,
+            shapes: operands.iter().map(|t| t.shape().to_vec()).collect(),
+            detail: Box::new(detail),
+        }),
+        other => other,
+    }
+}
+
+/// Validates the operands of the element-wise binary ops: equal shapes, or shapes that
+/// broadcast against each other
+fn check_elementwise_shapes<'t>(op: &'static str, first: &dyn TensorBase<f32>, second: Option<&'t dyn TensorBase<f32>>) -> MlResult<&'t dyn TensorBase<f32>> {
+    let second = second_operand(op, second)?;
+    if first.shape() != second.shape() && !can_broadcast(first.shape(), second.shape()) {
+        return Err(op_context(op, &[first, second])(MlError::TensorError(TensorError::InvalidOperation {
+            op,
+            reason: "shapes cannot be broadcast together".to_string(),
+        })));
+    }
+    Ok(second)
+}
+
+/// Applies an element-wise binary op, taking the backend's fast path for equal shapes
+fn elementwise(
+    first: &dyn TensorBase<f32>,
+    second: &dyn TensorBase<f32>,
+    same_shape: impl FnOnce(&[f32], &[f32]) -> Vec<f32>,
+    f: impl Fn(f32, f32) -> f32,
+) -> MlResult<ArcTensor<f32>> {
+    if first.shape() == second.shape() {
+        Tensor::<f32>::from_vec(same_shape(first.data(), second.data()), first.shape())
+    } else {
+        broadcast_op(first, second, f)
+    }
+}
+
+/// Finds the maximum of `values` and its position, propagating or skipping NaNs
+fn max_with_index(values: impl Iterator<Item = f32>, nan_propagate: bool) -> (f32, usize) {
+    let mut max: Option<(f32, usize)> = None;
+    let mut any_nan = false;
+    for (k, val) in values.enumerate() {
+        if val.is_nan() {
+            if nan_propagate {
+                return (f32::NAN, k);
+            }
+            any_nan = true;
+        } else if max.is_none_or(|(max_val, _)| val > max_val) {
+            max = Some((val, k));
+        }
+    }
+    match max {
+        Some(max) => max,
+        None if any_nan => (f32::NAN, 0),
+        None => (f32::NEG_INFINITY, 0),
+    }
+}
+
+/// Population variance of `values`, as mean then squared deviations in `f32`
+fn naive_variance(values: &[f32]) -> f32 {
+    let n = values.len() as f32;
+    let mean = values.iter().sum::<f32>() / n;
+    values.iter().map(|x| (x - mean) * (x - mean)).sum::<f32>() / n
+}
+
+/// Population variance of `values` with Welford's single-pass update in `f64`
+fn welford_variance(values: &[f32]) -> f32 {
+    let (mut n, mut mean, mut m2) = (0.0f64, 0.0f64, 0.0f64);
+    for &x in values {
+        let x = x as f64;
+        n += 1.0;
+        let delta = x - mean;
+        mean += delta / n;
+        m2 += delta * (x - mean);
+    }
+    (m2 / n) as f32
+}
+
+/// Validates the operands of `Matmul`, including the broadcasting of batch dimensions
+fn check_matmul_shapes(first: &dyn TensorBase<f32>, second: &dyn TensorBase<f32>) -> MlResult<()> {
+    if first.is_empty() || second.is_empty() {
+        return Err(MlError::TensorError(TensorError::EmptyTensor));
+    }
+    let (a, b) = (first.shape(), second.shape());
+    let compatible = match (first.ndim(), second.ndim()) {
+        (0, _) | (_, 0) => false,
+        (1, 1) => a == b,
+        (2, 1) => a[1] == b[0],
+        (1, 2) => a[0] == b[0],
+        (ra, rb) if ra >= 2 && rb >= 2 => {
+            let batch: usize = a[..ra - 2].iter().product();
+            let other_batch: usize = b[..rb - 2].iter().product();
+            a[ra - 1] == b[rb - 2] && (batch == 1 || other_batch == 1 || batch == other_batch)
+        }
+        _ => false,
+    };
+    if !compatible {
+        return Err(MlError::TensorError(TensorError::MatrixMultiplicationError {
+            left_shape: a.to_vec(),
+            right_shape: b.to_vec(),
+        }));
+    }
+    Ok(())
+}
 
 impl<'t> Function<'t, f32> for Abs<'t, f32> {
     type Forwarded = MlResult<ArcTensor<f32>>;
@@ -26,6 +147,8 @@ impl<'t> Function<'t, f32> for Abs<'t, f32> {
     fn forward(&'t mut self) -> Self::Forwarded {
         let tensor = Tensor::<f32>::from_vec(self.tensor.data().iter().map(|&x| x.abs()).collect(), self.tensor.shape())?;
         #[cfg(feature = "enable_backpropagation")]
+        let tensor = with_grad_fn(tensor, "abs", &[self.tensor]);
+        #[cfg(feature = "enable_backpropagation")]
         {
             self.output = Some(tensor.0.clone());
         }
@@ -63,6 +186,8 @@ impl<'t> Function<'t, f32> for Exp<'t, f32> {
     fn forward(&'t mut self) -> Self::Forwarded {
         let tensor = Tensor::<f32>::from_vec(self.backend.exp(&self.tensor.data()), self.tensor.shape())?;
         #[cfg(feature = "enable_backpropagation")]
+        let tensor = with_grad_fn(tensor, "exp", &[self.tensor]);
+        #[cfg(feature = "enable_backpropagation")]
         {
             self.output = Some(tensor.0.clone());
         }
@@ -99,6 +224,8 @@ impl<'t> Function<'t, f32> for Log<'t, f32> {
     /// A new tensor with each element being the natural logarithm of tensor_element
     fn forward(&'t mut self) -> Self::Forwarded {
         let tensor = Tensor::<f32>::from_vec(self.tensor.data().iter().map(|&x| x.ln()).collect(), self.tensor.shape())?;
+        #[cfg(feature = "enable_backpropagation")]
+        let tensor = with_grad_fn(tensor, "log", &[self.tensor]);
         #[cfg(feature = "enable_backpropagation")]
                 {
             self.output = Some(tensor.0.clone());
@@ -136,6 +263,8 @@ impl<'t> Function<'t, f32> for Neg<'t, f32> {
     /// A new tensor with each element being the negation of tensor_element
     fn forward(&'t mut self) -> Self::Forwarded {
         let tensor = Tensor::<f32>::from_vec(self.tensor.data().iter().map(|&x| -x).collect(), self.tensor.shape())?;
+        #[cfg(feature = "enable_backpropagation")]
+        let tensor = with_grad_fn(tensor, "neg", &[self.tensor]);
         #[cfg(feature = "enable_backpropagation")]
                 {
             self.output = Some(tensor.0.clone());
@@ -174,6 +303,8 @@ impl<'t> Function<'t, f32> for Sqrt<'t, f32> {
     /// A new tensor with each element being the square root of tensor_element
     fn forward(&'t mut self) -> Self::Forwarded {
         let tensor = Tensor::<f32>::from_vec(self.backend().sqrt(self.tensor.data()), self.tensor.shape())?;
+        #[cfg(feature = "enable_backpropagation")]
+        let tensor = with_grad_fn(tensor, "sqrt", &[self.tensor]);
         #[cfg(feature = "enable_backpropagation")]
                 {
             self.output = Some(tensor.0.clone());
@@ -211,6 +342,8 @@ impl<'t> Function<'t, f32> for Square<'t, f32> {
     /// A new tensor with each element being the square of the corresponding element in the input tensor
     fn forward(&'t mut self) -> Self::Forwarded {
         let tensor = Tensor::<f32>::from_vec(self.tensor.data().iter().map(|x| x * x).collect(), self.tensor.shape())?;
+        #[cfg(feature = "enable_backpropagation")]
+        let tensor = with_grad_fn(tensor, "square", &[self.tensor]);
         #[cfg(feature = "enable_backpropagation")]
                 {
             self.output = Some(tensor.0.clone());
@@ -228,6 +361,122 @@ impl<'t> Function<'t, f32> for Square<'t, f32> {
     }
 }
 
+impl<'t> Function<'t, f32> for Relu6<'t, f32> {
+    type Forwarded = MlResult<ArcTensor<f32>>;
+    #[cfg(feature = "enable_backpropagation")]
+    type Gradiant = MlResult<ArcTensor<f32>>;
+
+    fn new(first: &'t dyn TensorBase<f32>, _: Option<&'t dyn TensorBase<f32>>) -> MlResult<Self> {
+        Ok(Self {
+            tensor: first,
+            backend: Arc::new(backend::CpuBackend::new()?),
+            #[cfg(feature = "enable_backpropagation")]
+            output: None
+        })
+    }
+
+    /// Clamps each element to `[0, 6]`
+    ///
+    /// # Returns
+    /// A new tensor with each element being min(max(tensor_element, 0), 6)
+    fn forward(&'t mut self) -> Self::Forwarded {
+        let tensor = clamp(self.tensor, 0.0, 6.0)?;
+        #[cfg(feature = "enable_backpropagation")]
+        let tensor = with_grad_fn(tensor, "relu6", &[self.tensor]);
+        #[cfg(feature = "enable_backpropagation")]
+        {
+            self.output = Some(tensor.0.clone());
+        }
+        Ok(tensor)
+    }
+
+    /// Passes `grad` through where the input lies strictly inside `(0, 6)`
+    ///
+    /// The gradient is zero outside the interval and at the boundaries 0 and 6 themselves.
+    ///
+    /// # Returns
+    /// * `Ok(ArcTensor)` with the gradient of the input
+    /// * `Err(MlError::TensorError)` with `InvalidShape` if `grad` does not match the input shape
+    #[cfg(feature = "enable_backpropagation")]
+    fn backward(&'t mut self, grad: &'t dyn TensorBase<f32>) -> Self::Gradiant {
+        clamp_backward(self.tensor, grad, 0.0, 6.0)
+    }
+
+    fn backend(&self) -> &Arc<dyn Backend> {
+        &self.backend
+    }
+}
+
+impl<'t> Function<'t, f32> for Hardtanh<'t, f32> {
+    type Forwarded = MlResult<ArcTensor<f32>>;
+    #[cfg(feature = "enable_backpropagation")]
+    type Gradiant = MlResult<ArcTensor<f32>>;
+
+    fn new(first: &'t dyn TensorBase<f32>, _: Option<&'t dyn TensorBase<f32>>) -> MlResult<Self> {
+        Ok(Self {
+            tensor: first,
+            backend: Arc::new(backend::CpuBackend::new()?),
+            #[cfg(feature = "enable_backpropagation")]
+            output: None,
+            bounds: (-1.0, 1.0),
+        })
+    }
+
+    /// Clamps each element to `[min, max]`
+    ///
+    /// # Returns
+    /// * `Ok(ArcTensor)` with each element being min(max(tensor_element, min), max)
+    /// * `Err(MlError::TensorError)` with `InvalidOperation` if `min` is greater than `max`
+    fn forward(&'t mut self) -> Self::Forwarded {
+        let (min, max) = self.bounds;
+        let tensor = clamp(self.tensor, min, max)?;
+        #[cfg(feature = "enable_backpropagation")]
+        let tensor = with_grad_fn(tensor, "hardtanh", &[self.tensor]);
+        #[cfg(feature = "enable_backpropagation")]
+        {
+            self.output = Some(tensor.0.clone());
+        }
+        Ok(tensor)
+    }
+
+    /// Passes `grad` through where the input lies strictly inside `(min, max)`
+    ///
+    /// The gradient is zero outside the interval and at the boundaries themselves.
+    ///
+    /// # Returns
+    /// * `Ok(ArcTensor)` with the gradient of the input
+    /// * `Err(MlError::TensorError)` with `InvalidShape` if `grad` does not match the input shape
+    #[cfg(feature = "enable_backpropagation")]
+    fn backward(&'t mut self, grad: &'t dyn TensorBase<f32>) -> Self::Gradiant {
+        clamp_backward(self.tensor, grad, self.bounds.0, self.bounds.1)
+    }
+
+    fn backend(&self) -> &Arc<dyn Backend> {
+        &self.backend
+    }
+}
+
+/// Clamps every element of `tensor` to `[min, max]`
+fn clamp(tensor: &dyn TensorBase<f32>, min: f32, max: f32) -> MlResult<ArcTensor<f32>> {
+    if min.is_nan() || max.is_nan() || min > max {
+        return Err(MlError::TensorError(TensorError::InvalidOperation {
+            op: "hardtanh",
+            reason: format!("min ({}) must not be greater than max ({})", min, max),
+        }));
+    }
+    Tensor::<f32>::from_vec(tensor.data().iter().map(|&x| x.clamp(min, max)).collect(), tensor.shape())
+}
+
+/// Gradient of `clamp`: `grad` where `min < input < max`, zero elsewhere
+#[cfg(feature = "enable_backpropagation")]
+fn clamp_backward(input: &dyn TensorBase<f32>, grad: &dyn TensorBase<f32>, min: f32, max: f32) -> MlResult<ArcTensor<f32>> {
+    input.chk_shape(grad)?;
+    let data = input.data().iter().zip(grad.data())
+        .map(|(&x, &g)| if x > min && x < max { g } else { 0.0 })
+        .collect();
+    Tensor::<f32>::from_vec(data, input.shape())
+}
+
 impl<'t> Function<'t, f32> for Add<'t, f32> {
     type Forwarded = MlResult<ArcTensor<f32>>;
     #[cfg(feature = "enable_backpropagation")]
@@ -236,14 +485,14 @@ impl<'t> Function<'t, f32> for Add<'t, f32> {
     fn new(first: &'t dyn TensorBase<f32>, second: Option<&'t dyn TensorBase<f32>>) -> MlResult<Self>  {
         Ok(Self {
             first_tensor: first,
-            second_tensor: second.unwrap(),
+            second_tensor: check_elementwise_shapes("Add", first, second)?,
             backend: Arc::new(backend::CpuBackend::new()?),
             #[cfg(feature = "enable_backpropagation")]
             output: None
         })
     }
 
-    /// Adds two tensors element-wise
+    /// Adds two tensors element-wise, broadcasting operands of different shapes
     ///
     /// # Arguments
     /// * `other` - The tensor to add to the current tensor
@@ -251,34 +500,14 @@ impl<'t> Function<'t, f32> for Add<'t, f32> {
     /// # Returns
     /// A new tensor with the result of the element-wise addition
     fn forward(&'t mut self) -> Self::Forwarded {
-        if self.first_tensor.shape().len() == 2 && self.second_tensor.shape().len() == 1 && self.first_tensor.shape()[1] == self.second_tensor.shape()[0] {
-            let (_, features) = (self.first_tensor.shape()[0], self.first_tensor.shape()[1]);
-            let mut data = vec![0.0; self.first_tensor.data().len()];
-
-            for (i, chunk) in data.chunks_mut(features).enumerate() {
-                for (j, val) in chunk.iter_mut().enumerate() {
-                    *val = self.first_tensor.data()[i * features + j] + self.second_tensor.data()[j];
-                }
-            }
-             let tensor = Tensor::<f32>::from_vec(data, self.first_tensor.shape())?;
-            #[cfg(feature = "enable_backpropagation")]
-                    {
-            self.output = Some(tensor.0.clone());
-        }
-            return Ok(tensor)
-        }
-
-        match self.first_tensor.chk_shape(self.second_tensor) {
-            Err(e) => Err(e),
-            _ => {
-                let tensor = Tensor::<f32>::from_vec(self.backend().add(self.first_tensor.data(), self.second_tensor.data()), self.first_tensor.shape())?;
-                #[cfg(feature = "enable_backpropagation")]
-                        {
+        let tensor = elementwise(self.first_tensor, self.second_tensor, |a, b| self.backend.add(a, b), |a, b| a + b)?;
+        #[cfg(feature = "enable_backpropagation")]
+        let tensor = with_grad_fn(tensor, "add", &[self.first_tensor, self.second_tensor]);
+        #[cfg(feature = "enable_backpropagation")]
+        {
             self.output = Some(tensor.0.clone());
         }
-                Ok(tensor)
-            }
-        }
+        Ok(tensor)
     }
 
     #[cfg(feature = "enable_backpropagation")]
@@ -299,14 +528,14 @@ impl<'t> Function<'t, f32> for Sub<'t, f32> {
     fn new(first: &'t dyn TensorBase<f32>, second: Option<&'t dyn TensorBase<f32>>) -> MlResult<Self> {
         Ok(Self {
             first_tensor: first,
-            second_tensor: second.unwrap(),
+            second_tensor: check_elementwise_shapes("Sub", first, second)?,
             backend: Arc::new(backend::CpuBackend::new()?),
             #[cfg(feature = "enable_backpropagation")]
             output: None
         })
     }
 
-    /// Subtracts two tensors element-wise
+    /// Subtracts two tensors element-wise, broadcasting operands of different shapes
     ///
     /// # Arguments
     /// * `other` - The tensor to subtract from_vec the current tensor
@@ -314,35 +543,14 @@ impl<'t> Function<'t, f32> for Sub<'t, f32> {
     /// # Returns
     /// A new tensor with the result of the element-wise subtraction
     fn forward(&'t mut self) -> Self::Forwarded {
-        let tensor: ArcTensor<f32>;
-        if self.first_tensor.shape().len() == 2 && self.second_tensor.shape().len() == 1 && self.first_tensor.shape()[1] == self.second_tensor.shape()[0] {
-            let (batch_size, features) = (self.first_tensor.shape()[0], self.first_tensor.shape()[1]);
-            let mut data = vec![0.0; self.first_tensor.data().len()];
-
-            for i in 0..batch_size {
-                for j in 0..features {
-                    data[i * features + j] = self.first_tensor.data()[i * features + j] - self.second_tensor.data()[j];
-                }
-            }
-            tensor = Tensor::<f32>::from_vec(data, &self.first_tensor.shape())?;
-            #[cfg(feature = "enable_backpropagation")]
-                    {
-            self.output = Some(tensor.0.clone());
-        }
-            return Ok(tensor)
-        }
-
-        match self.first_tensor.chk_shape(self.second_tensor) {
-            Err(e) => Err(e),
-            _ => {
-                tensor = Tensor::<f32>::from_vec(self.backend().sub(self.first_tensor.data(), self.second_tensor.data()), self.first_tensor.shape())?;
-                #[cfg(feature = "enable_backpropagation")]
-                        {
+        let tensor = elementwise(self.first_tensor, self.second_tensor, |a, b| self.backend.sub(a, b), |a, b| a - b)?;
+        #[cfg(feature = "enable_backpropagation")]
+        let tensor = with_grad_fn(tensor, "sub", &[self.first_tensor, self.second_tensor]);
+        #[cfg(feature = "enable_backpropagation")]
+        {
             self.output = Some(tensor.0.clone());
         }
-                Ok(tensor)
-            }
-        }
+        Ok(tensor)
     }
 
     #[cfg(feature = "enable_backpropagation")]
@@ -358,19 +566,20 @@ impl<'t> Function<'t, f32> for Sub<'t, f32> {
 impl<'t> Function<'t, f32> for Mul<'t, f32> {
     type Forwarded = MlResult<ArcTensor<f32>>;
     #[cfg(feature = "enable_backpropagation")]
-    type Gradiant = ();
+    type Gradiant = MlResult<(ArcTensor<f32>, ArcTensor<f32>)>;
 
     fn new(first: &'t dyn TensorBase<f32>, second: Option<&'t dyn TensorBase<f32>>) -> MlResult<Self> {
+        let second = check_elementwise_shapes("Mul", first, second)?;
         Ok(Self {
             first_tensor: first,
-            second_tensor: second.unwrap(),
+            second_tensor: second,
             backend: Arc::new(backend::CpuBackend::new()?),
             #[cfg(feature = "enable_backpropagation")]
             output: None
         })
     }
 
-    /// Multiplies two tensors element-wise
+    /// Multiplies two tensors element-wise, broadcasting operands of different shapes
     ///
     /// # Arguments
     /// * `other` - The tensor to multiply the current tensor by
@@ -378,22 +587,36 @@ impl<'t> Function<'t, f32> for Mul<'t, f32> {
     /// # Returns
     /// A new tensor with the result of the element-wise multiplication
     fn forward(&'t mut self) -> Self::Forwarded {
-        match self.first_tensor.chk_shape(self.second_tensor) {
-            Err(e) => Err(e),
-            _ => {
-                let tensor = Tensor::<f32>::from_vec(self.backend().multiply(self.first_tensor.data(), self.second_tensor.data()), self.first_tensor.shape())?;
-                #[cfg(feature = "enable_backpropagation")]
-                        {
+        let tensor = elementwise(self.first_tensor, self.second_tensor, |a, b| self.backend.multiply(a, b), |a, b| a * b)?;
+        #[cfg(feature = "enable_backpropagation")]
+        let tensor = with_grad_fn(tensor, "mul", &[self.first_tensor, self.second_tensor]);
+        #[cfg(feature = "enable_backpropagation")]
+        {
             self.output = Some(tensor.0.clone());
         }
-                Ok(tensor)
-            }
-        }
+        Ok(tensor)
     }
 
+    /// Computes the gradients of both operands from the gradient of the product
+    ///
+    /// The gradients `grad * second` and `grad * first` are summed back to the
+    /// shape of their operand, so a broadcast operand receives a gradient of its own shape.
+    ///
+    /// # Returns
+    /// * `Ok((ArcTensor, ArcTensor))` with the gradients of the first and second operand
+    /// * `Err(MlError::TensorError)` with `InvalidShape` if `grad` does not match the shape of the product
     #[cfg(feature = "enable_backpropagation")]
     fn backward(&'t mut self, grad: &'t dyn TensorBase<f32>) -> Self::Gradiant {
-        todo!()
+        let shape = broadcast_shape(self.first_tensor.shape(), self.second_tensor.shape())?;
+        if grad.shape() != shape {
+            return Err(MlError::TensorError(TensorError::InvalidShape {
+                expected: shape,
+                got: grad.shape().to_vec(),
+            }));
+        }
+        let first_grad = broadcast_op(grad, self.second_tensor, |a, b| a * b)?.sum_to(self.first_tensor.shape())?;
+        let second_grad = broadcast_op(grad, self.first_tensor, |a, b| a * b)?.sum_to(self.second_tensor.shape())?;
+        Ok((first_grad, second_grad))
     }
 
     fn backend(&self) -> &Arc<dyn Backend> {
@@ -406,16 +629,17 @@ impl<'t> Function<'t, f32> for Div<'t, f32> {
     #[cfg(feature = "enable_backpropagation")]
     type Gradiant = ();
     fn new(first: &'t dyn TensorBase<f32>, second: Option<&'t dyn TensorBase<f32>>) -> MlResult<Self> {
+        let second = check_elementwise_shapes("Div", first, second)?;
         Ok(Self {
             first_tensor: first,
-            second_tensor: second.unwrap(),
+            second_tensor: second,
             backend: Arc::new(backend::CpuBackend::new()?),
             #[cfg(feature = "enable_backpropagation")]
             output: None
         })
     }
 
-    /// Divides two tensors element-wise
+    /// Divides two tensors element-wise, broadcasting operands of different shapes
     ///
     /// # Arguments
     /// * `other` - The tensor to divide the current tensor by
@@ -423,17 +647,14 @@ impl<'t> Function<'t, f32> for Div<'t, f32> {
     /// # Returns
     /// A new tensor with the result of the element-wise division
     fn forward(&'t mut self) -> Self::Forwarded {
-        match self.first_tensor.chk_shape(self.second_tensor) {
-            Err(e) => Err(e),
-            _ => {
-                 let tensor = Tensor::<f32>::from_vec(self.backend().div(self.first_tensor.data(), self.second_tensor.data()), self.first_tensor.shape())?;
-                #[cfg(feature = "enable_backpropagation")]
-                        {
+        let tensor = elementwise(self.first_tensor, self.second_tensor, |a, b| self.backend.div(a, b), |a, b| a / b)?;
+        #[cfg(feature = "enable_backpropagation")]
+        let tensor = with_grad_fn(tensor, "div", &[self.first_tensor, self.second_tensor]);
+        #[cfg(feature = "enable_backpropagation")]
+        {
             self.output = Some(tensor.0.clone());
         }
-                return Ok(tensor)
-            }
-        }
+        Ok(tensor)
     }
 
     #[cfg(feature = "enable_backpropagation")]
@@ -451,25 +672,44 @@ impl<'t> Function<'t, f32> for Pow<'t, f32> {
     #[cfg(feature = "enable_backpropagation")]
     type Gradiant = ();
 
-    fn new(first: &'t dyn TensorBase<f32>, _: Option<&'t dyn TensorBase<f32>>) -> MlResult<Self> {
+    fn new(first: &'t dyn TensorBase<f32>, second: Option<&'t dyn TensorBase<f32>>) -> MlResult<Self> {
         Ok(Self {
             tensor: first,
             backend: Arc::new(backend::CpuBackend::new()?),
             #[cfg(feature = "enable_backpropagation")]
             output: None,
             power: None,
+            exponent: second,
         })
     }
 
     /// Raises each element in the tensor to a power
     ///
     /// # Arguments
-    /// * `power` - The power to raise each element to
+    /// * `power` - The scalar power to raise each element to
+    /// * `exponent` - A tensor of powers, broadcast against the tensor; takes precedence over `power`
+    ///
+    /// A negative base with a non-integer exponent produces NaN, as with `f32::powf`.
     ///
     /// # Returns
-    /// A new tensor with each element being tensor_element ^ power
+    /// * `Ok(ArcTensor)` with each element being tensor_element ^ power
+    /// * `Err(MlError::TensorError)` if the shapes cannot be broadcast or no exponent was given
     fn forward(&'t mut self) -> Self::Forwarded {
-        let tensor = Tensor::<f32>::from_vec(self.backend().pow(self.tensor.data(), self.power.unwrap()), self.tensor.shape())?;
+        let tensor = match (self.exponent, self.power) {
+            (Some(exponent), _) => {
+                broadcast_op(self.tensor, exponent, f32::powf).map_err(op_context("Pow", &[self.tensor, exponent]))?
+            }
+            (None, Some(power)) => Tensor::<f32>::from_vec(self.backend().pow(self.tensor.data(), power), self.tensor.shape())?,
+            (None, None) => return Err(MlError::TensorError(TensorError::InvalidOperation {
+                op: "pow",
+                reason: "no exponent was given".to_string(),
+            })),
+        };
+        #[cfg(feature = "enable_backpropagation")]
+        let tensor = match self.exponent {
+            Some(exponent) => with_grad_fn(tensor, "pow", &[self.tensor, exponent]),
+            None => with_grad_fn(tensor, "pow", &[self.tensor]),
+        };
         #[cfg(feature = "enable_backpropagation")]
                 {
             self.output = Some(tensor.0.clone());
@@ -493,9 +733,11 @@ impl<'t> Function<'t, f32> for Matmul<'t, f32> {
     type Gradiant = ();
 
     fn new(first: &'t dyn TensorBase<f32>, second: Option<&'t dyn TensorBase<f32>>) -> MlResult<Self> {
+        let second = second_operand("Matmul", second)?;
+        check_matmul_shapes(first, second).map_err(op_context("Matmul", &[first, second]))?;
         Ok(Self {
             first_tensor: first,
-            second_tensor: second.unwrap(),
+            second_tensor: second,
             backend: Arc::new(backend::CpuBackend::new()?),
             #[cfg(feature = "enable_backpropagation")]
             output: None
@@ -509,39 +751,21 @@ impl<'t> Function<'t, f32> for Matmul<'t, f32> {
     ///
     /// # Returns
     /// A new tensor with the result of the matrix multiplication
-    // Handle empty tensors
     fn forward(&'t mut self) -> Self::Forwarded {
-        if self.first_tensor.data().is_empty() || self.second_tensor.data().is_empty() {
-            return Err(MlError::TensorError(TensorError::EmptyTensor));
-        }
-
-        let a = self.first_tensor.shape().len();
-        let b = self.second_tensor.shape().len();
+        let a = self.first_tensor.ndim();
+        let b = self.second_tensor.ndim();
 
         let tensor =  match (a, b) {
             // Case 1: 1D * 1D (dot product)
             (1, 1) => {
-                match self.first_tensor.chk_shape(self.second_tensor) {
-                    Err(e) => return Err(e),
-                    _ => {
-                        Tensor::<f32>::from_vec(
-                            vec![self.first_tensor.data().iter().zip(self.second_tensor.data().iter()).map(|(&a, &b)| a * b).sum::<f32>()],
-                            &vec![]
-                        )?
-                    }
-                }
+                Tensor::<f32>::from_vec(
+                    vec![self.first_tensor.data().iter().zip(self.second_tensor.data().iter()).map(|(&a, &b)| a * b).sum::<f32>()],
+                    &vec![]
+                )?
             }
 
             // Case 2: 2D * 1D or 1D * 2D
             (2, 1) => {
-                if self.first_tensor.shape()[1] != self.second_tensor.shape()[0] {
-                    return Err(MlError::TensorError(
-                        TensorError::MatrixMultiplicationError {
-                            left_shape: self.first_tensor.shape().to_vec(),
-                            right_shape: self.second_tensor.shape().to_vec(),
-                        },
-                    ));
-                }
                 let m = self.first_tensor.shape()[0];
                 let k = self.first_tensor.shape()[1];
                 let mut data = vec![0.0; m];
@@ -557,14 +781,6 @@ impl<'t> Function<'t, f32> for Matmul<'t, f32> {
             }
 
             (1, 2) => {
-                if self.first_tensor.shape()[0] != self.second_tensor.shape()[0] {
-                    return Err(MlError::TensorError(
-                        TensorError::MatrixMultiplicationError {
-                            left_shape: self.first_tensor.shape().to_vec(),
-                            right_shape: self.second_tensor.shape().to_vec(),
-                        },
-                    ));
-                }
                 let k = self.first_tensor.shape()[0];
                 let n = self.second_tensor.shape()[1];
                 let mut data = vec![0.0; n];
@@ -591,15 +807,6 @@ impl<'t> Function<'t, f32> for Matmul<'t, f32> {
                 let k = self.first_tensor.shape()[a - 1];
                 let n = self.second_tensor.shape()[b - 1];
 
-                if k != self.second_tensor.shape()[b - 2] {
-                    return Err(MlError::TensorError(
-                        TensorError::MatrixMultiplicationError {
-                            left_shape: self.first_tensor.shape().to_vec(),
-                            right_shape: self.second_tensor.shape().to_vec(),
-                        },
-                    ));
-                }
-
                 // Handle broadcasting for batch dimensions
                 let other_batch_size = if b > 2 {
                     self.second_tensor.shape()[..b - 2].iter().product()
@@ -607,20 +814,7 @@ impl<'t> Function<'t, f32> for Matmul<'t, f32> {
                     1
                 };
 
-                let output_batch_size = if batch_size == 1 {
-                    other_batch_size
-                } else if other_batch_size == 1 {
-                    batch_size
-                } else if batch_size == other_batch_size {
-                    batch_size
-                } else {
-                    return Err(MlError::TensorError(
-                        TensorError::MatrixMultiplicationError {
-                            left_shape: self.first_tensor.shape().to_vec(),
-                            right_shape: self.second_tensor.shape().to_vec()
-                        },
-                    ));
-                };
+                let output_batch_size = batch_size.max(other_batch_size);
 
                 let mut data = vec![0.0; output_batch_size * m * n];
 
@@ -659,6 +853,8 @@ impl<'t> Function<'t, f32> for Matmul<'t, f32> {
             }
         };
         #[cfg(feature = "enable_backpropagation")]
+        let tensor = with_grad_fn(tensor, "matmul", &[self.first_tensor, self.second_tensor]);
+        #[cfg(feature = "enable_backpropagation")]
         {
             self.output = Some(tensor.0.clone());
         }
@@ -700,67 +896,54 @@ impl<'t> Function<'t, f32> for Topk<'t, f32> {
     /// # Returns
     /// A tuple of two tensors (values, indices) containing the top k values and their indices
     fn forward(&'t mut self) -> Self::Forwarded {
-        if self.topk.unwrap().0 == 0 {
-                return Err(MlError::TensorError(TensorError::InvalidOperation {
-                    op: "topk",
-                    reason: "k must be greater than 0".to_string(),
-                }));
-            }
-
-            let last_dim = self.tensor.shape().len() - 1;
-            let last_dim_size = self.tensor.shape()[last_dim];
-
-            if self.topk.unwrap().0 > last_dim_size {
-                return Err(MlError::TensorError(TensorError::InvalidOperation {
-                    op: "topk",
-                    reason: format!(
-                        "k ({}) cannot be larger than last dimension size ({})",
-                        self.topk.unwrap().0, last_dim_size
-                    ),
-                }));
+        let (k, sorted) = self.topk.ok_or_else(|| op_context("Topk", &[self.tensor])(MlError::TensorError(TensorError::InvalidOperation {
+            op: "topk",
+            reason: "k was not set, construct the op with Topk::with_k".to_string(),
+        })))?;
+        check_topk(self.tensor, k).map_err(op_context("Topk", &[self.tensor]))?;
+
+        let last_dim = self.tensor.ndim() - 1;
+        let last_dim_size = self.tensor.shape()[last_dim];
+
+        let slice_size = last_dim_size;
+        let num_slices: usize = self.tensor.shape()[..last_dim].iter().product();
+        let mut values = Vec::with_capacity(num_slices * k);
+        let mut indices = Vec::with_capacity(num_slices * k);
+
+        for slice_idx in 0..num_slices {
+            let start_idx = slice_idx * slice_size;
+            let end_idx = start_idx + slice_size;
+            let slice_data = &self.tensor.data()[start_idx..end_idx];
+            let mut pairs: Vec<(f32, usize)> = slice_data
+                .iter()
+                .copied()
+                .enumerate()
+                .map(|(i, v)| (v, i))
+                .collect();
+
+            pairs.sort_by(|a, b| b.0.partial_cmp(&a.0).unwrap_or(std::cmp::Ordering::Equal));
+
+            let top_k = &pairs[..k];
+            let mut selected = top_k.to_vec();
+            if !sorted {
+                selected.sort_by_key(|pair| pair.1);
             }
 
+            values.extend(selected.iter().map(|pair| pair.0));
+            indices.extend(selected.iter().map(|pair| pair.1 as f32));
+        }
 
-            let slice_size = last_dim_size;
-            let num_slices: usize = self.tensor.shape()[..last_dim].iter().product();
-            let mut values = Vec::with_capacity(num_slices * self.topk.unwrap().0);
-            let mut indices = Vec::with_capacity(num_slices * self.topk.unwrap().0);
-
-
-            for slice_idx in 0..num_slices {
-                let start_idx = slice_idx * slice_size;
-                let end_idx = start_idx + slice_size;
-                let slice_data = &self.tensor.data()[start_idx..end_idx];
-                let mut pairs: Vec<(f32, usize)> = slice_data
-                    .iter()
-                    .copied()
-                    .enumerate()
-                    .map(|(i, v)| (v, i))
-                    .collect();
-
-
-                pairs.sort_by(|a, b| b.0.partial_cmp(&a.0).unwrap_or(std::cmp::Ordering::Equal));
-
-
-                let top_k = &pairs[..self.topk.unwrap().0];
-                let mut selected = top_k.to_vec();
-                if !self.topk.unwrap().1 {
-                    selected.sort_by_key(|pair| pair.1);
-                }
-
-                values.extend(selected.iter().map(|pair| pair.0));
-                indices.extend(selected.iter().map(|pair| pair.1 as f32));
-            }
-
-            let mut new_shape = self.tensor.shape().to_vec();
-            new_shape[last_dim] = self.topk.unwrap().0;
+        let mut new_shape = self.tensor.shape().to_vec();
+        new_shape[last_dim] = k;
 
-            let tensor = (Tensor::<f32>::from_vec(values, &new_shape)?, Tensor::<f32>::from_vec(indices, &new_shape)?);
-            #[cfg(feature = "enable_backpropagation")]
-            {
-                self.output = Some((tensor.0.0.clone(), tensor.1.0.clone()));
-            }
-            Ok(tensor)
+        let tensor = (Tensor::<f32>::from_vec(values, &new_shape)?, Tensor::<f32>::from_vec(indices, &new_shape)?);
+        #[cfg(feature = "enable_backpropagation")]
+        let tensor = (with_grad_fn(tensor.0, "topk", &[self.tensor]), tensor.1);
+        #[cfg(feature = "enable_backpropagation")]
+        {
+            self.output = Some((tensor.0.0.clone(), tensor.1.0.clone()));
+        }
+        Ok(tensor)
     }
 
     #[cfg(feature = "enable_backpropagation")]
@@ -773,6 +956,39 @@ impl<'t> Function<'t, f32> for Topk<'t, f32> {
     }
 }
 
+/// Checks that `k` elements can be taken from the last dimension of `tensor`
+fn check_topk(tensor: &dyn TensorBase<f32>, k: usize) -> MlResult<()> {
+    if k == 0 {
+        return Err(MlError::TensorError(TensorError::InvalidOperation {
+            op: "topk",
+            reason: "k must be greater than 0".to_string(),
+        }));
+    }
+    let last_dim_size = tensor.size(-1)?;
+    if k > last_dim_size {
+        return Err(MlError::TensorError(TensorError::InvalidOperation {
+            op: "topk",
+            reason: format!("k ({}) cannot be larger than last dimension size ({})", k, last_dim_size),
+        }));
+    }
+    Ok(())
+}
+
+impl<'t> Topk<'t, f32> {
+    /// Creates a top-k operation taking the `k` largest elements of the last dimension
+    ///
+    /// # Returns
+    /// * `Ok(Topk)` if `k` elements can be taken
+    /// * `Err(MlError::TensorError)` with `InvalidOperation` if `k` is 0 or larger than the
+    ///   last dimension, or `InvalidAxis` if `tensor` is a scalar
+    pub fn with_k(tensor: &'t dyn TensorBase<f32>, k: usize, sorted: bool) -> MlResult<Self> {
+        check_topk(tensor, k).map_err(op_context("Topk", &[tensor]))?;
+        let mut op = Self::new(tensor, None)?;
+        op.topk = Some((k, sorted));
+        Ok(op)
+    }
+}
+
 impl<'t> Function<'t, f32> for Matmax<'t, f32> {
     type Forwarded = MlResult<(ArcTensor<f32>, ArcTensor<f32>)>;
     #[cfg(feature = "enable_backpropagation")]
@@ -785,6 +1001,7 @@ impl<'t> Function<'t, f32> for Matmax<'t, f32> {
             #[cfg(feature = "enable_backpropagation")]
             output: None,
             matmax: None,
+            nan_propagate: true,
         })
     }
 
@@ -796,36 +1013,26 @@ impl<'t> Function<'t, f32> for Matmax<'t, f32> {
     /// * `keepdim` - Whether the output tensor has dim retained or not
     ///
     /// # Returns
-    /// If dim is None, returns a tensor with a single element containing the maximum value.
+    /// If dim is None, returns a 0-dimensional tensor containing the maximum value.
     /// If dim is specified, returns a tuple of two tensors (values, indices) containing the
     /// maximum values and their indices along the specified dimension.
     fn forward(&'t mut self) -> Self::Forwarded {
-        let tensor = match self.matmax.unwrap().0 {
+        let (dim, keepdim) = self.matmax.ok_or_else(|| op_context("Matmax", &[self.tensor])(MlError::TensorError(TensorError::InvalidOperation {
+            op: "matmax",
+            reason: "dim was not set, construct the op with Matmax::with_dim".to_string(),
+        })))?;
+        let tensor = match dim {
             None => {
                 // Find global maximum
-                let max_val = self.tensor.data().iter().fold(f32::NEG_INFINITY, |a, &b| a.max(b));
-                (Tensor::<f32>::from_vec(vec![max_val], &vec![1])?, Tensor::<f32>::zeros())
-                // 빈 data 때문에
-                // thread 'tensor::ops::tests::test_max' panicked at src\tensor\creation.rs:6:42:
-                // index out of bounds: the len is 0 but the index is 0
-                //오류 발생중 빈 텐서를 만들어주는 메서드를 추가 고려중.
+                let (max_val, index) = max_with_index(self.tensor.data().iter().copied(), self.nan_propagate);
+                (Tensor::<f32>::scalar(max_val), Tensor::<f32>::scalar(index as f32))
             }
             Some(d) => {
-                let dim = if d < 0 {
-                    (self.tensor.shape().len() as i32 + d) as usize
-                } else {
-                    d as usize
-                };
-
-                if dim >= self.tensor.shape().len() {
-                    return Err(MlError::TensorError(TensorError::InvalidAxis {
-                        axis: dim,
-                        shape: self.tensor.shape().to_vec(),
-                    }));
-                }
+                let dim = normalize_dim(d, self.tensor.ndim()).map_err(op_context("Matmax", &[self.tensor]))?;
+                let dim_size = self.tensor.shape()[dim];
 
                 let mut new_shape = self.tensor.shape().to_vec();
-                if !self.matmax.unwrap().1 {
+                if !keepdim {
                     new_shape.remove(dim);
                 } else {
                     new_shape[dim] = 1;
@@ -834,24 +1041,14 @@ impl<'t> Function<'t, f32> for Matmax<'t, f32> {
                 let stride: usize = self.tensor.shape()[dim + 1..].iter().product();
                 let outer_stride: usize = self.tensor.shape()[dim..].iter().product();
                 let outer_dims: usize = self.tensor.shape()[..dim].iter().product();
-                let dim_size = self.tensor.shape()[dim];
 
                 let mut max_values = Vec::with_capacity(self.tensor.data().len() / dim_size);
                 let mut max_indices = Vec::with_capacity(self.tensor.data().len() / dim_size);
 
                 for i in 0..outer_dims {
                     for j in 0..stride {
-                        let mut max_val = f32::NEG_INFINITY;
-                        let mut max_idx = 0;
-
-                        for k in 0..dim_size {
-                            let idx = i * outer_stride + k * stride + j;
-                            let val = self.tensor.data()[idx];
-                            if val > max_val {
-                                max_val = val;
-                                max_idx = k;
-                            }
-                        }
+                        let slice = (0..dim_size).map(|k| self.tensor.data()[i * outer_stride + k * stride + j]);
+                        let (max_val, max_idx) = max_with_index(slice, self.nan_propagate);
 
                         max_values.push(max_val);
                         max_indices.push(max_idx as f32);
@@ -862,6 +1059,8 @@ impl<'t> Function<'t, f32> for Matmax<'t, f32> {
             }
         };
         #[cfg(feature = "enable_backpropagation")]
+        let tensor = (with_grad_fn(tensor.0, "matmax", &[self.tensor]), tensor.1);
+        #[cfg(feature = "enable_backpropagation")]
         {
             self.output = Some((tensor.0.0.clone(), tensor.1.0.clone()))
         }
@@ -878,56 +1077,242 @@ impl<'t> Function<'t, f32> for Matmax<'t, f32> {
     }
 }
 
-
-/// Add trait implementation for owned tensors
-///
-/// # Arguments
-/// * `_other` - The tensor to add to self
-///
-/// # Returns
-/// A new tensor containing the element-wise sum
-///
-/// # Broadcasting
-/// * Supports broadcasting when adding a 1D tensor to each row of a 2D tensor
-impl std::ops::Add<&dyn TensorBase<f32>> for &dyn TensorBase<f32> {
-    type Output = ArcTensor<f32>;
-
-    fn add(self, other: &dyn TensorBase<f32>) -> Self::Output {
-        Add::<f32>::new(self, Some(other)).unwrap().forward().unwrap()
+impl<'t> Matmax<'t, f32> {
+    /// Creates a max operation along `dim`, or over all elements if `dim` is `None`
+    ///
+    /// # Returns
+    /// * `Ok(Matmax)` if `dim` is valid for `tensor`
+    /// * `Err(MlError::TensorError)` with `InvalidAxis` if `dim` is out of range
+    pub fn with_dim(tensor: &'t dyn TensorBase<f32>, dim: Option<i32>, keepdim: bool) -> MlResult<Self> {
+        if let Some(d) = dim {
+            normalize_dim(d, tensor.ndim()).map_err(op_context("Matmax", &[tensor]))?;
+        }
+        let mut op = Self::new(tensor, None)?;
+        op.matmax = Some((dim, keepdim));
+        Ok(op)
     }
 }
 
-/// Subtract trait implementation for owned tensors
-///
-/// # Arguments
-/// * `_other` - The tensor to subtract from self
-///
-/// # Returns
-/// A new tensor containing the element-wise difference
-///
-/// # Broadcasting
-/// * Supports broadcasting when subtracting a 1D tensor from each row of a 2D tensor
-impl std::ops::Sub<&dyn TensorBase<f32>> for &dyn TensorBase<f32> {
-    type Output = ArcTensor<f32>;
+impl<'t> Function<'t, f32> for Var<'t, f32> {
+    type Forwarded = MlResult<ArcTensor<f32>>;
+    #[cfg(feature = "enable_backpropagation")]
+    type Gradiant = MlResult<ArcTensor<f32>>;
 
-    fn sub(self, other: &dyn TensorBase<f32>) -> Self::Output {
-        Sub::<f32>::new(self, Some(other)).unwrap().forward().unwrap()
+    fn new(tensor: &'t dyn TensorBase<f32>, _: Option<&'t dyn TensorBase<f32>>) -> MlResult<Self> {
+        Ok(Self {
+            tensor,
+            backend: Arc::new(backend::CpuBackend::new()?),
+            #[cfg(feature = "enable_backpropagation")]
+            output: None,
+            var: None,
+            stable: false,
+        })
     }
-}
-
-/// Multiply trait implementation for owned tensors
-///
-/// # Arguments
-/// * `_other` - The tensor to multiply with self
-///
-/// # Returns
-/// A new tensor containing the element-wise product (Hadamard product)
-///
-/// # Note
-/// * This performs element-wise multiplication, not matrix multiplication
-/// * For matrix multiplication, use `matmul()` instead
-impl std::ops::Mul<&dyn TensorBase<f32>> for &dyn TensorBase<f32> {
-    type Output = ArcTensor<f32>;
+
+    /// Returns the population variance of all elements in the input tensor.
+    /// If dim is specified, returns the variance along the given dimension.
+    ///
+    /// # Arguments
+    /// * `dim` - Optional dimension along which to compute the variance
+    /// * `keepdim` - Whether the output tensor has dim retained or not
+    ///
+    /// # Returns
+    /// * `Ok(ArcTensor)` with a 0-dimensional tensor if dim is None, or with dim reduced
+    /// * `Err(MlError::TensorError)` with `EmptyTensor` if there are no elements, or
+    ///   `InvalidAxis` if dim is out of range
+    fn forward(&'t mut self) -> Self::Forwarded {
+        if self.tensor.is_empty() {
+            return Err(op_context("Var", &[self.tensor])(MlError::TensorError(TensorError::EmptyTensor)));
+        }
+        let variance = if self.stable { welford_variance } else { naive_variance };
+
+        let (dim, keepdim) = self.var.unwrap_or((None, false));
+        let tensor = match dim {
+            None => Tensor::<f32>::scalar(variance(self.tensor.data())),
+            Some(d) => {
+                let dim = normalize_dim(d, self.tensor.ndim()).map_err(op_context("Var", &[self.tensor]))?;
+                let dim_size = self.tensor.shape()[dim];
+                let stride: usize = self.tensor.shape()[dim + 1..].iter().product();
+                let outer_dims: usize = self.tensor.shape()[..dim].iter().product();
+
+                let mut result = Vec::with_capacity(outer_dims * stride);
+                let mut lane = Vec::with_capacity(dim_size);
+                for i in 0..outer_dims {
+                    for j in 0..stride {
+                        lane.clear();
+                        lane.extend((0..dim_size).map(|k| self.tensor.data()[(i * dim_size + k) * stride + j]));
+                        result.push(variance(&lane));
+                    }
+                }
+
+                let mut new_shape = self.tensor.shape().to_vec();
+                if keepdim {
+                    new_shape[dim] = 1;
+                } else {
+                    new_shape.remove(dim);
+                }
+                Tensor::<f32>::from_vec(result, &new_shape)?
+            }
+        };
+        #[cfg(feature = "enable_backpropagation")]
+        let tensor = with_grad_fn(tensor, "var", &[self.tensor]);
+        #[cfg(feature = "enable_backpropagation")]
+        {
+            self.output = Some(tensor.0.clone());
+        }
+        Ok(tensor)
+    }
+
+    /// Computes the gradient with respect to the input
+    ///
+    /// Each element receives `2 * (x - mean) / n` times the gradient of its variance,
+    /// where `n` is the number of elements reduced over.
+    ///
+    /// # Returns
+    /// * `Ok(ArcTensor)` with the shape of the input
+    /// * `Err(MlError::TensorError)` with `InvalidShape` if `grad` does not have the
+    ///   number of elements of the output, or `InvalidAxis` if dim is out of range
+    #[cfg(feature = "enable_backpropagation")]
+    fn backward(&'t mut self, grad: &'t dyn TensorBase<f32>) -> Self::Gradiant {
+        let shape = self.tensor.shape();
+        let (dim, keepdim) = self.var.unwrap_or((None, false));
+        let (outer_dims, dim_size, stride, output_shape) = match dim {
+            None => (1, self.tensor.numel(), 1, vec![]),
+            Some(d) => {
+                let dim = normalize_dim(d, self.tensor.ndim())?;
+                let mut output_shape = shape.to_vec();
+                if keepdim {
+                    output_shape[dim] = 1;
+                } else {
+                    output_shape.remove(dim);
+                }
+                (shape[..dim].iter().product(), shape[dim], shape[dim + 1..].iter().product(), output_shape)
+            }
+        };
+        if grad.numel() != outer_dims * stride {
+            return Err(MlError::TensorError(TensorError::InvalidShape {
+                expected: output_shape,
+                got: grad.shape().to_vec(),
+            }));
+        }
+
+        let data = self.tensor.data();
+        let mut result = vec![0.0; data.len()];
+        for i in 0..outer_dims {
+            for j in 0..stride {
+                let offsets = (0..dim_size).map(|k| (i * dim_size + k) * stride + j);
+                let mean = offsets.clone().map(|idx| data[idx]).sum::<f32>() / dim_size as f32;
+                let scale = 2.0 * grad.data()[i * stride + j] / dim_size as f32;
+                offsets.for_each(|idx| result[idx] = scale * (data[idx] - mean));
+            }
+        }
+        Tensor::<f32>::from_vec(result, shape)
+    }
+
+    fn backend(&self) -> &Arc<dyn Backend> {
+        &self.backend
+    }
+}
+
+
+impl<'t> Concat<'t, f32> {
+    /// Creates a concatenation of `tensors` along `dim`, validating their shapes
+    ///
+    /// # Returns
+    /// * `Ok(Concat)` if the tensors can be joined
+    /// * `Err(MlError::TensorError)` with `EmptyTensor` if `tensors` is empty, `InvalidShape`
+    ///   if the shapes differ outside `dim`, or `InvalidAxis` if `dim` is out of range
+    pub fn new(tensors: &[&'t dyn TensorBase<f32>], dim: i32) -> MlResult<Self> {
+        check_concat_shapes(tensors, dim).map_err(op_context("Concat", tensors))?;
+        Ok(Self { tensors: tensors.to_vec(), dim })
+    }
+
+    /// Joins the inputs along `dim`
+    ///
+    /// # Returns
+    /// A new tensor whose size along `dim` is the sum of the inputs' sizes
+    pub fn forward(&mut self) -> MlResult<ArcTensor<f32>> {
+        let tensor = concat(&self.tensors, self.dim)?;
+        #[cfg(feature = "enable_backpropagation")]
+        let tensor = with_grad_fn(tensor, "concat", &self.tensors);
+        Ok(tensor)
+    }
+
+    /// Splits the gradient of the output back into one gradient per input
+    ///
+    /// This is the inverse of the forward pass: `grad` is split along `dim` into pieces of
+    /// the inputs' sizes, in input order.
+    ///
+    /// # Returns
+    /// * `Ok(Vec<ArcTensor>)` with the gradient of each input, shaped like that input
+    /// * `Err(MlError::TensorError)` with `InvalidShape` if `grad` does not match the shape of the output
+    #[cfg(feature = "enable_backpropagation")]
+    pub fn backward(&self, grad: &dyn TensorBase<f32>) -> MlResult<Vec<ArcTensor<f32>>> {
+        let dim = normalize_dim(self.dim, self.tensors[0].ndim())?;
+        let sizes: Vec<usize> = self.tensors.iter().map(|t| t.shape()[dim]).collect();
+        let mut shape = self.tensors[0].shape().to_vec();
+        shape[dim] = sizes.iter().sum();
+        if grad.shape() != shape {
+            return Err(MlError::TensorError(TensorError::InvalidShape {
+                expected: shape,
+                got: grad.shape().to_vec(),
+            }));
+        }
+        grad.split_with_sizes(&sizes, dim as i32)
+    }
+}
+
+/// Add trait implementation for owned tensors
+///
+/// # Arguments
+/// * `_other` - The tensor to add to self
+///
+/// # Returns
+/// A new tensor containing the element-wise sum
+///
+/// # Broadcasting
+/// * Operands of different shapes are broadcast from the trailing dimensions, e.g. a
+///   `[n]` bias is added to each row of a `[batch, n]` matrix
+impl std::ops::Add<&dyn TensorBase<f32>> for &dyn TensorBase<f32> {
+    type Output = ArcTensor<f32>;
+
+    fn add(self, other: &dyn TensorBase<f32>) -> Self::Output {
+        Add::<f32>::new(self, Some(other)).unwrap().forward().unwrap()
+    }
+}
+
+/// Subtract trait implementation for owned tensors
+///
+/// # Arguments
+/// * `_other` - The tensor to subtract from self
+///
+/// # Returns
+/// A new tensor containing the element-wise difference
+///
+/// # Broadcasting
+/// * Operands of different shapes are broadcast from the trailing dimensions
+impl std::ops::Sub<&dyn TensorBase<f32>> for &dyn TensorBase<f32> {
+    type Output = ArcTensor<f32>;
+
+    fn sub(self, other: &dyn TensorBase<f32>) -> Self::Output {
+        Sub::<f32>::new(self, Some(other)).unwrap().forward().unwrap()
+    }
+}
+
+/// Multiply trait implementation for owned tensors
+///
+/// # Arguments
+/// * `_other` - The tensor to multiply with self
+///
+/// # Returns
+/// A new tensor containing the element-wise product (Hadamard product)
+///
+/// # Note
+/// * This performs element-wise multiplication, not matrix multiplication
+/// * For matrix multiplication, use `matmul()` instead
+/// * Operands of different shapes are broadcast from the trailing dimensions
+impl std::ops::Mul<&dyn TensorBase<f32>> for &dyn TensorBase<f32> {
+    type Output = ArcTensor<f32>;
 
     fn mul(self, other: &dyn TensorBase<f32>) -> Self::Output {
         Mul::<f32>::new(self, Some(other)).unwrap().forward().unwrap()
@@ -941,6 +1326,9 @@ impl std::ops::Mul<&dyn TensorBase<f32>> for &dyn TensorBase<f32> {
 ///
 /// # Returns
 /// A new tensor containing the element-wise quotient
+///
+/// # Broadcasting
+/// * Operands of different shapes are broadcast from the trailing dimensions
 impl std::ops::Div<&dyn TensorBase<f32>> for &dyn TensorBase<f32> {
     type Output = ArcTensor<f32>;
 
@@ -954,7 +1342,7 @@ impl std::ops::Add for ArcTensor<f32> {
     type Output = ArcTensor<f32>;
 
     fn add(self, other: ArcTensor<f32>) -> Self::Output {
-        self.0.deref().add(other.0.deref())
+        std::ops::Add::add(self.0.deref(), other.0.deref())
     }
 }
 
@@ -962,7 +1350,7 @@ impl std::ops::Sub for ArcTensor<f32> {
     type Output = ArcTensor<f32>;
 
     fn sub(self, other: ArcTensor<f32>) -> Self::Output {
-        self.0.deref().sub(other.0.deref())
+        std::ops::Sub::sub(self.0.deref(), other.0.deref())
     }
 }
 
@@ -970,7 +1358,7 @@ impl std::ops::Mul for ArcTensor<f32> {
     type Output = ArcTensor<f32>;
 
     fn mul(self, other: ArcTensor<f32>) -> Self::Output {
-        self.0.deref().mul(other.0.deref())
+        std::ops::Mul::mul(self.0.deref(), other.0.deref())
     }
 }
 
@@ -978,7 +1366,480 @@ impl std::ops::Div for ArcTensor<f32> {
     type Output = ArcTensor<f32>;
 
     fn div(self, other: ArcTensor<f32>) -> Self::Output {
-        self.0.deref().div(other.0.deref())
+        std::ops::Div::div(self.0.deref(), other.0.deref())
+    }
+}
+
+/// Applies `f` to every element, recording the op under `name` for autograd
+fn map_scalar(tensor: &dyn TensorBase<f32>, _name: &'static str, f: impl Fn(f32) -> f32) -> ArcTensor<f32> {
+    let result = Tensor::<f32>::from_vec(tensor.data().iter().map(|&x| f(x)).collect(), tensor.shape())
+        .expect("mapped data always matches its shape");
+    #[cfg(feature = "enable_backpropagation")]
+    let result = with_grad_fn(result, _name, &[tensor]);
+    result
+}
+
+/// Implements `tensor op scalar` and `scalar op tensor` for the tensor handle types
+macro_rules! impl_scalar_op {
+    ($trait:ident, $method:ident, $name:literal, $rname:literal, |$x:ident, $s:ident| $forward:expr, $reversed:expr) => {
+        impl std::ops::$trait<f32> for &dyn TensorBase<f32> {
+            type Output = ArcTensor<f32>;
+
+            fn $method(self, $s: f32) -> Self::Output {
+                map_scalar(self, $name, |$x| $forward)
+            }
+        }
+
+        impl std::ops::$trait<f32> for ArcTensor<f32> {
+            type Output = ArcTensor<f32>;
+
+            fn $method(self, scalar: f32) -> Self::Output {
+                std::ops::$trait::$method(self.0.deref(), scalar)
+            }
+        }
+
+        impl std::ops::$trait<&dyn TensorBase<f32>> for f32 {
+            type Output = ArcTensor<f32>;
+
+            fn $method(self, tensor: &dyn TensorBase<f32>) -> Self::Output {
+                let $s = self;
+                map_scalar(tensor, $rname, |$x| $reversed)
+            }
+        }
+
+        impl std::ops::$trait<ArcTensor<f32>> for f32 {
+            type Output = ArcTensor<f32>;
+
+            fn $method(self, tensor: ArcTensor<f32>) -> Self::Output {
+                self.$method(tensor.0.deref())
+            }
+        }
+    };
+}
+
+impl_scalar_op!(Add, add, "add_scalar", "add_scalar", |x, s| x + s, s + x);
+impl_scalar_op!(Sub, sub, "sub_scalar", "rsub_scalar", |x, s| x - s, s - x);
+impl_scalar_op!(Mul, mul, "mul_scalar", "mul_scalar", |x, s| x * s, s * x);
+impl_scalar_op!(Div, div, "div_scalar", "rdiv_scalar", |x, s| x / s, s / x);
+
+/// Sum trait implementation for iterators of tensors
+///
+/// # Returns
+/// A new tensor containing the element-wise sum of all tensors,
+/// or a 0-dimensional zero (`Tensor::zeros()`) if the iterator is empty
+///
+/// # Panics
+/// Panics if the tensors do not all have the same shape
+impl std::iter::Sum for ArcTensor<f32> {
+    fn sum<I: Iterator<Item = ArcTensor<f32>>>(mut iter: I) -> Self {
+        let first = match iter.next() {
+            Some(tensor) => tensor,
+            None => return Tensor::<f32>::zeros(),
+        };
+
+        iter.fold(first, |acc, tensor| {
+            if let Err(e) = acc.chk_shape(tensor.deref()) {
+                panic!("{}", e);
+            }
+            acc + tensor
+        })
+    }
+}
+
+impl Tensor<f32> {
+    /// Updates the tensor in place with an exponential moving average
+    ///
+    /// Computes `self = decay * self + (1 - decay) * new` without reallocating the buffer.
+    ///
+    /// # Arguments
+    /// * `new` - The new observation, with the same shape as `self`
+    /// * `decay` - The weight of the current value, in `[0, 1]`
+    ///
+    /// # Returns
+    /// * `Ok(())` on success
+    /// * `Err(MlError::TensorError)` if the shapes differ, `decay` is out of range or the
+    ///   tensor is part of an autograd graph
+    pub fn ema_update(&mut self, new: &dyn TensorBase<f32>, decay: f32) -> MlResult<()> {
+        self.check_in_place("ema_update")?;
+        if !(0.0..=1.0).contains(&decay) {
+            return Err(MlError::TensorError(TensorError::InvalidOperation {
+                op: "ema_update",
+                reason: format!("decay must be in [0, 1], got {}", decay),
+            }));
+        }
+        self.chk_shape(new)?;
+
+        self.elements_mut().iter_mut().zip(new.data()).for_each(|(x, &n)| *x = decay * *x + (1.0 - decay) * n);
+        Ok(())
+    }
+
+    /// Adds `other` element-wise in place
+    ///
+    /// # Returns
+    /// * `Ok(())` on success
+    /// * `Err(MlError::TensorError)` with `InvalidShape` if the shapes differ
+    pub fn add_(&mut self, other: &dyn TensorBase<f32>) -> MlResult<()> {
+        self.zip_mut(other, |x, y| *x += y)
+    }
+
+    /// Subtracts `other` element-wise in place
+    ///
+    /// # Returns
+    /// * `Ok(())` on success
+    /// * `Err(MlError::TensorError)` with `InvalidShape` if the shapes differ
+    pub fn sub_(&mut self, other: &dyn TensorBase<f32>) -> MlResult<()> {
+        self.zip_mut(other, |x, y| *x -= y)
+    }
+
+    /// Multiplies by `other` element-wise in place
+    ///
+    /// # Returns
+    /// * `Ok(())` on success
+    /// * `Err(MlError::TensorError)` with `InvalidShape` if the shapes differ
+    pub fn mul_(&mut self, other: &dyn TensorBase<f32>) -> MlResult<()> {
+        self.zip_mut(other, |x, y| *x *= y)
+    }
+
+    /// Divides by `other` element-wise in place
+    ///
+    /// # Returns
+    /// * `Ok(())` on success
+    /// * `Err(MlError::TensorError)` with `InvalidShape` if the shapes differ
+    pub fn div_(&mut self, other: &dyn TensorBase<f32>) -> MlResult<()> {
+        self.zip_mut(other, |x, y| *x /= y)
+    }
+
+    fn zip_mut(&mut self, other: &dyn TensorBase<f32>, f: impl Fn(&mut f32, f32)) -> MlResult<()> {
+        self.check_in_place("in-place arithmetic")?;
+        self.chk_shape(other)?;
+        self.elements_mut().iter_mut().zip(other.data()).for_each(|(x, &y)| f(x, y));
+        Ok(())
+    }
+}
+
+/// Implements a compound assignment operator for owned tensors
+///
+/// The tensor operand must have the same shape as the tensor being assigned to.
+/// Since the std traits cannot return a `Result`, a shape mismatch or a tensor that is
+/// part of an autograd graph panics with the `TensorError` message; use the fallible
+/// method (`add_`, `sub_`, ...) to handle it instead.
+macro_rules! impl_assign_op {
+    ($trait:ident, $method:ident, $fallible:ident, |$x:ident, $s:ident| $scalar:expr) => {
+        impl std::ops::$trait<&dyn TensorBase<f32>> for Tensor<f32> {
+            fn $method(&mut self, other: &dyn TensorBase<f32>) {
+                if let Err(e) = self.$fallible(other) {
+                    panic!("{}", e);
+                }
+            }
+        }
+
+        impl std::ops::$trait<&ArcTensor<f32>> for Tensor<f32> {
+            fn $method(&mut self, other: &ArcTensor<f32>) {
+                std::ops::$trait::$method(self, other.deref());
+            }
+        }
+
+        impl std::ops::$trait<f32> for Tensor<f32> {
+            fn $method(&mut self, $s: f32) {
+                if let Err(e) = self.check_in_place("in-place arithmetic") {
+                    panic!("{}", e);
+                }
+                self.elements_mut().iter_mut().for_each(|$x| $scalar);
+            }
+        }
+    };
+}
+
+impl_assign_op!(AddAssign, add_assign, add_, |x, s| *x += s);
+impl_assign_op!(SubAssign, sub_assign, sub_, |x, s| *x -= s);
+impl_assign_op!(MulAssign, mul_assign, mul_, |x, s| *x *= s);
+impl_assign_op!(DivAssign, div_assign, div_, |x, s| *x /= s);
+
+impl dyn TensorBase<f32> + '_ {
+    /// Adds `other` element-wise, see [`Add`]
+    ///
+    /// ```
+    /// # use MIT::prelude::*;
+    /// # fn main() -> MlResult<()> {
+    /// let a = Tensor::<f32>::from_vec(vec![1.0, 2.0], &[2])?;
+    /// let b = Tensor::<f32>::from_vec(vec![3.0, 4.0], &[2])?;
+    /// assert_eq!(a.add(&b)?.data(), &[4.0, 6.0]);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn add(&self, other: &(impl AsTensor<f32> + ?Sized)) -> MlResult<ArcTensor<f32>> {
+        Add::new(self, Some(other.as_tensor()))?.forward()
+    }
+
+    /// Subtracts `other` element-wise, see [`Sub`]
+    ///
+    /// ```
+    /// # use MIT::prelude::*;
+    /// # fn main() -> MlResult<()> {
+    /// let a = Tensor::<f32>::from_vec(vec![1.0, 2.0], &[2])?;
+    /// let b = Tensor::<f32>::from_vec(vec![3.0, 4.0], &[2])?;
+    /// assert_eq!(a.sub(&b)?.data(), &[-2.0, -2.0]);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn sub(&self, other: &(impl AsTensor<f32> + ?Sized)) -> MlResult<ArcTensor<f32>> {
+        Sub::new(self, Some(other.as_tensor()))?.forward()
+    }
+
+    /// Multiplies by `other` element-wise, broadcasting the operands, see [`Mul`]
+    ///
+    /// ```
+    /// # use MIT::prelude::*;
+    /// # fn main() -> MlResult<()> {
+    /// let a = Tensor::<f32>::from_vec(vec![1.0, 2.0, 3.0, 4.0], &[2, 2])?;
+    /// let b = Tensor::<f32>::from_vec(vec![10.0, 100.0], &[2])?;
+    /// assert_eq!(a.mul(&b)?.data(), &[10.0, 200.0, 30.0, 400.0]);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn mul(&self, other: &(impl AsTensor<f32> + ?Sized)) -> MlResult<ArcTensor<f32>> {
+        Mul::new(self, Some(other.as_tensor()))?.forward()
+    }
+
+    /// Divides by `other` element-wise, see [`Div`]
+    ///
+    /// ```
+    /// # use MIT::prelude::*;
+    /// # fn main() -> MlResult<()> {
+    /// let a = Tensor::<f32>::from_vec(vec![1.0, 2.0], &[2])?;
+    /// let b = Tensor::<f32>::from_vec(vec![4.0, 4.0], &[2])?;
+    /// assert_eq!(a.div(&b)?.data(), &[0.25, 0.5]);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn div(&self, other: &(impl AsTensor<f32> + ?Sized)) -> MlResult<ArcTensor<f32>> {
+        Div::new(self, Some(other.as_tensor()))?.forward()
+    }
+
+    /// Computes the matrix product with `other`, see [`Matmul`]
+    ///
+    /// ```
+    /// # use MIT::prelude::*;
+    /// # fn main() -> MlResult<()> {
+    /// let a = Tensor::<f32>::new(vec![vec![1.0, 2.0], vec![3.0, 4.0]])?;
+    /// let b = Tensor::<f32>::new(vec![vec![5.0, 6.0], vec![7.0, 8.0]])?;
+    /// assert_eq!(a.matmul(&b)?.data(), &[19.0, 22.0, 43.0, 50.0]);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn matmul(&self, other: &(impl AsTensor<f32> + ?Sized)) -> MlResult<ArcTensor<f32>> {
+        Matmul::new(self, Some(other.as_tensor()))?.forward()
+    }
+
+    /// Applies `e^x` element-wise, see [`Exp`]
+    ///
+    /// ```
+    /// # use MIT::prelude::*;
+    /// # fn main() -> MlResult<()> {
+    /// let a = Tensor::<f32>::from_vec(vec![0.0], &[1])?;
+    /// assert_eq!(a.exp()?.data(), &[1.0]);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn exp(&self) -> MlResult<ArcTensor<f32>> {
+        Exp::new(self, None)?.forward()
+    }
+
+    /// Negates each element, see [`Neg`]
+    ///
+    /// ```
+    /// # use MIT::prelude::*;
+    /// # fn main() -> MlResult<()> {
+    /// let a = Tensor::<f32>::from_vec(vec![1.0, -2.0], &[2])?;
+    /// assert_eq!(a.neg()?.data(), &[-1.0, 2.0]);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn neg(&self) -> MlResult<ArcTensor<f32>> {
+        Neg::new(self, None)?.forward()
+    }
+
+    /// Takes the square root of each element, see [`Sqrt`]
+    ///
+    /// ```
+    /// # use MIT::prelude::*;
+    /// # fn main() -> MlResult<()> {
+    /// let a = Tensor::<f32>::from_vec(vec![4.0, 9.0], &[2])?;
+    /// assert_eq!(a.sqrt()?.data(), &[2.0, 3.0]);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn sqrt(&self) -> MlResult<ArcTensor<f32>> {
+        Sqrt::new(self, None)?.forward()
+    }
+
+    /// Takes the absolute value of each element, see [`Abs`]
+    ///
+    /// ```
+    /// # use MIT::prelude::*;
+    /// # fn main() -> MlResult<()> {
+    /// let a = Tensor::<f32>::from_vec(vec![-1.5, 2.0], &[2])?;
+    /// assert_eq!(a.abs()?.data(), &[1.5, 2.0]);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn abs(&self) -> MlResult<ArcTensor<f32>> {
+        Abs::new(self, None)?.forward()
+    }
+
+    /// Squares each element, see [`Square`]
+    ///
+    /// ```
+    /// # use MIT::prelude::*;
+    /// # fn main() -> MlResult<()> {
+    /// let a = Tensor::<f32>::from_vec(vec![-3.0, 2.0], &[2])?;
+    /// assert_eq!(a.square()?.data(), &[9.0, 4.0]);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn square(&self) -> MlResult<ArcTensor<f32>> {
+        Square::new(self, None)?.forward()
+    }
+
+    /// Takes the natural logarithm of each element, see [`Log`]
+    ///
+    /// ```
+    /// # use MIT::prelude::*;
+    /// # fn main() -> MlResult<()> {
+    /// let a = Tensor::<f32>::from_vec(vec![1.0], &[1])?;
+    /// assert_eq!(a.log()?.data(), &[0.0]);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn log(&self) -> MlResult<ArcTensor<f32>> {
+        Log::new(self, None)?.forward()
+    }
+
+    /// Raises each element to the power `exponent`, see [`Pow`]
+    ///
+    /// ```
+    /// # use MIT::prelude::*;
+    /// # fn main() -> MlResult<()> {
+    /// let a = Tensor::<f32>::from_vec(vec![2.0, 3.0], &[2])?;
+    /// assert_eq!(a.pow(2.0)?.data(), &[4.0, 9.0]);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn pow(&self, exponent: f32) -> MlResult<ArcTensor<f32>> {
+        let mut op = Pow::new(self, None)?;
+        op.power = Some(exponent);
+        op.forward()
+    }
+
+    /// Raises each element to the power of the matching element of `exponent`, broadcasting, see [`Pow`]
+    ///
+    /// ```
+    /// # use MIT::prelude::*;
+    /// # fn main() -> MlResult<()> {
+    /// let a = Tensor::<f32>::from_vec(vec![2.0, 3.0], &[2])?;
+    /// let e = Tensor::<f32>::from_vec(vec![3.0, 2.0], &[2])?;
+    /// assert_eq!(a.pow_tensor(&e)?.data(), &[8.0, 9.0]);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn pow_tensor(&self, exponent: &(impl AsTensor<f32> + ?Sized)) -> MlResult<ArcTensor<f32>> {
+        let mut op = Pow::new(self, None)?;
+        op.exponent = Some(exponent.as_tensor());
+        op.forward()
+    }
+
+    /// Returns the `k` largest elements of the last dimension and their indices, see [`Topk`]
+    ///
+    /// ```
+    /// # use MIT::prelude::*;
+    /// # fn main() -> MlResult<()> {
+    /// let a = Tensor::<f32>::from_vec(vec![1.0, 4.0, 2.0, 3.0], &[4])?;
+    /// let (values, indices) = a.topk(2, true)?;
+    /// assert_eq!(values.data(), &[4.0, 3.0]);
+    /// assert_eq!(indices.data(), &[1.0, 3.0]);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn topk(&self, k: usize, sorted: bool) -> MlResult<(ArcTensor<f32>, ArcTensor<f32>)> {
+        Topk::with_k(self, k, sorted)?.forward()
+    }
+
+    /// Returns the maximum values and their indices along `dim`, or over all elements if `dim` is `None`, see [`Matmax`]
+    ///
+    /// ```
+    /// # use MIT::prelude::*;
+    /// # fn main() -> MlResult<()> {
+    /// let a = Tensor::<f32>::new(vec![vec![1.0, 5.0], vec![7.0, 2.0]])?;
+    /// let (values, indices) = a.matmax(Some(-1), false)?;
+    /// assert_eq!(values.data(), &[5.0, 7.0]);
+    /// assert_eq!(indices.data(), &[1.0, 0.0]);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn matmax(&self, dim: Option<i32>, keepdim: bool) -> MlResult<(ArcTensor<f32>, ArcTensor<f32>)> {
+        Matmax::with_dim(self, dim, keepdim)?.forward()
+    }
+
+    /// Returns the maximum values and their indices like [`matmax`](Self::matmax), choosing how NaNs are handled
+    ///
+    /// With `nan_propagate` a NaN makes the maximum of its slice NaN; without it NaNs are skipped.
+    pub fn reduce_max(&self, dim: Option<i32>, keepdim: bool, nan_propagate: bool) -> MlResult<(ArcTensor<f32>, ArcTensor<f32>)> {
+        let mut op = Matmax::with_dim(self, dim, keepdim)?;
+        op.nan_propagate = nan_propagate;
+        op.forward()
+    }
+
+    /// Returns the minimum values and their indices, the counterpart of [`reduce_max`](Self::reduce_max)
+    pub fn reduce_min(&self, dim: Option<i32>, keepdim: bool, nan_propagate: bool) -> MlResult<(ArcTensor<f32>, ArcTensor<f32>)> {
+        let negated = Tensor::<f32>::from_vec(self.data().iter().map(|&x| -x).collect(), self.shape())?;
+        let (values, indices) = negated.reduce_max(dim, keepdim, nan_propagate)?;
+        Ok((Tensor::<f32>::from_vec(values.data().iter().map(|&x| -x).collect(), values.shape())?, indices))
+    }
+
+    /// Divides element-wise by `other + eps`, broadcasting the operands
+    ///
+    /// # Arguments
+    /// * `other` - The denominator
+    /// * `eps` - A small value added to the denominator to avoid division by zero
+    ///
+    /// # Returns
+    /// * `Ok(ArcTensor)` with the broadcast shape of both operands
+    /// * `Err(MlError::TensorError)` with `InvalidShape` if the shapes cannot be broadcast
+    pub fn div_eps(&self, other: &dyn TensorBase<f32>, eps: f32) -> MlResult<ArcTensor<f32>> {
+        broadcast_op(self, other, |x, y| x / (y + eps))
+    }
+
+    /// Linearly interpolates towards `end` as `self + weight * (end - self)`, broadcasting the operands
+    ///
+    /// A weight of 0 gives `self` and 1 gives `end`; an exponential moving average update
+    /// is `average.lerp(&*value, 1.0 - decay)`.
+    ///
+    /// # Arguments
+    /// * `end` - The tensor to interpolate towards
+    /// * `weight` - The interpolation weight
+    ///
+    /// # Returns
+    /// * `Ok(ArcTensor)` with the broadcast shape of both operands
+    /// * `Err(MlError::TensorError)` with `InvalidShape` if the shapes cannot be broadcast
+    pub fn lerp(&self, end: &dyn TensorBase<f32>, weight: f32) -> MlResult<ArcTensor<f32>> {
+        broadcast_op(self, end, |start, end| start + weight * (end - start))
+    }
+
+    /// Like [`lerp`](Self::lerp) with a weight per element, broadcasting all three operands
+    ///
+    /// # Returns
+    /// * `Ok(ArcTensor)` with the broadcast shape of the operands
+    /// * `Err(MlError::TensorError)` with `InvalidShape` if the shapes cannot be broadcast
+    pub fn lerp_tensor(&self, end: &dyn TensorBase<f32>, weight: &dyn TensorBase<f32>) -> MlResult<ArcTensor<f32>> {
+        let shape = broadcast_shape(&broadcast_shape(self.shape(), end.shape())?, weight.shape())?;
+        let size: usize = shape.iter().product();
+        let data = (0..size).map(|i| {
+            let start = self.data()[broadcast_index(i, &shape, self.shape())];
+            let end = end.data()[broadcast_index(i, &shape, end.shape())];
+            let weight = weight.data()[broadcast_index(i, &shape, weight.shape())];
+            start + weight * (end - start)
+        }).collect();
+        Tensor::<f32>::from_vec(data, &shape)
     }
 }
 
@@ -991,6 +1852,207 @@ mod tests {
 
     use super::*;
 
+    #[test]
+    fn test_iter_sum() -> MlResult<()> {
+        let tensors = vec![
+            Tensor::<f32>::from_vec(vec![1.0, 2.0, 3.0, 4.0], &[2, 2])?,
+            Tensor::<f32>::from_vec(vec![10.0, 20.0, 30.0, 40.0], &[2, 2])?,
+            Tensor::<f32>::from_vec(vec![0.5, 0.5, 0.5, 0.5], &[2, 2])?,
+        ];
+        let sum: ArcTensor<f32> = tensors.into_iter().sum();
+        assert_eq!(sum.shape(), &[2, 2]);
+        assert_eq!(sum.data(), &[11.5, 22.5, 33.5, 44.5]);
+
+        // an empty sum is a 0-d zero that still broadcasts against other tensors
+        let empty: ArcTensor<f32> = Vec::<ArcTensor<f32>>::new().into_iter().sum();
+        assert!(empty.shape().is_empty());
+        assert_eq!(empty.item()?, 0.0);
+        assert_eq!(empty.add(sum.deref())?.data(), sum.data());
+        Ok(())
+    }
+
+    #[test]
+    #[should_panic(expected = "Invalid shape")]
+    fn test_iter_sum_shape_mismatch() {
+        let tensors = vec![
+            Tensor::<f32>::from_vec(vec![1.0, 2.0, 3.0, 4.0], &[2, 2]).unwrap(),
+            Tensor::<f32>::from_vec(vec![1.0, 2.0], &[2]).unwrap(),
+        ];
+        let _: ArcTensor<f32> = tensors.into_iter().sum();
+    }
+
+    #[test]
+    fn test_ema_update() -> MlResult<()> {
+        let mut running = Tensor::<f32>::from_vec_owned(vec![1.0, 2.0, 3.0], &[3])?;
+        let new = Tensor::<f32>::from_vec(vec![3.0, 4.0, 5.0], &[3])?;
+
+        running.ema_update(new.deref(), 0.75)?;
+        assert_eq!(running.data(), &[1.5, 2.5, 3.5]);
+        running.ema_update(new.deref(), 0.0)?;
+        assert_eq!(running.data(), new.data());
+
+        let wrong_shape = Tensor::<f32>::from_vec(vec![1.0, 2.0], &[2])?;
+        assert!(running.ema_update(wrong_shape.deref(), 0.5).is_err());
+        assert!(running.ema_update(new.deref(), 1.5).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_assign_ops() -> MlResult<()> {
+        let mut acc = Tensor::<f32>::from_vec_owned(vec![1.0, 2.0, 3.0], &[3])?;
+        let other = Tensor::<f32>::from_vec(vec![2.0, 4.0, -1.0], &[3])?;
+
+        acc += &other;
+        assert_eq!(acc.data(), &[3.0, 6.0, 2.0]);
+        acc -= other.deref();
+        assert_eq!(acc.data(), &[1.0, 2.0, 3.0]);
+        acc *= &other;
+        assert_eq!(acc.data(), &[2.0, 8.0, -3.0]);
+        acc /= &other;
+        assert_eq!(acc.data(), &[1.0, 2.0, 3.0]);
+
+        acc += 1.0;
+        acc *= 2.0;
+        acc -= 0.5;
+        acc /= 0.5;
+        assert_eq!(acc.data(), &[7.0, 11.0, 15.0]);
+        assert_eq!(acc.shape(), &[3]);
+
+        let wrong_shape = Tensor::<f32>::from_vec(vec![1.0, 2.0], &[2])?;
+        assert!(acc.add_(wrong_shape.deref()).is_err());
+        assert!(acc.div_(wrong_shape.deref()).is_err());
+        assert_eq!(acc.data(), &[7.0, 11.0, 15.0]);
+        Ok(())
+    }
+
+    #[test]
+    #[should_panic(expected = "Invalid shape")]
+    fn test_assign_ops_shape_mismatch() {
+        let mut acc = Tensor::<f32>::from_vec_owned(vec![1.0, 2.0, 3.0], &[3]).unwrap();
+        let other = Tensor::<f32>::from_vec(vec![1.0, 2.0], &[2]).unwrap();
+        acc += &other;
+    }
+
+    #[test]
+    fn test_div_eps() -> MlResult<()> {
+        let x = Tensor::<f32>::from_vec(vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0], &[2, 3])?;
+        let norm = Tensor::<f32>::from_vec(vec![0.0, 1.0], &[2, 1])?;
+        let result = x.div_eps(norm.deref(), 1e-6)?;
+
+        assert_eq!(result.shape(), &[2, 3]);
+        assert!(result.data().iter().all(|v| v.is_finite()));
+        assert!((result.data()[0] - 1e6).abs() < 1.0);
+        assert!((result.data()[5] - 6.0 / (1.0 + 1e-6)).abs() < 1e-6);
+
+        let wrong_shape = Tensor::<f32>::from_vec(vec![1.0, 2.0], &[2])?;
+        assert!(x.div_eps(wrong_shape.deref(), 1e-6).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_lerp() -> MlResult<()> {
+        let start = Tensor::<f32>::from_vec(vec![0.0, 10.0, 20.0, 30.0], &[2, 2])?;
+        let end = Tensor::<f32>::from_vec(vec![4.0, 2.0], &[2])?;
+
+        assert_eq!(start.lerp(end.deref(), 0.0)?.data(), start.data());
+        assert_eq!(start.lerp(end.deref(), 1.0)?.data(), &[4.0, 2.0, 4.0, 2.0]);
+        let half = start.lerp(end.deref(), 0.5)?;
+        assert_eq!(half.shape(), &[2, 2]);
+        assert_eq!(half.data(), &[2.0, 6.0, 12.0, 16.0]);
+
+        let weights = Tensor::<f32>::from_vec(vec![0.0, 1.0], &[2, 1])?;
+        let per_row = start.lerp_tensor(end.deref(), weights.deref())?;
+        assert_eq!(per_row.data(), &[0.0, 10.0, 4.0, 2.0]);
+
+        let wrong_shape = Tensor::<f32>::from_vec(vec![1.0; 3], &[3])?;
+        assert!(matches!(
+            start.lerp(wrong_shape.deref(), 0.5),
+            Err(MlError::TensorError(TensorError::InvalidShape { .. }))
+        ));
+        assert!(start.lerp_tensor(end.deref(), wrong_shape.deref()).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_pow_tensor_exponent() -> MlResult<()> {
+        let base = Tensor::<f32>::from_vec(vec![2.0, 3.0, 4.0, 9.0, -8.0, 0.0], &[2, 3])?;
+        let exponent = Tensor::<f32>::from_vec(vec![3.0, 2.0, 0.5, 0.5, 1.0 / 3.0, 0.0], &[2, 3])?;
+        let result = ops!(base, Pow, exponent)?;
+        assert_eq!(result.shape(), &[2, 3]);
+        assert_eq!(&result.data()[..4], &[8.0, 9.0, 2.0, 3.0]);
+        // negative bases with non-integer exponents have no real result
+        assert!(result.data()[4].is_nan());
+        assert_eq!(result.data()[5], 1.0);
+
+        let column = Tensor::<f32>::from_vec(vec![2.0, 0.0], &[2, 1])?;
+        let broadcast = ops!(base, Pow, column)?;
+        assert_eq!(broadcast.data(), &[4.0, 9.0, 16.0, 1.0, 1.0, 1.0]);
+
+        assert_eq!(ops!(base, Pow, 2.0)?.data(), &[4.0, 9.0, 16.0, 81.0, 64.0, 0.0]);
+
+        let wrong_shape = Tensor::<f32>::from_vec(vec![1.0, 2.0], &[2])?;
+        assert!(ops!(base, Pow, wrong_shape).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_scalar_operators() -> MlResult<()> {
+        let t = Tensor::<f32>::from_vec(vec![1.0, 2.0, -4.0], &[3])?;
+
+        assert_eq!((t.contiguous() + 2.0).data(), &[3.0, 4.0, -2.0]);
+        assert_eq!((t.contiguous() - 2.0).data(), &[-1.0, 0.0, -6.0]);
+        assert_eq!((t.contiguous() * 2.0).data(), &[2.0, 4.0, -8.0]);
+        assert_eq!((t.contiguous() / 2.0).data(), &[0.5, 1.0, -2.0]);
+        assert_eq!((2.0 + t.contiguous()).data(), &[3.0, 4.0, -2.0]);
+        assert_eq!((2.0 - t.contiguous()).data(), &[1.0, 0.0, 6.0]);
+        assert_eq!((2.0 * t.contiguous()).data(), &[2.0, 4.0, -8.0]);
+        assert_eq!((2.0 / t.contiguous()).data(), &[2.0, 1.0, -0.5]);
+
+        // borrowed trait objects work the same way and keep the shape
+        let matrix = Tensor::<f32>::from_vec(vec![1.0, 2.0, 3.0, 4.0], &[2, 2])?;
+        let doubled = matrix.deref() * 2.0;
+        assert_eq!(doubled.shape(), &[2, 2]);
+        assert_eq!((1.0 - matrix.deref()).data(), &[0.0, -1.0, -2.0, -3.0]);
+
+        assert_eq!(((t * 2.0 + 1.0) / 4.0).data(), &[0.75, 1.25, -1.75]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_mul_broadcast_forward() -> MlResult<()> {
+        let x = Tensor::<f32>::from_vec(vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0], &[2, 3])?;
+        let w = Tensor::<f32>::from_vec(vec![2.0, 0.5, -1.0], &[3])?;
+
+        let product = ops!(x, Mul, w)?;
+        assert_eq!(product.shape(), &[2, 3]);
+        assert_eq!(product.data(), &[2.0, 1.0, -3.0, 8.0, 2.5, -6.0]);
+        assert!(Mul::new(x.deref(), Some(Tensor::<f32>::from_vec(vec![1.0, 2.0], &[2])?.deref())).is_err());
+        Ok(())
+    }
+
+    #[cfg(feature = "enable_backpropagation")]
+    #[test]
+    fn test_mul_backward_broadcast() -> MlResult<()> {
+        let x = Tensor::<f32>::from_vec(vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0], &[2, 3])?;
+        let w = Tensor::<f32>::from_vec(vec![2.0, 0.5, -1.0], &[3])?;
+        let grad = Tensor::<f32>::from_vec(vec![1.0, 1.0, 1.0, 2.0, 2.0, 2.0], &[2, 3])?;
+
+        let (grad_x, grad_w) = Mul::new(x.deref(), Some(w.deref()))?.backward(grad.deref())?;
+        assert_eq!(grad_x.shape(), &[2, 3]);
+        assert_eq!(grad_x.data(), &[2.0, 0.5, -1.0, 4.0, 1.0, -2.0]);
+        assert_eq!(grad_w.shape(), &[3]);
+        assert_eq!(grad_w.data(), &[9.0, 12.0, 15.0]);
+
+        // the order of the operands does not matter
+        let (grad_w, grad_x) = Mul::new(w.deref(), Some(x.deref()))?.backward(grad.deref())?;
+        assert_eq!(grad_w.data(), &[9.0, 12.0, 15.0]);
+        assert_eq!(grad_x.shape(), &[2, 3]);
+
+        let wrong = Tensor::<f32>::from_vec(vec![1.0, 1.0, 1.0], &[3])?;
+        assert!(Mul::new(x.deref(), Some(w.deref()))?.backward(wrong.deref()).is_err());
+        Ok(())
+    }
+
     #[test]
     fn test_topk() -> MlResult<()> {
         // Test 1: Basic 1D tensor
@@ -1012,14 +2074,21 @@ mod tests {
         assert_eq!(values.data(), &[4.0, 3.0, 5.0]);
         assert_eq!(indices.data(), &[1.0, 2.0, 4.0]);
 
+        // Invalid k is rejected at construction, and an unset k in forward
+        assert!(Topk::with_k(tensor.deref(), 0, true).is_err());
+        assert!(Topk::with_k(tensor.deref(), 6, true).is_err());
+        assert!(Topk::new(tensor.deref(), None)?.forward().is_err());
+
         Ok(())
     }
     #[test]
     fn test_max() -> MlResult<()> {
         // Test global maximum
-        let tensor = Tensor::<f32>::new(vec![vec![1.0, 2.0, 3.0], vec![4.0, 5.0, 6.0]]);
-        let (max_all, _) = ops!(tensor, Matmax, None, false)?;
+        let tensor = Tensor::<f32>::new(vec![vec![1.0, 2.0, 3.0], vec![4.0, 5.0, 6.0]])?;
+        let (max_all, index_all) = ops!(tensor, Matmax, None, false)?;
         assert_eq!(max_all.data(), &[6.0]);
+        assert!(index_all.shape().is_empty());
+        assert_eq!(index_all.item()?, 5.0);
 
         // Test maximum along dimension 0
         let (max_dim0, indices0) = ops!(tensor, Matmax, Some(0), true)?;
@@ -1038,6 +2107,202 @@ mod tests {
         assert_eq!(max_neg.data(), &[3.0, 6.0]);
         assert_eq!(indices_neg.data(), &[2.0, 2.0]);
 
+        assert!(Matmax::with_dim(tensor.deref(), Some(2), false).is_err());
+        assert!(Matmax::new(tensor.deref(), None)?.forward().is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_relu6_hardtanh() -> MlResult<()> {
+        let tensor = Tensor::<f32>::from_vec(vec![-2.0, -1.0, 0.0, 0.5, 6.0, 7.5], &[2, 3])?;
+
+        let relu6 = ops!(tensor, Relu6)?;
+        assert_eq!(relu6.shape(), &[2, 3]);
+        assert_eq!(relu6.data(), &[0.0, 0.0, 0.0, 0.5, 6.0, 6.0]);
+
+        assert_eq!(ops!(tensor, Hardtanh)?.data(), &[-1.0, -1.0, 0.0, 0.5, 1.0, 1.0]);
+        assert_eq!(ops!(tensor, Hardtanh, -1.5, 6.5)?.data(), &[-1.5, -1.0, 0.0, 0.5, 6.0, 6.5]);
+        assert!(ops!(tensor, Hardtanh, 1.0, -1.0).is_err());
+        assert!(ops!(tensor, Hardtanh, f32::NAN, 1.0).is_err());
+        Ok(())
+    }
+
+    #[cfg(feature = "enable_backpropagation")]
+    #[test]
+    fn test_relu6_hardtanh_backward() -> MlResult<()> {
+        let tensor = Tensor::<f32>::from_vec(vec![-2.0, 0.0, 0.5, 3.0, 6.0, 7.5], &[6])?;
+        let grad = Tensor::<f32>::from_vec(vec![1.0; 6], &[6])?;
+
+        // boundaries 0 and 6 receive no gradient
+        let mut relu6 = Relu6::new(tensor.deref(), None)?;
+        assert_eq!(relu6.backward(grad.deref())?.data(), &[0.0, 0.0, 1.0, 1.0, 0.0, 0.0]);
+
+        let mut hardtanh = Hardtanh::new(tensor.deref(), None)?;
+        hardtanh.bounds = (0.0, 5.0);
+        assert_eq!(hardtanh.backward(grad.deref())?.data(), &[0.0, 0.0, 1.0, 1.0, 0.0, 0.0]);
+
+        let wrong = Tensor::<f32>::from_vec(vec![1.0; 3], &[3])?;
+        let mut relu6 = Relu6::new(tensor.deref(), None)?;
+        assert!(relu6.backward(wrong.deref()).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_concat_op() -> MlResult<()> {
+        let a = Tensor::<f32>::from_vec(vec![1.0, 2.0], &[2, 1])?;
+        let b = Tensor::<f32>::from_vec(vec![3.0, 4.0, 5.0, 6.0], &[2, 2])?;
+        let joined = Concat::new(&[a.deref(), b.deref()], 1)?.forward()?;
+        assert_eq!(joined.shape(), &[2, 3]);
+        assert_eq!(joined.data(), &[1.0, 3.0, 4.0, 2.0, 5.0, 6.0]);
+
+        let err = Concat::new(&[a.deref(), b.deref()], 0).err().unwrap();
+        assert_eq!(err.to_string(), "Tensor error: Concat: shapes do not match (left [2, 1], right [2, 2])");
+        match err {
+            MlError::TensorError(TensorError::OpFailed { op: "Concat", shapes, .. }) => {
+                assert_eq!(shapes, vec![vec![2, 1], vec![2, 2]])
+            }
+            other => panic!("unexpected error {:?}", other),
+        }
+        Ok(())
+    }
+
+    #[cfg(feature = "enable_backpropagation")]
+    #[test]
+    fn test_concat_gradient_check() -> MlResult<()> {
+        let a = vec![0.5, -1.0, 2.0, 0.25];
+        let b = vec![1.5, -0.5, 3.0, 1.0, -2.0, 0.75];
+        // the loss is sum(concat(a, b) * weights), so its gradient wrt the output is `weights`
+        let weights = Tensor::<f32>::from_vec((0..10).map(|x| x as f32 * 0.3 - 1.0).collect(), &[2, 5])?;
+        let loss = |a: &[f32], b: &[f32]| -> MlResult<f32> {
+            let a = Tensor::<f32>::from_vec(a.to_vec(), &[2, 2])?;
+            let b = Tensor::<f32>::from_vec(b.to_vec(), &[2, 3])?;
+            let out = Concat::new(&[a.deref(), b.deref()], 1)?.forward()?;
+            Ok(out.data().iter().zip(weights.data()).map(|(x, w)| x * w).sum())
+        };
+
+        let ta = Tensor::<f32>::from_vec(a.clone(), &[2, 2])?;
+        let tb = Tensor::<f32>::from_vec(b.clone(), &[2, 3])?;
+        let grads = Concat::new(&[ta.deref(), tb.deref()], 1)?.backward(weights.deref())?;
+        assert_eq!(grads.len(), 2);
+        assert_eq!(grads[0].shape(), &[2, 2]);
+        assert_eq!(grads[1].shape(), &[2, 3]);
+
+        let h = 1e-2;
+        for (input, grad) in [(0, &grads[0]), (1, &grads[1])] {
+            for i in 0..grad.numel() {
+                let (mut plus, mut minus) = ((a.clone(), b.clone()), (a.clone(), b.clone()));
+                if input == 0 {
+                    plus.0[i] += h;
+                    minus.0[i] -= h;
+                } else {
+                    plus.1[i] += h;
+                    minus.1[i] -= h;
+                }
+                let numeric = (loss(&plus.0, &plus.1)? - loss(&minus.0, &minus.1)?) / (2.0 * h);
+                assert!((numeric - grad.data()[i]).abs() < 1e-3, "input {} element {}", input, i);
+            }
+        }
+
+        let wrong = Tensor::<f32>::from_vec(vec![1.0; 8], &[2, 4])?;
+        assert!(Concat::new(&[ta.deref(), tb.deref()], 1)?.backward(wrong.deref()).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_var() -> MlResult<()> {
+        let tensor = Tensor::<f32>::from_vec(vec![1.0, 2.0, 3.0, 4.0, 6.0, 8.0], &[2, 3])?;
+
+        let total = ops!(tensor, Var, None, false)?;
+        assert!((total.data()[0] - 17.0 / 3.0).abs() < 1e-5);
+
+        let rows = ops!(tensor, Var, Some(-1), true)?;
+        assert_eq!(rows.shape(), &[2, 1]);
+        assert!((rows.data()[0] - 2.0 / 3.0).abs() < 1e-6);
+        assert!((rows.data()[1] - 8.0 / 3.0).abs() < 1e-6);
+
+        // both methods agree on well-conditioned data
+        let stable = ops!(tensor, Var, Some(0), false, true)?;
+        let naive = ops!(tensor, Var, Some(0), false)?;
+        assert_eq!(stable.shape(), &[3]);
+        for (a, b) in stable.data().iter().zip(naive.data()) {
+            assert!((a - b).abs() < 1e-6);
+        }
+
+        assert!(ops!(tensor, Var, Some(2), false).is_err());
+        assert!(ops!(Tensor::<f32>::from_vec(vec![], &[0])?, Var, None, false).is_err());
+        Ok(())
+    }
+
+    #[cfg(feature = "enable_backpropagation")]
+    #[test]
+    fn test_var_backward() -> MlResult<()> {
+        let values = vec![1.0, 2.0, 4.0, 3.0, 7.0, -1.0];
+        let tensor = Tensor::<f32>::from_vec(values.clone(), &[2, 3])?;
+
+        // rows have means 7/3 and 3; d var / dx = 2 (x - mean) / 3
+        let grad = Tensor::<f32>::from_vec(vec![1.0, 0.5], &[2, 1])?;
+        let mut var = Var::new(tensor.deref(), None)?;
+        var.var = Some((Some(1), true));
+        let input_grad = var.backward(grad.deref())?;
+        assert_eq!(input_grad.shape(), &[2, 3]);
+        let expected = [-8.0 / 9.0, -2.0 / 9.0, 10.0 / 9.0, 0.0, 4.0 / 3.0, -4.0 / 3.0];
+        for (g, e) in input_grad.data().iter().zip(expected) {
+            assert!((g - e).abs() < 1e-5, "{} != {}", g, e);
+        }
+
+        // central differences over all elements
+        let h = 1e-2;
+        let mut var = Var::new(tensor.deref(), None)?;
+        let input_grad = var.backward(Tensor::<f32>::scalar(1.0).deref())?;
+        for i in 0..values.len() {
+            let (mut plus, mut minus) = (values.clone(), values.clone());
+            plus[i] += h;
+            minus[i] -= h;
+            let var_of = |v: Vec<f32>| -> MlResult<f32> { ops!(Tensor::<f32>::from_vec(v, &[6])?, Var, None, false)?.item() };
+            let numeric = (var_of(plus)? - var_of(minus)?) / (2.0 * h);
+            assert!((input_grad.data()[i] - numeric).abs() < 1e-2);
+        }
+
+        let mut var = Var::new(tensor.deref(), None)?;
+        assert!(var.backward(grad.deref()).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_var_stable_shifted() -> MlResult<()> {
+        // 1e6 + {4, 7, 13, 16} is exact in f32 and has a population variance of 22.5
+        let data: Vec<f32> = [4.0, 7.0, 13.0, 16.0].iter().cycle().take(40_000).map(|x| 1e6 + x).collect();
+        let tensor = Tensor::<f32>::from_vec(data, &[40_000])?;
+
+        let stable = ops!(tensor, Var, None, false, true)?.data()[0];
+        assert!((stable - 22.5).abs() < 1e-3, "stable variance {}", stable);
+
+        let naive = ops!(tensor, Var, None, false)?.data()[0];
+        assert!((naive - 22.5).abs() > (stable - 22.5).abs());
+        Ok(())
+    }
+
+    #[test]
+    fn test_max_nan() -> MlResult<()> {
+        let tensor = Tensor::<f32>::new(vec![vec![1.0, f32::NAN, 3.0], vec![f32::NAN, f32::NAN, f32::NAN]])?;
+
+        let (values, indices) = ops!(tensor, Matmax, Some(1), false)?;
+        assert!(values.data().iter().all(|v| v.is_nan()));
+        assert_eq!(indices.data(), &[1.0, 0.0]);
+        assert!(ops!(tensor, Matmax, None, false)?.0.data()[0].is_nan());
+
+        let (values, indices) = ops!(tensor, Matmax, Some(1), false, false)?;
+        assert_eq!(values.data()[0], 3.0);
+        assert!(values.data()[1].is_nan());
+        assert_eq!(indices.data(), &[2.0, 0.0]);
+        assert_eq!(ops!(tensor, Matmax, None, false, false)?.0.data(), &[3.0]);
+
+        let (min_values, min_indices) = tensor.reduce_min(Some(-1), true, false)?;
+        assert_eq!(min_values.shape(), &[2, 1]);
+        assert_eq!(min_values.data()[0], 1.0);
+        assert_eq!(min_indices.data()[0], 0.0);
+        assert!(tensor.reduce_max(Some(0), false, true)?.0.data().iter().all(|v| v.is_nan()));
         Ok(())
     }
 
@@ -1112,6 +2377,156 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_op_error_context() -> MlResult<()> {
+        use std::error::Error;
+
+        let a = Tensor::<f32>::from_vec(vec![0.0; 8 * 32 * 64], &[8, 32, 64])?;
+        let b = Tensor::<f32>::from_vec(vec![0.0; 8 * 16 * 64], &[8, 16, 64])?;
+        let err = ops!(a, Matmul, b).err().unwrap();
+        assert_eq!(
+            err.to_string(),
+            "Tensor error: Matmul: inner dimensions differ (left [8, 32, 64], right [8, 16, 64])"
+        );
+        let source = err.source().unwrap().source().unwrap();
+        assert!(source.to_string().starts_with("Invalid dimensions for matrix multiplication"));
+
+        let x = Tensor::<f32>::from_vec(vec![1.0; 6], &[2, 3])?;
+        let y = Tensor::<f32>::from_vec(vec![1.0; 4], &[2, 2])?;
+        assert_eq!(
+            ops!(x, Add, y).err().unwrap().to_string(),
+            "Tensor error: Add: shapes cannot be broadcast together (left [2, 3], right [2, 2])"
+        );
+        assert_eq!(
+            ops!(x, Matmax, Some(2), false).err().unwrap().to_string(),
+            "Tensor error: Matmax: invalid axis 2 for 2 dimensions (input [2, 3])"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_ops_macro_nested() -> MlResult<()> {
+        let a = Tensor::<f32>::from_vec(vec![1.0, 4.0, -2.0, 0.5], &[2, 2])?;
+        let b = Tensor::<f32>::from_vec(vec![0.5, 1.0, 2.0, -1.5], &[2, 2])?;
+        let eps = Tensor::<f32>::from_vec(vec![1e-3; 4], &[2, 2])?;
+
+        let nested = ops!(Exp(Mul(a, b)))?;
+        let product = ops!(a, Mul, b)?;
+        assert_eq!(nested.data(), ops!(product, Exp)?.data());
+
+        let distance = ops!(Sqrt(Add(Square(Sub(a, b)), eps)))?;
+        let diff = ops!(a, Sub, b)?;
+        let squared = ops!(diff, Square)?;
+        let shifted = ops!(squared, Add, eps)?;
+        assert_eq!(distance.shape(), &[2, 2]);
+        assert_eq!(distance.data(), ops!(shifted, Sqrt)?.data());
+
+        // each leaf is evaluated once, however deeply it is nested
+        let mut calls = 0;
+        let mut leaf = || {
+            calls += 1;
+            a.clone()
+        };
+        ops!(Neg(Add(Abs(leaf()), b)))?;
+        assert_eq!(calls, 1);
+
+        let wrong = Tensor::<f32>::from_vec(vec![1.0; 3], &[3])?;
+        assert!(matches!(
+            ops!(Sqrt(Add(Square(a), wrong))),
+            Err(MlError::TensorError(TensorError::OpFailed { op: "Add", .. }))
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn test_binary_ops_broadcast() -> MlResult<()> {
+        let matrix = Tensor::<f32>::from_vec(vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0], &[2, 3])?;
+        let bias = Tensor::<f32>::from_vec(vec![10.0, 20.0, 30.0], &[3])?;
+        let scale = Tensor::<f32>::from_vec(vec![2.0], &[1])?;
+        let row = Tensor::<f32>::from_vec(vec![1.0, 2.0, 3.0], &[1, 3])?;
+
+        assert_eq!(ops!(matrix, Add, bias)?.data(), &[11.0, 22.0, 33.0, 14.0, 25.0, 36.0]);
+        assert_eq!(ops!(matrix, Sub, row)?.data(), &[0.0, 0.0, 0.0, 3.0, 3.0, 3.0]);
+        assert_eq!(ops!(matrix, Div, scale)?.data(), &[0.5, 1.0, 1.5, 2.0, 2.5, 3.0]);
+        let scaled = ops!(scale, Mul, matrix)?;
+        assert_eq!(scaled.shape(), &[2, 3]);
+        assert_eq!(scaled.data(), &[2.0, 4.0, 6.0, 8.0, 10.0, 12.0]);
+
+        // the operator overloads broadcast the same way
+        let sum = matrix.deref() + bias.deref();
+        assert_eq!(sum.data(), ops!(matrix, Add, bias)?.data());
+        assert_eq!((matrix.clone() / scale.clone()).data(), &[0.5, 1.0, 1.5, 2.0, 2.5, 3.0]);
+
+        let wrong = Tensor::<f32>::from_vec(vec![1.0, 2.0], &[2])?;
+        let results = [ops!(matrix, Add, wrong), ops!(matrix, Sub, wrong), ops!(matrix, Mul, wrong), ops!(matrix, Div, wrong)];
+        for (op, result) in ["Add", "Sub", "Mul", "Div"].into_iter().zip(results) {
+            assert_eq!(
+                result.err().unwrap().to_string(),
+                format!("Tensor error: {}: shapes cannot be broadcast together (left [2, 3], right [2])", op)
+            );
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_ops_macro_returns_shape_errors() -> MlResult<()> {
+        let a = Tensor::<f32>::from_vec(vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0], &[2, 3])?;
+        let b = Tensor::<f32>::from_vec(vec![1.0, 2.0, 3.0, 4.0], &[2, 2])?;
+
+        for result in [ops!(a, Add, b), ops!(a, Sub, b), ops!(a, Mul, b), ops!(a, Div, b)] {
+            match result.err().unwrap() {
+                MlError::TensorError(TensorError::OpFailed { detail, .. }) => {
+                    assert!(matches!(*detail, TensorError::InvalidOperation { .. }))
+                }
+                other => panic!("unexpected error {:?}", other),
+            }
+        }
+        match ops!(a, Matmul, a).err().unwrap() {
+            MlError::TensorError(TensorError::OpFailed { detail, .. }) => {
+                assert!(matches!(*detail, TensorError::MatrixMultiplicationError { .. }))
+            }
+            other => panic!("unexpected error {:?}", other),
+        }
+
+        // validation happens when the op is built, before forward runs
+        assert!(Add::new(a.deref(), Some(b.deref())).is_err());
+        assert!(Mul::new(a.deref(), None).is_err());
+        let vector = Tensor::<f32>::from_vec(vec![1.0, 2.0], &[2])?;
+        let rank3 = Tensor::<f32>::from_vec(vec![1.0; 8], &[2, 2, 2])?;
+        assert!(Matmul::new(rank3.deref(), Some(vector.deref())).is_err());
+        assert!(Matmul::new(Tensor::<f32>::scalar(1.0).deref(), Some(vector.deref())).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_fluent_api_matches_macros() -> MlResult<()> {
+        let a = Tensor::<f32>::new(vec![vec![1.0, 2.0], vec![3.0, 4.0]])?;
+        let b = Tensor::<f32>::new(vec![vec![0.5, -1.0], vec![2.0, 0.25]])?;
+
+        assert_eq!(a.add(&b)?.data(), ops!(a, Add, b)?.data());
+        assert_eq!(a.sub(&b)?.data(), ops!(a, Sub, b)?.data());
+        assert_eq!(a.mul(&b)?.data(), ops!(a, Mul, b)?.data());
+        assert_eq!(a.div(&b)?.data(), ops!(a, Div, b)?.data());
+        assert_eq!(a.matmul(&b)?.data(), ops!(a, Matmul, b)?.data());
+        assert_eq!(a.exp()?.data(), ops!(a, Exp)?.data());
+        assert_eq!(b.abs()?.data(), ops!(b, Abs)?.data());
+        assert_eq!(a.pow(3.0)?.data(), ops!(a, Pow, 3.0)?.data());
+        assert_eq!(a.pow_tensor(&b)?.data(), ops!(a, Pow, b)?.data());
+
+        let (values, indices) = a.topk(1, true)?;
+        let (macro_values, macro_indices) = ops!(a, Topk, 1, true)?;
+        assert_eq!((values.data(), indices.data()), (macro_values.data(), macro_indices.data()));
+        let (values, _) = a.matmax(Some(-1), true)?;
+        assert_eq!(values.shape(), ops!(a, Matmax, Some(-1), true)?.0.shape());
+
+        // chained calls read left to right
+        let chained = a.add(&b)?.mul(&a)?.square()?.sqrt()?.neg()?;
+        let nested = ops!(ops!(ops!(ops!(ops!(a, Add, b)?, Mul, a)?, Square)?, Sqrt)?, Neg)?;
+        assert_eq!(chained.data(), nested.data());
+        assert!(a.add(&Tensor::<f32>::from_vec(vec![1.0; 3], &[3])?).is_err());
+        Ok(())
+    }
+
     #[test]
     fn test_matmul_1x1() -> MlResult<()> {
         // Case 5: 1x1 Matrix Multiplication
@@ -1131,7 +2546,7 @@ mod tests {
         let b = Tensor::<f32>::from_vec(vec![4.0, 5.0, 6.0], &[3])?;
         let c = ops!(a, Matmul, b)?;
 
-        assert_eq!(c.shape(), &[]); // scalar output
+        assert_eq!(c.shape(), &[] as &[usize]); // scalar output
         assert_eq!(c.data(), &[32.0]); // 1*4 + 2*5 + 3*6 = 32
         Ok(())
     }
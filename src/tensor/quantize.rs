@@ -0,0 +1,211 @@
+use crate::{MlError, MlResult};
+use crate::tensor::{normalize_dim, ArcTensor, Tensor, TensorBase, TensorError};
+
+impl dyn TensorBase<f32> + '_ {
+    /// Quantizes to `i8` with a single scale and zero point
+    ///
+    /// Each element maps to `round(x / scale) + zero_point`, clamped to `[-128, 127]`.
+    ///
+    /// # Arguments
+    /// * `scale` - The step between two quantized values, must be positive and finite
+    /// * `zero_point` - The quantized value representing 0.0
+    ///
+    /// # Returns
+    /// * `Ok(ArcTensor<i8>)` with the same shape as `self`
+    /// * `Err(MlError::TensorError)` with `InvalidOperation` if `scale` is not positive and finite
+    pub fn quantize(&self, scale: f32, zero_point: i32) -> MlResult<ArcTensor<i8>> {
+        check_scale(scale)?;
+        let data = self.data().iter().map(|&x| quantize_value(x, scale, zero_point)).collect();
+        Tensor::<i8>::from_vec(data, self.shape())
+    }
+
+    /// Quantizes to `i8` with a separate scale and zero point per channel
+    ///
+    /// # Arguments
+    /// * `scales` - A 1-D tensor with one scale per slice along `axis`
+    /// * `zero_points` - A 1-D tensor with one zero point per slice along `axis`
+    /// * `axis` - The channel dimension; negative values index from the end
+    ///
+    /// # Returns
+    /// * `Ok(ArcTensor<i8>)` with the same shape as `self`
+    /// * `Err(MlError::TensorError)` with `InvalidAxis` if `axis` is out of range, `InvalidShape`
+    ///   if `scales` or `zero_points` do not have one entry per channel, or `InvalidOperation`
+    ///   if a scale is not positive and finite
+    pub fn quantize_per_channel(&self, scales: &dyn TensorBase<f32>, zero_points: &dyn TensorBase<i32>, axis: i32) -> MlResult<ArcTensor<i8>> {
+        let channel = channel_index(self.shape(), scales, zero_points, axis)?;
+        for &scale in scales.data() {
+            check_scale(scale)?;
+        }
+        let data = self.data().iter().enumerate().map(|(i, &x)| {
+            let c = channel(i);
+            quantize_value(x, scales.data()[c], zero_points.data()[c])
+        }).collect();
+        Tensor::<i8>::from_vec(data, self.shape())
+    }
+
+    /// Linearly rescales all elements to `[0, 1]` using the global minimum and maximum
+    ///
+    /// Handy for viewing a feature map as an image together with [`to_u8`](Self::to_u8).
+    /// A constant tensor (minimum equal to maximum) becomes all zeros. NaNs are ignored
+    /// when finding the range and stay NaN.
+    pub fn normalize_to_unit(&self) -> ArcTensor<f32> {
+        let (min, max) = self.data().iter().fold((f32::INFINITY, f32::NEG_INFINITY), |(min, max), &x| (min.min(x), max.max(x)));
+        let range = max - min;
+        let data = self.data().iter()
+            .map(|&x| if range > 0.0 { (x - min) / range } else if x.is_nan() { x } else { 0.0 })
+            .collect();
+        Tensor::<f32>::from_vec(data, self.shape()).expect("data always matches the shape")
+    }
+
+    /// Maps values in `[0, 1]` to bytes in `0..=255`
+    ///
+    /// Values are clamped to `[0, 1]` before scaling and rounded to the nearest byte; NaN maps to 0.
+    pub fn to_u8(&self) -> ArcTensor<u8> {
+        let data = self.data().iter().map(|&x| (x.clamp(0.0, 1.0) * 255.0).round() as u8).collect();
+        Tensor::<u8>::from_vec(data, self.shape()).expect("data always matches the shape")
+    }
+}
+
+impl dyn TensorBase<i8> + '_ {
+    /// Maps quantized values back to `f32` as `(q - zero_point) * scale`
+    ///
+    /// # Arguments
+    /// * `scale` - The scale used for quantization
+    /// * `zero_point` - The zero point used for quantization
+    ///
+    /// # Returns
+    /// A new tensor with the same shape as `self`
+    pub fn dequantize(&self, scale: f32, zero_point: i32) -> ArcTensor<f32> {
+        let data = self.data().iter().map(|&q| (q as i32 - zero_point) as f32 * scale).collect();
+        Tensor::<f32>::from_vec(data, self.shape()).expect("data always matches the shape")
+    }
+
+    /// Maps quantized values back to `f32` with a separate scale and zero point per channel
+    ///
+    /// # Arguments
+    /// * `scales` - A 1-D tensor with one scale per slice along `axis`
+    /// * `zero_points` - A 1-D tensor with one zero point per slice along `axis`
+    /// * `axis` - The channel dimension; negative values index from the end
+    ///
+    /// # Returns
+    /// * `Ok(ArcTensor)` with the same shape as `self`
+    /// * `Err(MlError::TensorError)` with `InvalidAxis` if `axis` is out of range, or
+    ///   `InvalidShape` if `scales` or `zero_points` do not have one entry per channel
+    pub fn dequantize_per_channel(&self, scales: &dyn TensorBase<f32>, zero_points: &dyn TensorBase<i32>, axis: i32) -> MlResult<ArcTensor<f32>> {
+        let channel = channel_index(self.shape(), scales, zero_points, axis)?;
+        let data = self.data().iter().enumerate().map(|(i, &q)| {
+            let c = channel(i);
+            (q as i32 - zero_points.data()[c]) as f32 * scales.data()[c]
+        }).collect();
+        Tensor::<f32>::from_vec(data, self.shape())
+    }
+}
+
+fn quantize_value(x: f32, scale: f32, zero_point: i32) -> i8 {
+    ((x / scale).round() + zero_point as f32).clamp(i8::MIN as f32, i8::MAX as f32) as i8
+}
+
+fn check_scale(scale: f32) -> MlResult<()> {
+    if !(scale > 0.0 && scale.is_finite()) {
+        return Err(MlError::TensorError(TensorError::InvalidOperation {
+            op: "quantize",
+            reason: format!("scale must be positive and finite, got {}", scale),
+        }));
+    }
+    Ok(())
+}
+
+/// Validates the per-channel parameters and returns a map from flat index to channel
+fn channel_index(shape: &[usize], scales: &dyn TensorBase<f32>, zero_points: &dyn TensorBase<i32>, axis: i32) -> MlResult<impl Fn(usize) -> usize> {
+    let axis = normalize_dim(axis, shape.len())?;
+    let channels = shape[axis];
+    for params in [scales.shape(), zero_points.shape()] {
+        if params != [channels] {
+            return Err(MlError::TensorError(TensorError::InvalidShape {
+                expected: vec![channels],
+                got: params.to_vec(),
+            }));
+        }
+    }
+    let stride: usize = shape[axis + 1..].iter().product();
+    Ok(move |i: usize| (i / stride) % channels)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::MlResult;
+    use crate::tensor::*;
+
+    #[test]
+    fn test_quantize_round_trip() -> MlResult<()> {
+        let values: Vec<f32> = (0..41).map(|i| -1.0 + i as f32 * 0.05).collect();
+        let tensor = Tensor::<f32>::from_vec(values.clone(), &[41])?;
+
+        for scale in [0.01, 0.02, 0.1] {
+            let quantized = tensor.quantize(scale, 0)?;
+            let restored = quantized.dequantize(scale, 0);
+            assert_eq!(restored.shape(), &[41]);
+            for (&x, &y) in values.iter().zip(restored.data()) {
+                // within range the error is at most half a step
+                if x.abs() <= 127.0 * scale {
+                    assert!((x - y).abs() <= scale / 2.0 + 1e-6, "scale {}: {} -> {}", scale, x, y);
+                }
+            }
+        }
+
+        // values outside the range saturate
+        let quantized = tensor.quantize(0.001, 10)?;
+        assert_eq!(quantized.data()[0], i8::MIN);
+        assert_eq!(quantized.data()[40], i8::MAX);
+        assert_eq!(tensor.quantize(0.01, 5)?.data()[20], 5);
+
+        assert!(tensor.quantize(0.0, 0).is_err());
+        assert!(tensor.quantize(f32::NAN, 0).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_normalize_to_unit_and_to_u8() -> MlResult<()> {
+        let feature_map = Tensor::<f32>::from_vec(vec![-2.0, 0.0, 2.0, 6.0], &[2, 2])?;
+        let unit = feature_map.normalize_to_unit();
+        assert_eq!(unit.shape(), &[2, 2]);
+        assert_eq!(unit.data(), &[0.0, 0.25, 0.5, 1.0]);
+        assert_eq!(unit.to_u8().data(), &[0, 64, 128, 255]);
+
+        let constant = Tensor::<f32>::from_vec(vec![3.0; 3], &[3])?;
+        assert_eq!(constant.normalize_to_unit().data(), &[0.0; 3]);
+
+        let out_of_range = Tensor::<f32>::from_vec(vec![-0.5, 1.5, f32::NAN], &[3])?;
+        assert_eq!(out_of_range.to_u8().data(), &[0, 255, 0]);
+        let with_nan = out_of_range.normalize_to_unit();
+        assert_eq!(&with_nan.data()[..2], &[0.0, 1.0]);
+        assert!(with_nan.data()[2].is_nan());
+        Ok(())
+    }
+
+    #[test]
+    fn test_quantize_per_channel() -> MlResult<()> {
+        // two output channels with very different ranges
+        let weight = Tensor::<f32>::new(vec![vec![0.1, -0.2, 0.3], vec![10.0, -20.0, 30.0]])?;
+        let scales = Tensor::<f32>::from_vec(vec![0.3 / 127.0, 30.0 / 127.0], &[2])?;
+        let zero_points = Tensor::<i32>::from_vec(vec![0, 0], &[2])?;
+
+        let quantized = weight.quantize_per_channel(&*scales, &*zero_points, 0)?;
+        assert_eq!(quantized.data()[2], 127);
+        assert_eq!(quantized.data()[5], 127);
+
+        let restored = quantized.dequantize_per_channel(&*scales, &*zero_points, 0)?;
+        for (i, (&x, &y)) in weight.data().iter().zip(restored.data()).enumerate() {
+            assert!((x - y).abs() <= scales.data()[i / 3] / 2.0 + 1e-6);
+        }
+
+        let columns = Tensor::<f32>::from_vec(vec![1.0, 1.0, 1.0], &[3])?;
+        let column_zero_points = Tensor::<i32>::from_vec(vec![0, 1, -1], &[3])?;
+        let per_column = weight.quantize_per_channel(&*columns, &*column_zero_points, -1)?;
+        assert_eq!(per_column.data(), &[0, 1, -1, 10, -19, 29]);
+
+        assert!(weight.quantize_per_channel(&*columns, &*zero_points, 0).is_err());
+        assert!(weight.quantize_per_channel(&*scales, &*zero_points, 2).is_err());
+        Ok(())
+    }
+}
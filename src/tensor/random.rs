@@ -0,0 +1,169 @@
+use crate::{MlError, MlResult};
+use crate::tensor::{ArcTensor, Tensor, TensorBase, TensorError};
+
+/// A small seedable pseudo-random number generator (SplitMix64).
+///
+/// The same seed always produces the same sequence, which keeps weight
+/// initialization and sampling reproducible across runs and platforms.
+#[derive(Debug, Clone)]
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    /// Creates a generator from a seed
+    pub fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    /// Returns the next 64 random bits
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// Returns a uniformly distributed value in `[0, 1)`
+    pub fn next_f32(&mut self) -> f32 {
+        (self.next_u64() >> 40) as f32 / (1u64 << 24) as f32
+    }
+
+    /// Returns a uniformly distributed value in `[low, high)`
+    pub fn uniform(&mut self, low: f32, high: f32) -> f32 {
+        low + (high - low) * self.next_f32()
+    }
+
+    /// Returns a normally distributed value using the Box-Muller transform
+    pub fn normal(&mut self, mean: f32, std: f32) -> f32 {
+        let u1 = 1.0 - self.next_f32();
+        let u2 = self.next_f32();
+        let z = (-2.0 * u1.ln()).sqrt() * (2.0 * std::f32::consts::PI * u2).cos();
+        mean + std * z
+    }
+}
+
+impl dyn TensorBase<f32> + '_ {
+    /// Draws category indices from each row of a probability tensor, with replacement
+    ///
+    /// Rows are normalized internally, so unnormalized weights work as well.
+    ///
+    /// # Arguments
+    /// * `num_samples` - The number of indices to draw per row
+    /// * `seed` - The seed of the [`Rng`] used for sampling
+    ///
+    /// # Returns
+    /// * `Ok(ArcTensor<usize>)` with shape `[batch, num_samples]`, or `[num_samples]` for a 1-D input
+    /// * `Err(MlError::TensorError)` with `InvalidShape` if the tensor is not 1-D or 2-D, or
+    ///   `InvalidOperation` if a row has a negative or non-finite weight or sums to zero
+    pub fn multinomial(&self, num_samples: usize, seed: u64) -> MlResult<ArcTensor<usize>> {
+        let (batch, n) = match *self.shape() {
+            [n] => (1, n),
+            [batch, n] => (batch, n),
+            _ => {
+                return Err(MlError::TensorError(TensorError::InvalidShape {
+                    expected: vec![self.data().len()],
+                    got: self.shape().to_vec(),
+                }));
+            }
+        };
+
+        let mut rng = Rng::new(seed);
+        let mut samples = Vec::with_capacity(batch * num_samples);
+        for (b, row) in self.data().chunks(n.max(1)).take(batch).enumerate() {
+            if row.iter().any(|&p| !(p >= 0.0 && p.is_finite())) {
+                return Err(MlError::TensorError(TensorError::InvalidOperation {
+                    op: "multinomial",
+                    reason: format!("row {} has a negative or non-finite probability", b),
+                }));
+            }
+            let total: f64 = row.iter().map(|&p| p as f64).sum();
+            if total <= 0.0 {
+                return Err(MlError::TensorError(TensorError::InvalidOperation {
+                    op: "multinomial",
+                    reason: format!("row {} does not sum to a positive value", b),
+                }));
+            }
+
+            let last = row.iter().rposition(|&p| p > 0.0).unwrap_or(0);
+            for _ in 0..num_samples {
+                let target = rng.next_f32() as f64 * total;
+                let mut cumulative = 0.0;
+                let index = row.iter().position(|&p| {
+                    cumulative += p as f64;
+                    cumulative > target
+                });
+                samples.push(index.unwrap_or(last));
+            }
+        }
+
+        let shape = match self.shape().len() {
+            1 => vec![num_samples],
+            _ => vec![batch, num_samples],
+        };
+        Tensor::<usize>::from_vec(samples, &shape)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::MlResult;
+    use crate::tensor::*;
+
+    #[test]
+    fn test_rng_deterministic() {
+        let mut a = Rng::new(42);
+        let mut b = Rng::new(42);
+        let mut c = Rng::new(43);
+
+        let xs: Vec<u64> = (0..8).map(|_| a.next_u64()).collect();
+        assert_eq!(xs, (0..8).map(|_| b.next_u64()).collect::<Vec<_>>());
+        assert_ne!(xs, (0..8).map(|_| c.next_u64()).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_rng_ranges() {
+        let mut rng = Rng::new(0);
+        for _ in 0..1000 {
+            let x = rng.next_f32();
+            assert!((0.0..1.0).contains(&x));
+            let y = rng.uniform(-2.0, 3.0);
+            assert!((-2.0..3.0).contains(&y));
+            assert!(rng.normal(0.0, 1.0).is_finite());
+        }
+    }
+
+    #[test]
+    fn test_multinomial() -> MlResult<()> {
+        let probs = Tensor::<f32>::new(vec![vec![0.0, 1.0, 0.0], vec![2.0, 0.0, 6.0]])?;
+        let samples = probs.multinomial(1000, 7)?;
+        assert_eq!(samples.shape(), &[2, 1000]);
+        assert!(samples.data()[..1000].iter().all(|&i| i == 1));
+
+        // unnormalized weights: index 2 is three times as likely as index 0
+        let second = &samples.data()[1000..];
+        assert!(second.iter().all(|&i| i == 0 || i == 2));
+        let twos = second.iter().filter(|&&i| i == 2).count();
+        assert!((650..850).contains(&twos), "{}", twos);
+
+        assert_eq!(probs.multinomial(16, 3)?.data(), probs.multinomial(16, 3)?.data());
+        let row = Tensor::<f32>::from_vec(vec![0.5, 0.5], &[2])?;
+        assert_eq!(row.multinomial(4, 0)?.shape(), &[4]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_multinomial_invalid() -> MlResult<()> {
+        let negative = Tensor::<f32>::new(vec![vec![0.5, -0.1]])?;
+        let zeros = Tensor::<f32>::new(vec![vec![0.0, 0.0]])?;
+        let nan = Tensor::<f32>::new(vec![vec![f32::NAN, 1.0]])?;
+        let cube = Tensor::<f32>::from_vec(vec![1.0; 8], &[2, 2, 2])?;
+
+        assert!(negative.multinomial(1, 0).is_err());
+        assert!(zeros.multinomial(1, 0).is_err());
+        assert!(nan.multinomial(1, 0).is_err());
+        assert!(cube.multinomial(1, 0).is_err());
+        Ok(())
+    }
+}
@@ -0,0 +1,402 @@
+use crate::{MlError, MlResult};
+use crate::tensor::{normalize_dim, ArcTensor, Tensor, TensorBase, TensorError};
+use crate::tensor::broadcast::{broadcast_index, broadcast_shape};
+
+impl dyn TensorBase<f32> + '_ {
+    /// Computes the `q`-th quantile of the tensor
+    ///
+    /// Values between two order statistics are linearly interpolated, so `q = 0.5`
+    /// on an even number of elements yields the average of the two middle values.
+    ///
+    /// # Arguments
+    /// * `q` - The quantile to compute, in `[0, 1]`
+    /// * `dim` - Optional dimension to reduce; negative values index from the end
+    ///
+    /// # Returns
+    /// If dim is None, returns a 0-dimensional tensor containing the quantile of all elements.
+    /// If dim is specified, returns a tensor with that dimension removed.
+    pub fn quantile(&self, q: f32, dim: Option<i32>) -> MlResult<ArcTensor<f32>> {
+        if !(0.0..=1.0).contains(&q) {
+            return Err(MlError::TensorError(TensorError::InvalidOperation {
+                op: "quantile",
+                reason: format!("q must be in [0, 1], got {}", q),
+            }));
+        }
+        if self.data().is_empty() {
+            return Err(MlError::TensorError(TensorError::EmptyTensor));
+        }
+
+        let dim = match dim {
+            None => {
+                let mut values = self.data().to_vec();
+                return Ok(Tensor::<f32>::scalar(interpolate_sorted(&mut values, q)));
+            }
+            Some(d) => normalize_dim(d, self.ndim())?,
+        };
+
+        let stride: usize = self.shape()[dim + 1..].iter().product();
+        let outer_dims: usize = self.shape()[..dim].iter().product();
+        let dim_size = self.shape()[dim];
+
+        let mut result = Vec::with_capacity(outer_dims * stride);
+        let mut values = Vec::with_capacity(dim_size);
+        for i in 0..outer_dims {
+            for j in 0..stride {
+                values.clear();
+                values.extend((0..dim_size).map(|k| self.data()[(i * dim_size + k) * stride + j]));
+                result.push(interpolate_sorted(&mut values, q));
+            }
+        }
+
+        let mut shape = self.shape().to_vec();
+        shape.remove(dim);
+        Tensor::<f32>::from_vec(result, &shape)
+    }
+
+    /// Computes the cumulative log-sum-exp along a dimension
+    ///
+    /// Element `i` of the output is `log(sum(exp(x[..=i])))`. A running maximum is
+    /// carried alongside the sum of exponentials shifted by it, so large inputs do
+    /// not overflow.
+    ///
+    /// # Arguments
+    /// * `dim` - The dimension to scan; negative values index from the end
+    ///
+    /// # Returns
+    /// * `Ok(ArcTensor)` with the same shape as `self`
+    /// * `Err(MlError::TensorError)` with `InvalidAxis` if `dim` is out of range
+    pub fn logcumsumexp(&self, dim: i32) -> MlResult<ArcTensor<f32>> {
+        let dim = normalize_dim(dim, self.ndim())?;
+        let stride: usize = self.shape()[dim + 1..].iter().product();
+        let outer_dims: usize = self.shape()[..dim].iter().product();
+        let dim_size = self.shape()[dim];
+
+        let mut result = vec![0.0; self.data().len()];
+        for i in 0..outer_dims {
+            for j in 0..stride {
+                let (mut max, mut sum) = (f32::NEG_INFINITY, 0.0f32);
+                for k in 0..dim_size {
+                    let idx = (i * dim_size + k) * stride + j;
+                    let x = self.data()[idx];
+                    if x > max {
+                        sum = sum * (max - x).exp() + 1.0;
+                        max = x;
+                    } else if x != f32::NEG_INFINITY {
+                        sum += (x - max).exp();
+                    }
+                    result[idx] = max + sum.ln();
+                }
+            }
+        }
+        Tensor::<f32>::from_vec(result, self.shape())
+    }
+
+    /// Counts the nonzero elements seen so far along a dimension
+    ///
+    /// Element `i` of the output is the number of nonzero values in `x[..=i]`, which
+    /// gives the gather offsets of the valid positions in a packed sequence. NaN counts
+    /// as nonzero.
+    ///
+    /// # Arguments
+    /// * `dim` - The dimension to scan; negative values index from the end
+    ///
+    /// # Returns
+    /// * `Ok(ArcTensor)` with the same shape as `self`
+    /// * `Err(MlError::TensorError)` with `InvalidAxis` if `dim` is out of range
+    pub fn cumulative_count_nonzero(&self, dim: i32) -> MlResult<ArcTensor<usize>> {
+        let dim = normalize_dim(dim, self.ndim())?;
+        let stride: usize = self.shape()[dim + 1..].iter().product();
+        let outer_dims: usize = self.shape()[..dim].iter().product();
+        let dim_size = self.shape()[dim];
+
+        let mut result = vec![0; self.data().len()];
+        for i in 0..outer_dims {
+            for j in 0..stride {
+                let mut count = 0;
+                for k in 0..dim_size {
+                    let idx = (i * dim_size + k) * stride + j;
+                    if self.data()[idx] != 0.0 {
+                        count += 1;
+                    }
+                    result[idx] = count;
+                }
+            }
+        }
+        Tensor::<usize>::from_vec(result, self.shape())
+    }
+
+    /// Computes the mean over the positions where `mask` is nonzero
+    ///
+    /// Each slice is divided by its own number of valid positions, which averages
+    /// variable-length sequences without counting the padding. A slice with no valid
+    /// positions has a mean of 0 rather than NaN.
+    ///
+    /// # Arguments
+    /// * `mask` - A tensor broadcastable to the shape of `self`; nonzero marks a valid position
+    /// * `dim` - Optional dimension to reduce; negative values index from the end
+    ///
+    /// # Returns
+    /// * `Ok(ArcTensor)` with a 0-dimensional tensor if dim is None, or with dim removed
+    /// * `Err(MlError::TensorError)` with `InvalidShape` if the mask does not broadcast to
+    ///   `self`, or `InvalidAxis` if dim is out of range
+    pub fn masked_mean(&self, mask: &dyn TensorBase<f32>, dim: Option<i32>) -> MlResult<ArcTensor<f32>> {
+        if broadcast_shape(self.shape(), mask.shape())? != self.shape() {
+            return Err(MlError::TensorError(TensorError::InvalidShape {
+                expected: self.shape().to_vec(),
+                got: mask.shape().to_vec(),
+            }));
+        }
+        let valid = |i: usize| mask.data()[broadcast_index(i, self.shape(), mask.shape())] != 0.0;
+        let mean = |sum: f32, count: usize| if count == 0 { 0.0 } else { sum / count as f32 };
+
+        let dim = match dim {
+            None => {
+                let (sum, count) = (0..self.numel()).filter(|&i| valid(i))
+                    .fold((0.0, 0), |(sum, count), i| (sum + self.data()[i], count + 1));
+                return Ok(Tensor::<f32>::scalar(mean(sum, count)));
+            }
+            Some(d) => normalize_dim(d, self.ndim())?,
+        };
+
+        let stride: usize = self.shape()[dim + 1..].iter().product();
+        let outer_dims: usize = self.shape()[..dim].iter().product();
+        let dim_size = self.shape()[dim];
+
+        let mut result = Vec::with_capacity(outer_dims * stride);
+        for i in 0..outer_dims {
+            for j in 0..stride {
+                let (sum, count) = (0..dim_size).map(|k| (i * dim_size + k) * stride + j).filter(|&idx| valid(idx))
+                    .fold((0.0, 0), |(sum, count), idx| (sum + self.data()[idx], count + 1));
+                result.push(mean(sum, count));
+            }
+        }
+
+        let mut shape = self.shape().to_vec();
+        shape.remove(dim);
+        Tensor::<f32>::from_vec(result, &shape)
+    }
+
+    /// Averages a `[N, C, H, W]` tensor over its spatial dimensions
+    ///
+    /// This is the usual last pooling step of a CNN before the classifier.
+    ///
+    /// # Arguments
+    /// * `keepdim` - Whether to return `[N, C, 1, 1]` rather than `[N, C]`
+    ///
+    /// # Returns
+    /// * `Ok(ArcTensor)` holding the mean of each `H x W` plane
+    /// * `Err(MlError::TensorError)` with `InvalidOperation` if the tensor is not 4-D, or
+    ///   `EmptyTensor` if `H * W` is 0
+    pub fn global_avg_pool2d(&self, keepdim: bool) -> MlResult<ArcTensor<f32>> {
+        let &[n, c, h, w] = self.shape() else {
+            return Err(MlError::TensorError(TensorError::InvalidOperation {
+                op: "global_avg_pool2d",
+                reason: format!("expected a [N, C, H, W] tensor, got shape {:?}", self.shape()),
+            }));
+        };
+        if h * w == 0 {
+            return Err(MlError::TensorError(TensorError::EmptyTensor));
+        }
+
+        let result = self.data().chunks(h * w)
+            .map(|plane| plane.iter().sum::<f32>() / (h * w) as f32)
+            .collect();
+        if keepdim {
+            Tensor::<f32>::from_vec(result, &[n, c, 1, 1])
+        } else {
+            Tensor::<f32>::from_vec(result, &[n, c])
+        }
+    }
+
+    /// Sums the tensor down to `shape`, undoing a broadcast
+    ///
+    /// Every element is added into the position it was broadcast from, which is how
+    /// the gradient of a broadcast operand is reduced back to the operand's shape.
+    ///
+    /// # Arguments
+    /// * `shape` - The shape to reduce to; it must broadcast to the shape of `self`
+    ///
+    /// # Returns
+    /// * `Ok(ArcTensor)` with the given shape
+    /// * `Err(MlError::TensorError)` with `InvalidShape` if `shape` does not broadcast to `self`
+    pub fn sum_to(&self, shape: &[usize]) -> MlResult<ArcTensor<f32>> {
+        if broadcast_shape(self.shape(), shape)? != self.shape() {
+            return Err(MlError::TensorError(TensorError::InvalidShape {
+                expected: self.shape().to_vec(),
+                got: shape.to_vec(),
+            }));
+        }
+
+        let mut result = vec![0.0; shape.iter().product()];
+        for (i, &x) in self.data().iter().enumerate() {
+            result[broadcast_index(i, self.shape(), shape)] += x;
+        }
+        Tensor::<f32>::from_vec(result, shape)
+    }
+}
+
+fn interpolate_sorted(values: &mut [f32], q: f32) -> f32 {
+    values.sort_by(|a, b| a.total_cmp(b));
+    let pos = q * (values.len() - 1) as f32;
+    let (lo, hi) = (pos.floor() as usize, pos.ceil() as usize);
+    values[lo] + (values[hi] - values[lo]) * (pos - lo as f32)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::MlResult;
+    use crate::tensor::*;
+
+    #[test]
+    fn test_quantile_global() -> MlResult<()> {
+        let tensor = Tensor::<f32>::from_vec(vec![4.0, 1.0, 3.0, 2.0], &[2, 2])?;
+
+        assert_eq!(tensor.quantile(0.0, None)?.data(), &[1.0]);
+        assert_eq!(tensor.quantile(1.0, None)?.data(), &[4.0]);
+        assert_eq!(tensor.quantile(0.5, None)?.data(), &[2.5]);
+        assert_eq!(tensor.quantile(0.25, None)?.data(), &[1.75]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_quantile_dim() -> MlResult<()> {
+        let tensor = Tensor::<f32>::new(vec![vec![1.0, 5.0, 3.0], vec![6.0, 2.0, 4.0]])?;
+
+        let median = tensor.quantile(0.5, Some(1))?;
+        assert_eq!(median.shape(), &[2]);
+        assert_eq!(median.data(), &[3.0, 4.0]);
+
+        let upper = tensor.quantile(1.0, Some(0))?;
+        assert_eq!(upper.shape(), &[3]);
+        assert_eq!(upper.data(), &[6.0, 5.0, 4.0]);
+
+        assert_eq!(tensor.quantile(0.5, Some(-1))?.data(), median.data());
+        Ok(())
+    }
+
+    #[test]
+    fn test_quantile_invalid() -> MlResult<()> {
+        let tensor = Tensor::<f32>::new(vec![vec![1.0, 2.0]])?;
+
+        assert!(tensor.quantile(1.5, None).is_err());
+        assert!(tensor.quantile(-0.1, Some(0)).is_err());
+        assert!(tensor.quantile(0.5, Some(2)).is_err());
+        Ok(())
+    }
+
+    fn stable_logsumexp(values: &[f32]) -> f32 {
+        let max = values.iter().fold(f32::NEG_INFINITY, |a, &b| a.max(b));
+        max + values.iter().map(|x| (x - max).exp()).sum::<f32>().ln()
+    }
+
+    #[test]
+    fn test_logcumsumexp() -> MlResult<()> {
+        let values = vec![0.5, -1.0, 2.0, 1000.0, 3.0, -2.0];
+        let tensor = Tensor::<f32>::from_vec(values.clone(), &[2, 3])?;
+
+        let rows = tensor.logcumsumexp(1)?;
+        assert_eq!(rows.shape(), &[2, 3]);
+        for (r, row) in values.chunks(3).enumerate() {
+            for k in 0..3 {
+                let expected = stable_logsumexp(&row[..=k]);
+                assert!((rows.data()[r * 3 + k] - expected).abs() < 1e-4);
+            }
+        }
+        // exp(1000) overflows, the running max keeps it finite
+        assert_eq!(rows.data()[3], 1000.0);
+        assert!(rows.data().iter().all(|v| v.is_finite()));
+
+        let columns = tensor.logcumsumexp(-2)?;
+        assert_eq!(&columns.data()[..3], &values[..3]);
+        assert!((columns.data()[4] - stable_logsumexp(&[-1.0, 3.0])).abs() < 1e-5);
+        Ok(())
+    }
+
+    #[test]
+    fn test_cumulative_count_nonzero() -> MlResult<()> {
+        // two padded sequences, zeros mark padding
+        let mask = Tensor::<f32>::from_vec(vec![1.0, 0.0, 3.0, -2.0, 0.0, 0.0, f32::NAN, 5.0], &[2, 4])?;
+
+        let rows = mask.cumulative_count_nonzero(1)?;
+        assert_eq!(rows.shape(), &[2, 4]);
+        assert_eq!(rows.data(), &[1, 1, 2, 3, 0, 0, 1, 2]);
+
+        let columns = mask.cumulative_count_nonzero(-2)?;
+        assert_eq!(columns.data(), &[1, 0, 1, 1, 1, 0, 2, 2]);
+
+        assert!(mask.cumulative_count_nonzero(2).is_err());
+        assert!(mask.cumulative_count_nonzero(-3).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_sum_to() -> MlResult<()> {
+        let tensor = Tensor::<f32>::from_vec(vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0], &[2, 3])?;
+
+        assert_eq!(tensor.sum_to(&[3])?.data(), &[5.0, 7.0, 9.0]);
+        let columns = tensor.sum_to(&[2, 1])?;
+        assert_eq!(columns.shape(), &[2, 1]);
+        assert_eq!(columns.data(), &[6.0, 15.0]);
+        assert_eq!(tensor.sum_to(&[])?.data(), &[21.0]);
+        assert_eq!(tensor.sum_to(&[2, 3])?.data(), tensor.data());
+
+        assert!(tensor.sum_to(&[2]).is_err());
+        assert!(tensor.sum_to(&[4, 2, 3]).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_masked_mean() -> MlResult<()> {
+        // two sequences of lengths 3 and 1, padded to 4
+        let values = Tensor::<f32>::from_vec(vec![1.0, 2.0, 6.0, 9.0, 4.0, 9.0, 9.0, 9.0], &[2, 4])?;
+        let mask = Tensor::<f32>::from_vec(vec![1.0, 1.0, 1.0, 0.0, 1.0, 0.0, 0.0, 0.0], &[2, 4])?;
+
+        let per_sequence = values.masked_mean(&*mask, Some(-1))?;
+        assert_eq!(per_sequence.shape(), &[2]);
+        assert_eq!(per_sequence.data(), &[3.0, 4.0]);
+        assert_eq!(values.masked_mean(&*mask, None)?.data(), &[3.25]);
+
+        // a position valid in no sequence averages to 0
+        let per_position = values.masked_mean(&*mask, Some(0))?;
+        assert_eq!(per_position.data(), &[2.5, 2.0, 6.0, 0.0]);
+
+        // the mask broadcasts across rows
+        let columns = Tensor::<f32>::from_vec(vec![0.0, 1.0, 0.0, 1.0], &[4])?;
+        assert_eq!(values.masked_mean(&*columns, Some(1))?.data(), &[5.5, 9.0]);
+
+        let wrong = Tensor::<f32>::from_vec(vec![1.0; 3], &[3])?;
+        assert!(values.masked_mean(&*wrong, None).is_err());
+        let too_big = Tensor::<f32>::from_vec(vec![1.0; 16], &[2, 2, 4])?;
+        assert!(values.masked_mean(&*too_big, None).is_err());
+        assert!(values.masked_mean(&*mask, Some(2)).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_global_avg_pool2d() -> MlResult<()> {
+        let tensor = Tensor::<f32>::from_vec((0..24).map(|x| x as f32).collect(), &[2, 3, 2, 2])?;
+
+        let pooled = tensor.global_avg_pool2d(false)?;
+        assert_eq!(pooled.shape(), &[2, 3]);
+        assert_eq!(pooled.data(), &[1.5, 5.5, 9.5, 13.5, 17.5, 21.5]);
+
+        let kept = tensor.global_avg_pool2d(true)?;
+        assert_eq!(kept.shape(), &[2, 3, 1, 1]);
+        assert_eq!(kept.data(), pooled.data());
+
+        assert!(tensor.view(&[6, 2, 2])?.global_avg_pool2d(false).is_err());
+        assert!(Tensor::<f32>::from_vec(vec![], &[1, 2, 0, 3])?.global_avg_pool2d(false).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_logcumsumexp_edge_cases() -> MlResult<()> {
+        let tensor = Tensor::<f32>::from_vec(vec![f32::NEG_INFINITY, 0.0, f32::NEG_INFINITY], &[3])?;
+        let result = tensor.logcumsumexp(0)?;
+        assert_eq!(result.data(), &[f32::NEG_INFINITY, 0.0, 0.0]);
+
+        assert!(tensor.logcumsumexp(1).is_err());
+        assert!(tensor.logcumsumexp(-2).is_err());
+        Ok(())
+    }
+}
@@ -0,0 +1,516 @@
+use std::collections::HashMap;
+
+use crate::{MlError, MlResult};
+use crate::tensor::{ArcTensor, FormatError, Tensor, TensorBase};
+
+const HEADER_ALIGN: usize = 8;
+const MAX_HEADER_SIZE: u64 = 100_000_000;
+/// Deepest nesting of arrays and objects accepted in a header; real headers use 3 levels
+const MAX_JSON_DEPTH: usize = 64;
+
+/// Saves named tensors in the safetensors format
+///
+/// The file holds the length of the JSON header as a little-endian `u64`, the header
+/// itself (padded with spaces to a multiple of 8 bytes) and the raw `F32` data of every
+/// tensor, in the order given.
+///
+/// # Arguments
+/// * `path` - The file to write
+/// * `tensors` - The `(name, tensor)` pairs to save
+///
+/// # Returns
+/// * `Ok(())` on success
+/// * `Err(MlError::StringError)` if a name is used twice or the file cannot be written
+pub fn save_safetensors(path: &str, tensors: &[(&str, &dyn TensorBase<f32>)]) -> MlResult<()> {
+    let mut entries = Vec::with_capacity(tensors.len());
+    let mut offset = 0;
+    for (i, (name, tensor)) in tensors.iter().enumerate() {
+        if tensors[..i].iter().any(|(other, _)| other == name) {
+            return Err(safetensors_error(format!("duplicate tensor name '{}'", name)));
+        }
+        let shape = tensor.shape().iter().map(|d| d.to_string()).collect::<Vec<_>>().join(",");
+        let end = offset + tensor.data().len() * 4;
+        entries.push(format!(
+            "{}:{{\"dtype\":\"F32\",\"shape\":[{}],\"data_offsets\":[{},{}]}}",
+            json_string(name), shape, offset, end
+        ));
+        offset = end;
+    }
+
+    let mut header = format!("{{{}}}", entries.join(","));
+    let padding = (HEADER_ALIGN - header.len() % HEADER_ALIGN) % HEADER_ALIGN;
+    header.push_str(&" ".repeat(padding));
+
+    let mut bytes = Vec::with_capacity(8 + header.len() + offset);
+    bytes.extend_from_slice(&(header.len() as u64).to_le_bytes());
+    bytes.extend_from_slice(header.as_bytes());
+    for (_, tensor) in tensors {
+        for value in tensor.data() {
+            bytes.extend_from_slice(&value.to_le_bytes());
+        }
+    }
+    std::fs::write(path, bytes).map_err(|e| MlError::StringError(format!("I/O error: {}", e)))
+}
+
+/// Loads all tensors from a safetensors file
+///
+/// `F32`, `F64`, `F16` and `BF16` tensors are supported; other float widths are
+/// converted to `f32`. The `__metadata__` entry is ignored.
+///
+/// # Arguments
+/// * `path` - The file to read
+///
+/// # Returns
+/// * `Ok(HashMap)` from tensor name to tensor
+/// * `Err(MlError::StringError)` if the header is malformed, a dtype is unsupported, or the
+///   data offsets are out of bounds, overlap or do not match the shape
+/// * `Err(MlError::FormatError)` with `SizeOverflow` if the byte size of a shape overflows
+pub fn load_safetensors(path: &str) -> MlResult<HashMap<String, ArcTensor<f32>>> {
+    let bytes = std::fs::read(path).map_err(|e| MlError::StringError(format!("I/O error: {}", e)))?;
+    let header_len = bytes.get(..8)
+        .map(|b| u64::from_le_bytes(b.try_into().unwrap()))
+        .ok_or_else(|| safetensors_error("file is too short".to_string()))?;
+    if header_len > MAX_HEADER_SIZE || header_len > (bytes.len() - 8) as u64 {
+        return Err(safetensors_error(format!("header length {} exceeds the file size", header_len)));
+    }
+    let buffer_start = 8 + header_len as usize;
+    let header = std::str::from_utf8(&bytes[8..buffer_start])
+        .map_err(|_| safetensors_error("header is not valid UTF-8".to_string()))?;
+    let buffer = &bytes[buffer_start..];
+
+    let entries = match JsonParser::new(header).parse()? {
+        Json::Object(entries) => entries,
+        _ => return Err(safetensors_error("header is not a JSON object".to_string())),
+    };
+
+    let mut infos = Vec::new();
+    for (name, value) in entries.iter().filter(|(name, _)| name != "__metadata__") {
+        infos.push(TensorInfo::from_json(name, value)?);
+    }
+
+    // tensors must not share bytes, so walk them in buffer order
+    infos.sort_by_key(|info| info.begin);
+    let mut end_of_previous = 0;
+    for info in &infos {
+        if info.begin < end_of_previous {
+            return Err(safetensors_error(format!("data of '{}' overlaps the previous tensor", info.name)));
+        }
+        if info.end > buffer.len() {
+            return Err(safetensors_error(format!(
+                "data of '{}' ends at {} but the buffer holds {} bytes", info.name, info.end, buffer.len()
+            )));
+        }
+        end_of_previous = info.end;
+    }
+
+    let mut tensors = HashMap::new();
+    for info in infos {
+        let data = info.dtype.decode(&buffer[info.begin..info.end]);
+        tensors.insert(info.name, Tensor::<f32>::from_vec(data, &info.shape)?);
+    }
+    Ok(tensors)
+}
+
+#[derive(Clone, Copy)]
+enum Dtype {
+    F64,
+    F32,
+    F16,
+    BF16,
+}
+
+impl Dtype {
+    fn parse(name: &str) -> Option<Self> {
+        match name {
+            "F64" => Some(Dtype::F64),
+            "F32" => Some(Dtype::F32),
+            "F16" => Some(Dtype::F16),
+            "BF16" => Some(Dtype::BF16),
+            _ => None,
+        }
+    }
+
+    fn size(self) -> usize {
+        match self {
+            Dtype::F64 => 8,
+            Dtype::F32 => 4,
+            Dtype::F16 | Dtype::BF16 => 2,
+        }
+    }
+
+    fn decode(self, bytes: &[u8]) -> Vec<f32> {
+        bytes.chunks_exact(self.size()).map(|b| match self {
+            Dtype::F64 => f64::from_le_bytes(b.try_into().unwrap()) as f32,
+            Dtype::F32 => f32::from_le_bytes(b.try_into().unwrap()),
+            Dtype::F16 => f16_to_f32(u16::from_le_bytes([b[0], b[1]])),
+            Dtype::BF16 => f32::from_bits((u16::from_le_bytes([b[0], b[1]]) as u32) << 16),
+        }).collect()
+    }
+}
+
+struct TensorInfo {
+    name: String,
+    dtype: Dtype,
+    shape: Vec<usize>,
+    begin: usize,
+    end: usize,
+}
+
+impl TensorInfo {
+    fn from_json(name: &str, value: &Json) -> MlResult<Self> {
+        let field = |key: &str| value.get(key)
+            .ok_or_else(|| safetensors_error(format!("entry '{}' is missing '{}'", name, key)));
+        let dtype = match field("dtype")? {
+            Json::String(dtype) => Dtype::parse(dtype)
+                .ok_or_else(|| safetensors_error(format!("unsupported dtype '{}' for '{}'", dtype, name)))?,
+            _ => return Err(safetensors_error(format!("invalid dtype for '{}'", name))),
+        };
+        let shape = field("shape")?.as_usize_list()
+            .ok_or_else(|| safetensors_error(format!("invalid shape for '{}'", name)))?;
+        let (begin, end) = match field("data_offsets")?.as_usize_list().as_deref() {
+            Some(&[begin, end]) if begin <= end => (begin, end),
+            _ => return Err(safetensors_error(format!("invalid data_offsets for '{}'", name))),
+        };
+
+        let expected = shape.iter()
+            .try_fold(dtype.size(), |bytes, &d| bytes.checked_mul(d))
+            .ok_or_else(|| MlError::FormatError(FormatError::SizeOverflow { shape: shape.clone() }))?;
+        if end - begin != expected {
+            return Err(safetensors_error(format!(
+                "'{}' has {} bytes of data but its shape {:?} needs {}", name, end - begin, shape, expected
+            )));
+        }
+        Ok(Self { name: name.to_string(), dtype, shape, begin, end })
+    }
+}
+
+/// Converts IEEE 754 half precision bits to `f32`
+fn f16_to_f32(bits: u16) -> f32 {
+    let sign = ((bits >> 15) as u32) << 31;
+    let exponent = ((bits >> 10) & 0x1F) as u32;
+    let mantissa = (bits & 0x3FF) as u32;
+    let magnitude = match exponent {
+        // subnormal halves are exact multiples of 2^-24
+        0 => return (mantissa as f32 * 2f32.powi(-24)).copysign(f32::from_bits(sign | 0x3F80_0000)),
+        0x1F => 0x7F80_0000 | (mantissa << 13),
+        _ => ((exponent + 127 - 15) << 23) | (mantissa << 13),
+    };
+    f32::from_bits(sign | magnitude)
+}
+
+fn json_string(value: &str) -> String {
+    let mut out = String::with_capacity(value.len() + 2);
+    out.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            c if (c as u32) < 0x20 => out.push_str(&format!("\\u{:04x}", c as u32)),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+/// The subset of JSON needed to read a safetensors header
+enum Json {
+    Null,
+    Bool,
+    Number(f64),
+    String(String),
+    Array(Vec<Json>),
+    Object(Vec<(String, Json)>),
+}
+
+impl Json {
+    fn get(&self, key: &str) -> Option<&Json> {
+        match self {
+            Json::Object(entries) => entries.iter().find(|(k, _)| k == key).map(|(_, v)| v),
+            _ => None,
+        }
+    }
+
+    fn as_usize_list(&self) -> Option<Vec<usize>> {
+        match self {
+            Json::Array(items) => items.iter().map(|item| match item {
+                Json::Number(n) if *n >= 0.0 && n.fract() == 0.0 => Some(*n as usize),
+                _ => None,
+            }).collect(),
+            _ => None,
+        }
+    }
+}
+
+struct JsonParser<'a> {
+    text: &'a str,
+    pos: usize,
+    depth: usize,
+}
+
+impl<'a> JsonParser<'a> {
+    fn new(text: &'a str) -> Self {
+        Self { text, pos: 0, depth: 0 }
+    }
+
+    fn parse(&mut self) -> MlResult<Json> {
+        let value = self.value()?;
+        self.skip_whitespace();
+        match self.pos == self.text.len() {
+            true => Ok(value),
+            false => Err(self.error("trailing characters")),
+        }
+    }
+
+    fn value(&mut self) -> MlResult<Json> {
+        self.skip_whitespace();
+        match self.peek() {
+            Some('{') => self.nested(Self::object),
+            Some('[') => self.nested(Self::array),
+            Some('"') => self.string().map(Json::String),
+            Some('t') => self.literal("true", Json::Bool),
+            Some('f') => self.literal("false", Json::Bool),
+            Some('n') => self.literal("null", Json::Null),
+            Some(c) if c == '-' || c.is_ascii_digit() => self.number(),
+            _ => Err(self.error("expected a value")),
+        }
+    }
+
+    /// Parses an array or object, bounding the recursion so a hostile header cannot
+    /// overflow the stack
+    fn nested(&mut self, parse: fn(&mut Self) -> MlResult<Json>) -> MlResult<Json> {
+        if self.depth == MAX_JSON_DEPTH {
+            return Err(self.error("nesting is too deep"));
+        }
+        self.depth += 1;
+        let value = parse(self);
+        self.depth -= 1;
+        value
+    }
+
+    fn object(&mut self) -> MlResult<Json> {
+        self.pos += 1;
+        let mut entries = Vec::new();
+        self.skip_whitespace();
+        if self.eat('}') {
+            return Ok(Json::Object(entries));
+        }
+        loop {
+            self.skip_whitespace();
+            let key = self.string()?;
+            self.skip_whitespace();
+            if !self.eat(':') {
+                return Err(self.error("expected ':'"));
+            }
+            entries.push((key, self.value()?));
+            self.skip_whitespace();
+            if self.eat('}') {
+                return Ok(Json::Object(entries));
+            }
+            if !self.eat(',') {
+                return Err(self.error("expected ',' or '}'"));
+            }
+        }
+    }
+
+    fn array(&mut self) -> MlResult<Json> {
+        self.pos += 1;
+        let mut items = Vec::new();
+        self.skip_whitespace();
+        if self.eat(']') {
+            return Ok(Json::Array(items));
+        }
+        loop {
+            items.push(self.value()?);
+            self.skip_whitespace();
+            if self.eat(']') {
+                return Ok(Json::Array(items));
+            }
+            if !self.eat(',') {
+                return Err(self.error("expected ',' or ']'"));
+            }
+        }
+    }
+
+    fn string(&mut self) -> MlResult<String> {
+        if !self.eat('"') {
+            return Err(self.error("expected a string"));
+        }
+        let mut out = String::new();
+        loop {
+            let c = self.peek().ok_or_else(|| self.error("unterminated string"))?;
+            self.pos += c.len_utf8();
+            match c {
+                '"' => return Ok(out),
+                '\\' => {
+                    let escaped = self.peek().ok_or_else(|| self.error("unterminated string"))?;
+                    self.pos += 1;
+                    match escaped {
+                        '"' | '\\' | '/' => out.push(escaped),
+                        'b' => out.push('\u{8}'),
+                        'f' => out.push('\u{c}'),
+                        'n' => out.push('\n'),
+                        'r' => out.push('\r'),
+                        't' => out.push('\t'),
+                        'u' => {
+                            let code = self.text.get(self.pos..self.pos + 4)
+                                .and_then(|hex| u32::from_str_radix(hex, 16).ok())
+                                .ok_or_else(|| self.error("invalid unicode escape"))?;
+                            self.pos += 4;
+                            out.push(char::from_u32(code).unwrap_or(char::REPLACEMENT_CHARACTER));
+                        }
+                        _ => return Err(self.error("invalid escape")),
+                    }
+                }
+                c => out.push(c),
+            }
+        }
+    }
+
+    fn number(&mut self) -> MlResult<Json> {
+        let start = self.pos;
+        while let Some(c) = self.peek().filter(|c| c.is_ascii_digit() || "+-.eE".contains(*c)) {
+            self.pos += c.len_utf8();
+        }
+        self.text[start..self.pos].parse().map(Json::Number).map_err(|_| self.error("invalid number"))
+    }
+
+    fn literal(&mut self, word: &str, value: Json) -> MlResult<Json> {
+        match self.text[self.pos..].starts_with(word) {
+            true => {
+                self.pos += word.len();
+                Ok(value)
+            }
+            false => Err(self.error("invalid literal")),
+        }
+    }
+
+    fn skip_whitespace(&mut self) {
+        while let Some(c) = self.peek().filter(|c| c.is_ascii_whitespace()) {
+            self.pos += c.len_utf8();
+        }
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.text[self.pos..].chars().next()
+    }
+
+    fn eat(&mut self, c: char) -> bool {
+        match self.peek() == Some(c) {
+            true => {
+                self.pos += 1;
+                true
+            }
+            false => false,
+        }
+    }
+
+    fn error(&self, reason: &str) -> MlError {
+        safetensors_error(format!("malformed header at byte {}: {}", self.pos, reason))
+    }
+}
+
+fn safetensors_error(reason: String) -> MlError {
+    MlError::StringError(format!("invalid .safetensors file: {}", reason))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{MlError, MlResult};
+    use crate::tensor::*;
+    use crate::testing::temp_path;
+
+    fn write_raw(name: &str, header: &str, buffer: &[u8]) -> String {
+        let path = temp_path(name);
+        let mut bytes = (header.len() as u64).to_le_bytes().to_vec();
+        bytes.extend_from_slice(header.as_bytes());
+        bytes.extend_from_slice(buffer);
+        std::fs::write(&path, bytes).unwrap();
+        path
+    }
+
+    #[test]
+    fn test_safetensors_round_trip() -> MlResult<()> {
+        let path = temp_path("weights.safetensors");
+        let weight = Tensor::<f32>::from_vec(vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0], &[2, 3])?;
+        let bias = Tensor::<f32>::from_vec(vec![-0.5, 0.5], &[2])?;
+        let scale = Tensor::<f32>::scalar(3.0);
+        save_safetensors(&path, &[("layer.weight", &*weight), ("layer.bias", &*bias), ("scale\"", &*scale)])?;
+
+        let header_len = u64::from_le_bytes(std::fs::read(&path).unwrap()[..8].try_into().unwrap());
+        assert_eq!(header_len % 8, 0);
+
+        let loaded = load_safetensors(&path)?;
+        assert_eq!(loaded.len(), 3);
+        assert_eq!(loaded["layer.weight"].shape(), &[2, 3]);
+        assert_eq!(loaded["layer.weight"].data(), weight.data());
+        assert_eq!(loaded["layer.bias"].data(), bias.data());
+        assert!(loaded["scale\""].shape().is_empty());
+        std::fs::remove_file(&path).unwrap();
+
+        assert!(save_safetensors(&path, &[("bias", &*bias), ("bias", &*weight)]).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_load_python_safetensors_fixture() -> MlResult<()> {
+        let loaded = load_safetensors(&format!("{}/testdata/model.safetensors", env!("CARGO_MANIFEST_DIR")))?;
+
+        assert_eq!(loaded.len(), 2);
+        assert_eq!(loaded["weight"].shape(), &[2, 2]);
+        assert_eq!(loaded["weight"].data(), &[1.0, -2.0, 0.5, 4.0]);
+        assert_eq!(loaded["half"].shape(), &[3]);
+        assert_eq!(loaded["half"].data(), &[1.5, -0.25, 65504.0]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_safetensors_invalid_offsets() {
+        let buffer = [0u8; 16];
+        let overlapping = write_raw(
+            "overlap.safetensors",
+            r#"{"a":{"dtype":"F32","shape":[2],"data_offsets":[0,8]},"b":{"dtype":"F32","shape":[2],"data_offsets":[4,12]}}"#,
+            &buffer,
+        );
+        let message = load_safetensors(&overlapping).err().unwrap().to_string();
+        assert!(message.contains("overlaps"));
+
+        let out_of_bounds = write_raw(
+            "bounds.safetensors",
+            r#"{"a":{"dtype":"F32","shape":[8],"data_offsets":[0,32]}}"#,
+            &buffer,
+        );
+        assert!(load_safetensors(&out_of_bounds).is_err());
+
+        let wrong_size = write_raw(
+            "size.safetensors",
+            r#"{"a":{"dtype":"F16","shape":[3],"data_offsets":[0,8]}}"#,
+            &buffer,
+        );
+        assert!(load_safetensors(&wrong_size).is_err());
+
+        let unsupported = write_raw(
+            "dtype.safetensors",
+            r#"{"a":{"dtype":"I64","shape":[2],"data_offsets":[0,16]}}"#,
+            &buffer,
+        );
+        assert!(load_safetensors(&unsupported).err().unwrap().to_string().contains("'I64'"));
+
+        let overflowing = write_raw(
+            "overflow.safetensors",
+            r#"{"a":{"dtype":"F32","shape":[4294967296,4294967296],"data_offsets":[0,0]}}"#,
+            &buffer,
+        );
+        assert!(matches!(
+            load_safetensors(&overflowing),
+            Err(MlError::FormatError(FormatError::SizeOverflow { .. }))
+        ));
+
+        let deep = write_raw("deep.safetensors", &"[".repeat(100_000), &buffer);
+        assert!(load_safetensors(&deep).err().unwrap().to_string().contains("nesting is too deep"));
+
+        for path in [overlapping, out_of_bounds, wrong_size, unsupported, overflowing, deep] {
+            std::fs::remove_file(path).unwrap();
+        }
+    }
+}
@@ -0,0 +1,100 @@
+use std::fmt::Debug;
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::tensor::{ArcTensor, Tensor, TensorBase};
+
+/// Serialized form of a tensor; gradients and the grad function are not stored
+#[derive(Serialize)]
+struct TensorRef<'a, T> {
+    data: &'a [T],
+    shape: &'a [usize],
+    requires_grad: bool,
+}
+
+#[derive(Deserialize)]
+struct TensorData<T> {
+    data: Vec<T>,
+    shape: Vec<usize>,
+    requires_grad: bool,
+}
+
+fn serialize_tensor<T: Debug + Serialize + 'static, S: Serializer>(tensor: &dyn TensorBase<T>, serializer: S) -> Result<S::Ok, S::Error> {
+    TensorRef {
+        data: tensor.data(),
+        shape: tensor.shape(),
+        requires_grad: tensor.requires_grad(),
+    }.serialize(serializer)
+}
+
+impl<T: Debug + Serialize + 'static> Serialize for Tensor<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serialize_tensor(self, serializer)
+    }
+}
+
+impl<T: Debug + Serialize + 'static> Serialize for ArcTensor<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serialize_tensor(&**self, serializer)
+    }
+}
+
+/// Validates that the data length matches the shape, reporting `InvalidDataLength` otherwise
+impl<'de, T: Debug + Deserialize<'de> + 'static> Deserialize<'de> for Tensor<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let raw = TensorData::<T>::deserialize(deserializer)?;
+        let mut tensor = Tensor::<T>::from_vec_owned(raw.data, &raw.shape).map_err(serde::de::Error::custom)?;
+        tensor.requires_grad = raw.requires_grad;
+        Ok(tensor)
+    }
+}
+
+impl<'de, T: Debug + Deserialize<'de> + 'static> Deserialize<'de> for ArcTensor<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Tensor::<T>::deserialize(deserializer).map(ArcTensor::new)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::MlResult;
+    use crate::tensor::*;
+
+    #[test]
+    fn test_serde_json_round_trip() -> MlResult<()> {
+        let tensor = Tensor::<f32>::from_vec(vec![1.0, 2.5, -3.0, 4.0], &[2, 2])?;
+        let json = serde_json::to_string(&tensor).unwrap();
+        let restored: ArcTensor<f32> = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored.shape(), &[2, 2]);
+        assert_eq!(restored.data(), tensor.data());
+        assert_eq!(restored.requires_grad(), tensor.requires_grad());
+
+        // a zero-dimensional tensor holds a single element
+        let owned: Tensor<usize> = serde_json::from_str(r#"{"data":[7],"shape":[],"requires_grad":false}"#).unwrap();
+        assert_eq!(owned.data(), &[7]);
+        assert_eq!(owned.shape().len(), 0);
+        assert_eq!(serde_json::to_string(&owned).unwrap(), r#"{"data":[7],"shape":[],"requires_grad":false}"#);
+        Ok(())
+    }
+
+    #[test]
+    fn test_bincode_round_trip() -> MlResult<()> {
+        let tensor = Tensor::<f32>::from_vec(vec![0.5; 6], &[1, 2, 3])?;
+        let bytes = bincode::serialize(&tensor).unwrap();
+        let restored: Tensor<f32> = bincode::deserialize(&bytes).unwrap();
+        assert_eq!(restored.shape(), &[1, 2, 3]);
+        assert_eq!(restored.data(), tensor.data());
+
+        let empty = Tensor::<f32>::from_vec(vec![], &[0])?;
+        let restored: ArcTensor<f32> = bincode::deserialize(&bincode::serialize(&empty).unwrap()).unwrap();
+        assert_eq!(restored.shape(), &[0]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_deserialize_invalid_length() {
+        let result = serde_json::from_str::<Tensor<f32>>(r#"{"data":[1.0,2.0,3.0],"shape":[2,2],"requires_grad":false}"#);
+        let message = result.err().unwrap().to_string();
+        assert!(message.contains("Invalid data length"));
+    }
+}
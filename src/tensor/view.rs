@@ -0,0 +1,93 @@
+use std::fmt::Debug;
+use std::sync::Arc;
+
+use crate::{MlError, MlResult};
+use crate::tensor::{ArcTensor, Tensor, TensorBase, TensorError};
+#[cfg(feature = "enable_backpropagation")]
+use crate::tensor::GradFn;
+
+/// A tensor that reads its elements from another tensor under a different shape
+///
+/// Created by `ArcTensor::reinterpret`; the source is kept alive by the `Arc`.
+pub(crate) struct ReshapedView<T: Debug + 'static> {
+    source: Arc<dyn TensorBase<T>>,
+    shape: Vec<usize>,
+
+    #[cfg(feature = "enable_backpropagation")]
+    grad_fn: Option<Arc<dyn GradFn<T>>>,
+}
+
+impl<T: Debug + 'static> ReshapedView<T> {
+    pub(crate) fn new(source: Arc<dyn TensorBase<T>>, shape: &[usize]) -> Self {
+        Self {
+            source,
+            shape: shape.to_vec(),
+
+            #[cfg(feature = "enable_backpropagation")]
+            grad_fn: None,
+        }
+    }
+}
+
+impl<T: Debug + 'static> TensorBase<T> for ReshapedView<T> {
+    fn new(data: Vec<Vec<T>>) -> MlResult<ArcTensor<T>> {
+        Tensor::<T>::new(data)
+    }
+
+    fn from_vec(data: Vec<T>, shape: &[usize]) -> MlResult<ArcTensor<T>> {
+        Tensor::<T>::from_vec(data, shape)
+    }
+
+    fn shape(&self) -> &[usize] {
+        &self.shape
+    }
+
+    fn data(&self) -> &[T] {
+        self.source.data()
+    }
+
+    fn get(&self, indices: &[usize]) -> Option<&T> {
+        self.data().get(self.index(indices)?)
+    }
+
+    fn index(&self, indices: &[usize]) -> Option<usize> {
+        if indices.len() != self.shape.len() {
+            return None;
+        }
+        indices.iter().zip(&self.shape).try_fold(0, |acc, (&i, &dim)| (i < dim).then_some(acc * dim + i))
+    }
+
+    fn chk_shape(&self, other: &dyn TensorBase<T>) -> MlResult<()> {
+        if self.shape != other.shape() {
+            return Err(MlError::TensorError(TensorError::InvalidShape {
+                expected: self.shape.to_vec(),
+                got: other.shape().to_vec(),
+            }));
+        }
+        Ok(())
+    }
+
+    fn requires_grad(&self) -> bool {
+        self.source.requires_grad()
+    }
+
+    #[cfg(feature = "enable_backpropagation")]
+    fn set_grad_fn(&mut self, grad_fn: Box<dyn GradFn<T>>) {
+        self.grad_fn = Some(Arc::from(grad_fn));
+    }
+
+    #[cfg(feature = "enable_backpropagation")]
+    fn grad_fn(&self) -> Option<Arc<dyn GradFn<T>>> {
+        self.grad_fn.clone()
+    }
+
+    #[cfg(feature = "enable_backpropagation")]
+    fn grad(&self) -> Option<&dyn TensorBase<T>> {
+        None
+    }
+
+    #[cfg(feature = "enable_backpropagation")]
+    fn shared_data(&self) -> Arc<Vec<T>> {
+        self.source.shared_data()
+    }
+}
@@ -0,0 +1,238 @@
+//! Assertions and fixtures for testing code built on tensors.
+//!
+//! The macros are exported at the crate root and re-exported here, so both
+//! `MIT::assert_tensor_close!` and `MIT::testing::assert_tensor_close!` work.
+
+use crate::tensor::{ArcTensor, AsTensor, Rng, Tensor, TensorBase};
+
+pub use crate::{assert_shape_eq, assert_tensor_close};
+
+/// The number of mismatching elements listed in a failure message
+const MAX_REPORTED: usize = 5;
+
+/// Asserts that two tensors have the same shape and element-wise close values
+///
+/// Two values are close if `|actual - expected| <= atol + rtol * |expected|`, the rule
+/// of NumPy's `isclose`. Equal infinities are close, NaN is never close. Without
+/// tolerances, `rtol = 1e-5` and `atol = 1e-8` are used.
+///
+/// # Panics
+/// Panics if the shapes differ or any element is not close, listing the first
+/// mismatching indices with both values.
+///
+/// # Examples
+/// ```
+/// use MIT::prelude::*;
+///
+/// let a = Tensor::<f32>::from_vec(vec![1.0, 2.0], &[2]).unwrap();
+/// let b = Tensor::<f32>::from_vec(vec![1.0, 2.0 + 1e-7], &[2]).unwrap();
+/// assert_tensor_close!(a, b);
+/// assert_tensor_close!(a, b, 0.0, 1e-6);
+/// ```
+#[macro_export]
+macro_rules! assert_tensor_close {
+    ($actual:expr, $expected:expr) => {
+        $crate::assert_tensor_close!($actual, $expected, 1e-5, 1e-8)
+    };
+
+    ($actual:expr, $expected:expr, $rtol:expr, $atol:expr) => {
+        if let Err(message) = $crate::testing::check_close(&$actual, &$expected, $rtol, $atol) {
+            panic!("{}", message);
+        }
+    };
+}
+
+/// Asserts that a tensor has the given shape
+///
+/// # Examples
+/// ```
+/// use MIT::prelude::*;
+///
+/// let a = Tensor::<f32>::from_vec(vec![0.0; 6], &[2, 3]).unwrap();
+/// assert_shape_eq!(a, [2, 3]);
+/// ```
+#[macro_export]
+macro_rules! assert_shape_eq {
+    ($tensor:expr, $shape:expr) => {{
+        let expected: &[usize] = &$shape;
+        let actual = $crate::tensor::AsTensor::<f32>::as_tensor(&$tensor).shape();
+        if actual != expected {
+            panic!("shape mismatch: expected {:?}, got {:?}", expected, actual);
+        }
+    }};
+}
+
+/// Compares two tensors with the rule of [`assert_tensor_close!`]
+///
+/// # Returns
+/// * `Ok(())` if the shapes match and every element is close
+/// * `Err(String)` with a description of the mismatch otherwise
+pub fn check_close(
+    actual: &(impl AsTensor<f32> + ?Sized),
+    expected: &(impl AsTensor<f32> + ?Sized),
+    rtol: f32,
+    atol: f32,
+) -> Result<(), String> {
+    let (actual, expected) = (actual.as_tensor(), expected.as_tensor());
+    if actual.shape() != expected.shape() {
+        return Err(format!("shape mismatch: expected {:?}, got {:?}", expected.shape(), actual.shape()));
+    }
+
+    let mismatches: Vec<_> = actual.indexed_iter()
+        .zip(expected.data())
+        .filter(|((_, &a), &e)| !is_close(a, e, rtol, atol))
+        .collect();
+    if mismatches.is_empty() {
+        return Ok(());
+    }
+
+    let mut message = format!(
+        "tensors are not close (rtol={}, atol={}): {} of {} elements differ",
+        rtol, atol, mismatches.len(), actual.data().len()
+    );
+    for ((index, a), e) in mismatches.iter().take(MAX_REPORTED) {
+        message.push_str(&format!("\n  at {:?}: got {}, expected {} (diff {})", index, a, e, (*a - *e).abs()));
+    }
+    if mismatches.len() > MAX_REPORTED {
+        message.push_str(&format!("\n  ... and {} more", mismatches.len() - MAX_REPORTED));
+    }
+    Err(message)
+}
+
+/// Asserts that a tensor has `expected_shape` and holds `expected_data` within `tol`
+///
+/// The shape is checked first, then each element against `|actual - expected| <= tol`.
+/// Useful in place of `assert_eq!(t.data(), ...)`, which ignores the shape and
+/// compares floats exactly.
+///
+/// # Panics
+/// Panics if `expected_data` does not fit `expected_shape`, the shapes differ, or any
+/// element is not close, listing the first mismatching indices with both values.
+///
+/// # Examples
+/// ```
+/// use MIT::prelude::*;
+/// use MIT::testing::assert_close;
+///
+/// let a = Tensor::<f32>::from_vec(vec![0.1 + 0.2, 1.0], &[2, 1]).unwrap();
+/// assert_close(&*a, &[0.3, 1.0], &[2, 1], 1e-6);
+/// ```
+#[track_caller]
+pub fn assert_close(tensor: &dyn TensorBase<f32>, expected_data: &[f32], expected_shape: &[usize], tol: f32) {
+    let expected = match Tensor::<f32>::from_vec(expected_data.to_vec(), expected_shape) {
+        Ok(expected) => expected,
+        Err(_) => panic!("{} expected values do not fit the expected shape {:?}", expected_data.len(), expected_shape),
+    };
+    if let Err(message) = check_close(tensor, &expected, 0.0, tol) {
+        panic!("{}", message);
+    }
+}
+
+fn is_close(actual: f32, expected: f32, rtol: f32, atol: f32) -> bool {
+    actual == expected || (actual - expected).abs() <= atol + rtol * expected.abs()
+}
+
+/// Creates a tensor of values drawn uniformly from `[-1, 1)` with a seeded [`Rng`]
+pub fn random_tensor(shape: &[usize], seed: u64) -> ArcTensor<f32> {
+    let mut rng = Rng::new(seed);
+    let data = (0..shape.iter().product()).map(|_| rng.uniform(-1.0, 1.0)).collect();
+    Tensor::<f32>::from_vec(data, shape).expect("data always matches the shape")
+}
+
+/// Creates a tensor holding `0, 1, 2, ...` in row-major order
+pub fn sequential_tensor(shape: &[usize]) -> ArcTensor<f32> {
+    let data = (0..shape.iter().product::<usize>()).map(|x| x as f32).collect();
+    Tensor::<f32>::from_vec(data, shape).expect("data always matches the shape")
+}
+
+/// Returns a path in the system temp directory for a file named `name`
+///
+/// The process id is part of the file name, so concurrent test runs do not overwrite
+/// each other's files.
+pub fn temp_path(name: &str) -> String {
+    std::env::temp_dir().join(format!("mit_{}_{}", std::process::id(), name)).to_string_lossy().into_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::panic::{catch_unwind, AssertUnwindSafe};
+
+    use crate::tensor::*;
+    use super::*;
+
+    fn panic_message(f: impl FnOnce()) -> String {
+        let payload = catch_unwind(AssertUnwindSafe(f)).expect_err("the assertion should fail");
+        match payload.downcast::<String>() {
+            Ok(message) => *message,
+            Err(payload) => payload.downcast_ref::<&str>().unwrap().to_string(),
+        }
+    }
+
+    #[test]
+    fn test_assert_tensor_close() {
+        let a = sequential_tensor(&[2, 3]);
+        let b = Tensor::<f32>::from_vec(vec![0.0, 1.0, 2.0 + 1e-6, 3.0, 4.0, 5.0], &[2, 3]).unwrap();
+
+        assert_tensor_close!(a, b);
+        assert_tensor_close!(&*a, &*b, 0.0, 1e-5);
+        let inf = Tensor::<f32>::from_vec(vec![f32::INFINITY], &[1]).unwrap();
+        assert_tensor_close!(inf, inf);
+        assert!(check_close(&a, &b, 0.0, 0.0).is_err());
+        let nan = Tensor::<f32>::from_vec(vec![f32::NAN], &[1]).unwrap();
+        assert!(check_close(&nan, &nan, 1.0, 1.0).is_err());
+    }
+
+    #[test]
+    fn test_assert_close() {
+        let a = sequential_tensor(&[2, 2]);
+        assert_close(&*a, &[0.0, 1.0, 2.0, 3.0 + 1e-4], &[2, 2], 1e-3);
+
+        let message = panic_message(|| assert_close(&*a, &[0.0, 1.0, 2.0, 3.0], &[4], 1e-3));
+        assert!(message.contains("shape mismatch: expected [4], got [2, 2]"), "{}", message);
+        let message = panic_message(|| assert_close(&*a, &[0.0, 1.0, 2.5, 3.0], &[2, 2], 1e-3));
+        assert!(message.contains("at [1, 0]: got 2, expected 2.5"), "{}", message);
+        let message = panic_message(|| assert_close(&*a, &[0.0, 1.0], &[2, 2], 1e-3));
+        assert!(message.contains("do not fit"), "{}", message);
+    }
+
+    #[test]
+    fn test_assert_tensor_close_message() {
+        let a = sequential_tensor(&[2, 4]);
+        let b = Tensor::<f32>::from_vec(vec![0.0, 1.5, 2.0, 3.0, 4.0, 5.0, 6.0, 9.0], &[2, 4]).unwrap();
+        let message = panic_message(|| assert_tensor_close!(a, b, 1e-5, 1e-3));
+
+        assert!(message.contains("2 of 8 elements differ"), "{}", message);
+        assert!(message.contains("at [0, 1]: got 1, expected 1.5"), "{}", message);
+        assert!(message.contains("at [1, 3]: got 7, expected 9"), "{}", message);
+
+        let many = Tensor::<f32>::from_vec(vec![-1.0; 8], &[2, 4]).unwrap();
+        let message = panic_message(|| assert_tensor_close!(a, many));
+        assert_eq!(message.lines().count(), 1 + MAX_REPORTED + 1);
+        assert!(message.ends_with("... and 3 more"), "{}", message);
+
+        let flat = sequential_tensor(&[8]);
+        let message = panic_message(|| assert_tensor_close!(a, flat));
+        assert_eq!(message, "shape mismatch: expected [8], got [2, 4]");
+    }
+
+    #[test]
+    fn test_assert_shape_eq() {
+        let a = random_tensor(&[3, 2], 0);
+        assert_shape_eq!(a, [3, 2]);
+
+        let message = panic_message(|| assert_shape_eq!(a, [2, 3]));
+        assert_eq!(message, "shape mismatch: expected [2, 3], got [3, 2]");
+    }
+
+    #[test]
+    fn test_fixtures() {
+        let a = random_tensor(&[4, 5], 42);
+        assert_eq!(a.shape(), &[4, 5]);
+        assert!(a.data().iter().all(|x| (-1.0..1.0).contains(x)));
+        assert_eq!(a.data(), random_tensor(&[4, 5], 42).data());
+        assert_ne!(a.data(), random_tensor(&[4, 5], 43).data());
+
+        assert_eq!(sequential_tensor(&[2, 2]).data(), &[0.0, 1.0, 2.0, 3.0]);
+        assert_eq!(sequential_tensor(&[]).data(), &[0.0]);
+    }
+}